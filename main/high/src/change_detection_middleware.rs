@@ -1,25 +1,48 @@
 use crate::{
-    get_media_track_guid, ControlSurfaceEvent, Fx, FxParameter, Guid, Project, Reaper, Track,
-    TrackRoute,
+    get_index_from_query_index, get_media_track_guid, BookmarkType, ControlSurfaceEvent, Fx,
+    FxChain, FxParameter, Guid, Item, Project, Reaper, Take, Track, TrackRoute,
 };
 use reaper_medium::ProjectContext::{CurrentProject, Proj};
 use reaper_medium::{
-    reaper_str, AutomationMode, Bpm, ExtSetFxParamArgs, GlobalAutomationModeOverride,
-    InputMonitoringMode, MediaTrack, Pan, PanMode, PlayState, PlaybackSpeedFactor, ReaProject,
+    reaper_str, AutomationMode, BookmarkId, Bpm, DurationInSeconds, ExtSetFxParamArgs,
+    GetFocusedFxResult, GetLastMarkerAndCurRegionResult, GetLastTouchedFxResult,
+    GetLoopTimeRange2Result, GetTempoTimeSigMarkerResult, GlobalAutomationModeOverride,
+    InputMonitoringMode, ItemAttributeKey, MediaItem, MediaItemTake, MediaTrack, NativeColor, Pan,
+    PanMode, PlayState, PlaybackSpeedFactor, PositionInSeconds, ReaProject,
     ReaperNormalizedFxParamValue, ReaperPanValue, ReaperStr, ReaperVersion, ReaperVolumeValue,
-    TrackAttributeKey, TrackFxChainType, TrackLocation, TrackSendCategory, TrackSendDirection,
-    VersionDependentFxLocation, VersionDependentTrackFxLocation,
+    ReaperWidthValue, TimeSignature, TrackArea, TrackAttributeKey, TrackFxChainType, TrackLocation,
+    TrackSendCategory, TrackSendDirection, VersionDependentFxLocation,
+    VersionDependentTrackFxLocation,
 };
 use std::cell::{Cell, RefCell, RefMut};
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 #[derive(Debug)]
 pub struct ChangeDetectionMiddleware {
     num_track_set_changes_left_to_be_propagated: Cell<u32>,
     last_active_project: Cell<Project>,
+    // In tab order
+    project_tabs: RefCell<Vec<ReaProject>>,
+    // REAPER only reports play state changes of the current project
+    play_states: RefCell<HashMap<ReaProject, PlayState>>,
     last_global_automation_mode_override: Cell<Option<GlobalAutomationModeOverride>>,
     project_datas: RefCell<ProjectDataMap>,
+    item_datas: RefCell<HashMap<ReaProject, ProjectItemData>>,
+    bookmark_datas: RefCell<HashMap<ReaProject, BookmarkDataMap>>,
+    tempo_map_datas: RefCell<HashMap<ReaProject, TempoMapData>>,
     fx_has_been_touched_just_a_moment_ago: Cell<bool>,
+    // REAPER doesn't notify us about take FX parameter changes, so we poll all of them
+    take_fx_param_values: RefCell<HashMap<TakeFxParamKey, ReaperNormalizedFxParamValue>>,
+    // Project and its state change count at the time of the last take FX parameter scan
+    last_take_fx_param_scan: Cell<Option<(ReaProject, u32)>>,
+    // Take, FX index and parameter index of the last touched take FX parameter
+    last_touched_take_fx_param: Cell<Option<(MediaItemTake, u32, u32)>>,
+    position_data: Cell<Option<PositionData>>,
+    position_change_thresholds: Cell<PositionChangeThresholds>,
+    cycles_since_last_position_check: Cell<u32>,
+    track_attribute_watch: RefCell<TrackAttributeWatch>,
     // Capabilities depending on REAPER version
     supports_detection_of_input_fx: bool,
     supports_detection_of_input_fx_in_set_fx_change: bool,
@@ -27,6 +50,9 @@ pub struct ChangeDetectionMiddleware {
 
 type ProjectDataMap = HashMap<ReaProject, TrackDataMap>;
 type TrackDataMap = HashMap<MediaTrack, TrackData>;
+// Bookmark IDs are only unique within one bookmark type. Ordered so that change events are emitted
+// in a deterministic order (markers first, then regions, each one by ID).
+type BookmarkDataMap = BTreeMap<(BookmarkType, BookmarkId), BookmarkData>;
 
 /// Keeps current track values for detecting real value changes.
 ///
@@ -128,6 +154,107 @@ struct FxChainPair {
     output_fx_guids: HashSet<Guid>,
 }
 
+/// Differences between two lists of project tabs.
+#[derive(PartialEq, Debug, Default)]
+struct ProjectTabChanges {
+    closed: Vec<ReaProject>,
+    opened: Vec<ReaProject>,
+    // Project, old index, new index
+    reordered: Vec<(ReaProject, u32, u32)>,
+}
+
+/// For detection of item and take changes.
+#[derive(Debug, Default)]
+struct ProjectItemData {
+    last_state_change_count: Option<u32>,
+    track_item_hashes: HashMap<MediaTrack, u64>,
+    items: HashMap<MediaItem, ItemData>,
+}
+
+#[derive(Debug)]
+struct ItemData {
+    guid: Guid,
+    track: MediaTrack,
+    position: PositionInSeconds,
+    length: DurationInSeconds,
+    selected: bool,
+    active_take: Option<MediaItemTake>,
+    take_names: Vec<(MediaItemTake, String)>,
+    take_fx_guids: Vec<(MediaItemTake, Vec<Guid>)>,
+}
+
+/// For detection of marker and region changes.
+#[derive(Debug)]
+struct BookmarkData {
+    name: String,
+    position: PositionInSeconds,
+    region_end_position: Option<PositionInSeconds>,
+    color: NativeColor,
+}
+
+/// For detection of tempo map changes.
+#[derive(Debug, Default)]
+struct TempoMapData {
+    last_state_change_count: Option<u32>,
+    markers: Vec<GetTempoTimeSigMarkerResult>,
+    // Project tempo and time signature, relevant if there are no markers
+    start_tempo: Option<(Bpm, TimeSignature)>,
+    time_bases: Vec<i32>,
+}
+
+/// For opt-in detection of track attribute changes which are not reported by REAPER.
+#[derive(Debug, Default)]
+struct TrackAttributeWatch {
+    keys: Vec<TrackAttributeKey<'static>>,
+    check_interval: u32,
+    cycles_since_last_check: u32,
+    // Values in the same order as the keys
+    values: HashMap<MediaTrack, Vec<f64>>,
+}
+
+/// For detection of cursor and time range changes.
+///
+/// Contains the last reported values, not the last polled ones, so that slow movements below the
+/// threshold are still reported eventually.
+#[derive(Copy, Clone, Debug)]
+struct PositionData {
+    project: Project,
+    edit_cursor_position: PositionInSeconds,
+    time_selection: Option<GetLoopTimeRange2Result>,
+    loop_points: Option<GetLoopTimeRange2Result>,
+    current_bookmark: GetLastMarkerAndCurRegionResult,
+}
+
+/// Thresholds for the polling-based detection of cursor and time range changes.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PositionChangeThresholds {
+    /// Minimum distance the edit cursor must move before `EditCursorMoved` is emitted.
+    pub edit_cursor_distance: DurationInSeconds,
+    /// Minimum distance the start or end of the time selection or loop points must move before
+    /// `TimeSelectionChanged` or `LoopPointsChanged` is emitted.
+    pub time_range_distance: DurationInSeconds,
+    /// Number of `run()` invocations between two checks. 1 means checking in each cycle.
+    pub check_interval: u32,
+}
+
+impl Default for PositionChangeThresholds {
+    fn default() -> Self {
+        PositionChangeThresholds {
+            edit_cursor_distance: DurationInSeconds::new(0.001),
+            time_range_distance: DurationInSeconds::new(0.001),
+            check_interval: 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct TakeFxParamKey {
+    take: MediaItemTake,
+    // GUID instead of index so that reordering FX doesn't look like parameter value changes
+    fx_guid: Guid,
+    param_index: u32,
+}
+
 #[derive(Eq, PartialEq, Hash, Debug)]
 struct TrackFxKey {
     is_input_fx: bool,
@@ -149,11 +276,23 @@ impl Default for ChangeDetectionMiddleware {
         ChangeDetectionMiddleware {
             num_track_set_changes_left_to_be_propagated: Default::default(),
             last_active_project: Cell::new(last_active_project),
+            project_tabs: RefCell::new(capture_project_tabs()),
+            play_states: Default::default(),
             last_global_automation_mode_override: Cell::new(
                 Reaper::get().global_automation_override(),
             ),
             project_datas: Default::default(),
+            item_datas: Default::default(),
+            bookmark_datas: Default::default(),
+            tempo_map_datas: Default::default(),
             fx_has_been_touched_just_a_moment_ago: Default::default(),
+            take_fx_param_values: Default::default(),
+            last_take_fx_param_scan: Default::default(),
+            last_touched_take_fx_param: Default::default(),
+            position_data: Default::default(),
+            position_change_thresholds: Default::default(),
+            cycles_since_last_position_check: Default::default(),
+            track_attribute_watch: Default::default(),
             // since pre1,
             supports_detection_of_input_fx: version >= reaper_version_5_95,
             // since pre2 to be accurate but so what
@@ -179,6 +318,51 @@ impl ChangeDetectionMiddleware {
         self.react_to_track_list_change(Reaper::get().current_project(), handle_change);
     }
 
+    pub fn set_position_change_thresholds(&self, thresholds: PositionChangeThresholds) {
+        self.position_change_thresholds.set(thresholds);
+    }
+
+    /// Enables periodic detection of changes of the given track attributes.
+    ///
+    /// REAPER doesn't notify control surfaces about changes of e.g. track color, visibility or
+    /// folder state. With this, the attributes of all tracks in the current project (except the
+    /// master track) are compared every `check_interval` invocations of `run()`. Only numeric
+    /// attributes are supported. Pass an empty list to disable it again.
+    pub fn watch_track_attributes(
+        &self,
+        keys: impl IntoIterator<Item = TrackAttributeKey<'static>>,
+        check_interval: u32,
+    ) {
+        self.track_attribute_watch.replace(TrackAttributeWatch {
+            keys: keys.into_iter().collect(),
+            check_interval,
+            ..Default::default()
+        });
+    }
+
+    /// Detects changes which are not reported by control surface callbacks, e.g. item changes.
+    ///
+    /// Should be called in each main loop cycle, e.g. in `ControlSurfaceMiddleware::run()`. It's
+    /// cheap as long as the project state change counts don't change. Item and tempo map changes
+    /// are detected in all open projects, not just in the current one. REAPER doesn't report take
+    /// FX parameter changes at all, so the parameter values of all take FX in the current project
+    /// are polled whenever its state change count has changed. If just the last touched take FX
+    /// parameter has changed, only that one is polled. Changes caused by take FX parameter
+    /// automation alone are not detected.
+    pub fn run(&self, handle_change: impl FnMut(ChangeEvent) + Copy) {
+        let current_project = Reaper::get().current_project();
+        self.detect_project_tab_changes(handle_change);
+        self.detect_background_play_state_changes(current_project, handle_change);
+        for project in Reaper::get().projects() {
+            self.detect_item_changes(project, handle_change);
+            self.detect_tempo_map_changes(project, handle_change);
+        }
+        // The following ones are only relevant for the current project
+        self.detect_take_fx_param_changes(current_project, handle_change);
+        self.detect_position_changes(current_project, handle_change);
+        self.detect_track_attribute_changes(current_project, handle_change);
+    }
+
     pub fn process(
         &self,
         event: ControlSurfaceEvent,
@@ -477,8 +661,36 @@ impl ChangeDetectionMiddleware {
                 };
                 use VersionDependentFxLocation::*;
                 match fx_ref.fx_location {
-                    TakeFx { .. } => {
-                        // TODO Not handled right now
+                    TakeFx {
+                        item_index,
+                        fx_index,
+                    } => {
+                        // REAPER doesn't tell us the take index here, but GetFocusedFX does
+                        let reaper = Reaper::get().medium_reaper();
+                        let take_index = match reaper.get_focused_fx() {
+                            Some(GetFocusedFxResult::TakeFx {
+                                item_index: focused_item_index,
+                                take_index,
+                                fx_index: focused_fx_index,
+                                ..
+                            }) if focused_item_index == item_index
+                                && focused_fx_index == fx_index =>
+                            {
+                                take_index
+                            }
+                            _ => return true,
+                        };
+                        let fx = unsafe {
+                            reaper
+                                .get_track_media_item(fx_ref.track, item_index)
+                                .and_then(|raw_item| reaper.get_take(raw_item, take_index))
+                                .and_then(|raw_take| {
+                                    Take::new(raw_take).fx_chain().fx_by_index(fx_index)
+                                })
+                        };
+                        if let Some(fx) = fx {
+                            handle_change(ChangeEvent::FxFocused(FxFocusedEvent { fx: Some(fx) }));
+                        }
                     }
                     TrackFx(track_fx_ref) => {
                         // Unfortunately, we don't have a ReaProject* here. Therefore we pass a
@@ -586,19 +798,26 @@ impl ChangeDetectionMiddleware {
             }
             ExtTrackFxPresetChanged(args) => {
                 let track = Track::new(args.track, None);
-                let fx = track
-                    .fx_by_query_index(args.fx_location.to_raw())
+                let (index, is_input_fx) = get_index_from_query_index(args.fx_location.to_raw());
+                let fx = track_fx_chain(&track, is_input_fx)
+                    .fx_by_index(index)
                     .expect("preset changed but FX not found");
                 handle_change(ChangeEvent::FxPresetChanged(FxPresetChangedEvent { fx }));
             }
             SetPlayState(args) => {
+                let project = Reaper::get().current_project();
+                let new_value = PlayState {
+                    is_playing: args.is_playing,
+                    is_paused: args.is_paused,
+                    is_recording: args.is_recording,
+                };
+                // Keep it up-to-date for when the project is moved to the background
+                self.play_states
+                    .borrow_mut()
+                    .insert(project.raw(), new_value);
                 handle_change(ChangeEvent::PlayStateChanged(PlayStateChangedEvent {
-                    project: Reaper::get().current_project(),
-                    new_value: PlayState {
-                        is_playing: args.is_playing,
-                        is_paused: args.is_paused,
-                        is_recording: args.is_recording,
-                    },
+                    project,
+                    new_value,
                 }));
             }
             SetRepeatState(args) => {
@@ -608,9 +827,14 @@ impl ChangeDetectionMiddleware {
                 }));
             }
             ExtSetProjectMarkerChange(_) => {
+                let project = Reaper::get().current_project();
                 handle_change(ChangeEvent::BookmarksChanged(BookmarksChangedEvent {
-                    project: Reaper::get().current_project()
+                    project
                 }));
+                // REAPER doesn't tell us which project is affected, could be a background one
+                for p in Reaper::get().projects() {
+                    self.detect_bookmark_changes(p, handle_change);
+                }
             }
             CloseNoReset |
             SetAutoMode(_) |
@@ -639,14 +863,13 @@ impl ChangeDetectionMiddleware {
         match parm_fx_index {
             Old(index) => {
                 let is_input_fx = self.is_probably_input_fx(track, index, param_index, param_value);
-                let fx_chain = if is_input_fx {
-                    track.input_fx_chain()
-                } else {
-                    track.normal_fx_chain()
-                };
+                let fx_chain = track_fx_chain(track, is_input_fx);
                 fx_chain.fx_by_index(index)
             }
-            New(fx_ref) => track.fx_by_query_index(fx_ref.to_raw()),
+            New(fx_ref) => {
+                let (index, is_input_fx) = get_index_from_query_index(fx_ref.to_raw());
+                track_fx_chain(track, is_input_fx).fx_by_index(index)
+            }
         }
     }
 
@@ -793,11 +1016,7 @@ impl ChangeDetectionMiddleware {
         ) {
             return;
         }
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
+        let fx_chain = track_fx_chain(&track, is_input_fx);
         if let Some(fx) = fx_chain.fx_by_index(args.fx_index as u32) {
             let parameter = fx.parameter_by_index(args.param_index as u32);
             handle_change(ChangeEvent::FxParameterValueChanged(
@@ -811,7 +1030,8 @@ impl ChangeDetectionMiddleware {
     }
 
     fn set_track_list_change(&self, handle_change: impl FnMut(ChangeEvent) + Copy) {
-        // TODO-low Not multi-project compatible!
+        // Only the tracks of the active project are reported via the subsequent SetSurface*
+        // callbacks
         let new_active_project = Reaper::get().current_project();
         self.num_track_set_changes_left_to_be_propagated
             .replace(new_active_project.track_count() + 1);
@@ -823,6 +1043,8 @@ impl ChangeDetectionMiddleware {
         new_active_project: Project,
         mut handle_change: impl FnMut(ChangeEvent) + Copy,
     ) {
+        // Report opened projects before switching to them
+        self.detect_project_tab_changes(handle_change);
         if new_active_project != self.last_active_project.get() {
             let old = self.last_active_project.replace(new_active_project);
             handle_change(ChangeEvent::ProjectSwitched(ProjectSwitchedEvent {
@@ -831,27 +1053,126 @@ impl ChangeDetectionMiddleware {
             }));
         }
         self.remove_invalid_rea_projects(handle_change);
-        self.detect_track_set_changes(handle_change);
+        // A track list change can also affect background projects, e.g. when a project is loaded
+        // into another tab
+        for project in Reaper::get().projects() {
+            self.detect_track_set_changes(project, handle_change);
+        }
+        // Capture the initial bookmarks so that the first marker change can be diffed
+        if !self
+            .bookmark_datas
+            .borrow()
+            .contains_key(&new_active_project.raw())
+        {
+            self.detect_bookmark_changes(new_active_project, handle_change);
+        }
     }
 
+    /// Emits exactly one `ProjectClosed` for each known project which has been closed, no matter
+    /// if it was detected via a track list change or via the project tabs.
     fn remove_invalid_rea_projects(&self, mut handle_change: impl FnMut(ChangeEvent) + Copy) {
+        let reaper = Reaper::get().medium_reaper();
+        let is_valid =
+            |rea_project: &ReaProject| reaper.validate_ptr_2(CurrentProject, *rea_project);
+        let mut closed_projects = vec![];
+        self.project_tabs.borrow_mut().retain(|rea_project| {
+            if is_valid(rea_project) {
+                true
+            } else {
+                closed_projects.push(*rea_project);
+                false
+            }
+        });
         self.project_datas.borrow_mut().retain(|rea_project, _| {
-            if Reaper::get()
-                .medium_reaper()
-                .validate_ptr_2(CurrentProject, *rea_project)
-            {
+            if is_valid(rea_project) {
                 true
             } else {
-                handle_change(ChangeEvent::ProjectClosed(ProjectClosedEvent {
-                    project: Project::new(*rea_project),
-                }));
+                if !closed_projects.contains(rea_project) {
+                    closed_projects.push(*rea_project);
+                }
                 false
             }
         });
+        self.item_datas.borrow_mut().retain(|p, _| is_valid(p));
+        self.bookmark_datas.borrow_mut().retain(|p, _| is_valid(p));
+        self.tempo_map_datas.borrow_mut().retain(|p, _| is_valid(p));
+        self.play_states.borrow_mut().retain(|p, _| is_valid(p));
+        for rea_project in closed_projects {
+            handle_change(ChangeEvent::ProjectClosed(ProjectClosedEvent {
+                project: Project::new(rea_project),
+            }));
+        }
+    }
+
+    fn detect_project_tab_changes(&self, mut handle_change: impl FnMut(ChangeEvent) + Copy) {
+        let new_tabs = capture_project_tabs();
+        if *self.project_tabs.borrow() == new_tabs {
+            return;
+        }
+        let changes = project_tab_changes(&self.project_tabs.borrow(), &new_tabs);
+        if !changes.closed.is_empty() {
+            self.remove_invalid_rea_projects(handle_change);
+        }
+        self.project_tabs.replace(new_tabs);
+        for rea_project in changes.opened {
+            handle_change(ChangeEvent::ProjectOpened(ProjectOpenedEvent {
+                project: Project::new(rea_project),
+            }));
+        }
+        for (rea_project, old_index, new_index) in changes.reordered {
+            handle_change(ChangeEvent::ProjectTabReordered(ProjectTabReorderedEvent {
+                project: Project::new(rea_project),
+                old_index,
+                new_index,
+            }));
+        }
     }
 
-    fn detect_track_set_changes(&self, handle_change: impl FnMut(ChangeEvent) + Copy) {
-        let project = Reaper::get().current_project();
+    fn detect_background_play_state_changes(
+        &self,
+        current_project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let reaper = Reaper::get().medium_reaper();
+        let mut play_states = self.play_states.borrow_mut();
+        // Usually no background project is playing, so we can skip checking each one of them
+        let combined_state = reaper.get_all_project_play_states(Some(current_project.raw()));
+        let was_active = play_states
+            .iter()
+            .any(|(p, s)| *p != current_project.raw() && is_active(*s));
+        if !is_active(combined_state) && !was_active {
+            return;
+        }
+        let mut events = vec![];
+        for project in Reaper::get().projects() {
+            if project == current_project {
+                continue;
+            }
+            let new_value = project.play_state();
+            let has_changed = match play_states.insert(project.raw(), new_value) {
+                // Background projects which we don't know yet count as stopped
+                None => is_active(new_value),
+                Some(old_value) => new_value != old_value,
+            };
+            if has_changed {
+                events.push(ChangeEvent::PlayStateChanged(PlayStateChangedEvent {
+                    project,
+                    new_value,
+                }));
+            }
+        }
+        // Listeners might start or stop playback, which makes REAPER report the play state
+        drop(play_states);
+        for event in events {
+            handle_change(event);
+        }
+    }
+
+    fn detect_track_set_changes(
+        &self,
+        project: Project,
+        handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
         let mut project_datas = self.project_datas.borrow_mut();
         let track_datas = project_datas.entry(project.raw()).or_default();
         let old_track_count = track_datas.len() as u32;
@@ -1032,11 +1353,7 @@ impl ChangeDetectionMiddleware {
         handle_change: impl FnMut(ChangeEvent) + Copy,
     ) -> bool {
         let old_fx_count = old_fx_guids.len() as u32;
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
+        let fx_chain = track_fx_chain(track, is_input_fx);
         let new_fx_count = fx_chain.fx_count();
         use std::cmp::Ordering::*;
         match new_fx_count.cmp(&old_fx_count) {
@@ -1081,11 +1398,7 @@ impl ChangeDetectionMiddleware {
                 true
             } else {
                 if notify_listeners_about_changes {
-                    let fx_chain = if is_input_fx {
-                        track.input_fx_chain()
-                    } else {
-                        track.normal_fx_chain()
-                    };
+                    let fx_chain = track_fx_chain(track, is_input_fx);
                     let removed_fx = fx_chain.fx_by_guid(old_fx_guid);
                     handle_change(ChangeEvent::FxRemoved(FxRemovedEvent { fx: removed_fx }));
                 }
@@ -1095,11 +1408,7 @@ impl ChangeDetectionMiddleware {
     }
 
     fn fx_guids_on_track(&self, track: &Track, is_input_fx: bool) -> HashSet<Guid> {
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
+        let fx_chain = track_fx_chain(track, is_input_fx);
         fx_chain
             .fxs()
             .map(|fx| fx.guid().expect("No FX GUID set"))
@@ -1114,11 +1423,7 @@ impl ChangeDetectionMiddleware {
         notify_listeners_about_changes: bool,
         mut handle_change: impl FnMut(ChangeEvent) + Copy,
     ) {
-        let fx_chain = if is_input_fx {
-            track.input_fx_chain()
-        } else {
-            track.normal_fx_chain()
-        };
+        let fx_chain = track_fx_chain(track, is_input_fx);
         for fx in fx_chain.fxs() {
             let was_inserted = fx_guids.insert(fx.guid().expect("No FX GUID set"));
             if was_inserted && notify_listeners_about_changes {
@@ -1127,6 +1432,439 @@ impl ChangeDetectionMiddleware {
         }
     }
 
+    fn detect_item_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let state_change_count = Reaper::get()
+            .medium_reaper()
+            .get_project_state_change_count(project.context());
+        let mut item_datas = self.item_datas.borrow_mut();
+        let data = item_datas.entry(project.raw()).or_default();
+        // The first scan just captures the initial state
+        let notify_listeners_about_changes =
+            match data.last_state_change_count.replace(state_change_count) {
+                Some(c) if c == state_change_count => return,
+                Some(_) => true,
+                None => false,
+            };
+        // Only look closer at tracks whose items have changed
+        let mut changed_tracks = HashSet::new();
+        let mut new_items = HashMap::new();
+        for track in project.tracks() {
+            let media_track = track.raw();
+            let items = unsafe { capture_track_items(media_track) };
+            let hash = hash_track_items(&items);
+            if data.track_item_hashes.insert(media_track, hash) != Some(hash) {
+                changed_tracks.insert(media_track);
+                new_items.extend(items);
+            }
+        }
+        let reaper = Reaper::get().medium_reaper();
+        data.track_item_hashes.retain(|media_track, _| {
+            if reaper.validate_ptr_2(Proj(project.raw()), *media_track) {
+                true
+            } else {
+                changed_tracks.insert(*media_track);
+                false
+            }
+        });
+        if changed_tracks.is_empty() {
+            return;
+        }
+        let old_raw_items: Vec<_> = data
+            .items
+            .iter()
+            .filter(|(_, d)| changed_tracks.contains(&d.track))
+            .map(|(raw_item, _)| *raw_item)
+            .collect();
+        let old_items: HashMap<_, _> = old_raw_items
+            .into_iter()
+            .filter_map(|raw_item| Some((raw_item, data.items.remove(&raw_item)?)))
+            .collect();
+        let mut events = vec![];
+        if notify_listeners_about_changes {
+            for (raw_item, old) in &old_items {
+                if !new_items.contains_key(raw_item) {
+                    events.push(ChangeEvent::ItemRemoved(ItemRemovedEvent {
+                        project,
+                        guid: old.guid,
+                    }));
+                }
+            }
+            for (raw_item, new) in &new_items {
+                let item = Item::new(*raw_item);
+                match old_items.get(raw_item) {
+                    None => events.push(ChangeEvent::ItemAdded(ItemAddedEvent { item })),
+                    Some(old) => push_item_change_events(item, old, new, &mut events),
+                }
+            }
+        }
+        data.items.extend(new_items);
+        // Listeners might re-enter change detection
+        drop(item_datas);
+        for event in events {
+            handle_change(event);
+        }
+    }
+
+    fn detect_tempo_map_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let reaper = Reaper::get().medium_reaper();
+        let state_change_count = reaper.get_project_state_change_count(project.context());
+        let mut tempo_map_datas = self.tempo_map_datas.borrow_mut();
+        let data = tempo_map_datas.entry(project.raw()).or_default();
+        // The first scan just captures the initial state
+        let notify_listeners_about_changes =
+            match data.last_state_change_count.replace(state_change_count) {
+                Some(c) if c == state_change_count => return,
+                Some(_) => true,
+                None => false,
+            };
+        let markers: Vec<_> = (0..reaper.count_tempo_time_sig_markers(project.context()))
+            .filter_map(|i| reaper.get_tempo_time_sig_marker(project.context(), i))
+            .collect();
+        let project_start = PositionInSeconds::new(0.0);
+        let start_tempo = Some((
+            reaper.time_map_2_get_divided_bpm_at_time(project.context(), project_start),
+            project.beat_info_at(project_start).time_signature,
+        ));
+        let time_bases = capture_time_bases(project);
+        let old_markers = std::mem::replace(&mut data.markers, markers);
+        let old_start_tempo = std::mem::replace(&mut data.start_tempo, start_tempo);
+        let old_time_bases = std::mem::replace(&mut data.time_bases, time_bases);
+        if !notify_listeners_about_changes {
+            return;
+        }
+        let start = if data.time_bases != old_time_bases || data.start_tempo != old_start_tempo {
+            Some(project_start)
+        } else {
+            tempo_map_change_start(&old_markers, &data.markers)
+        };
+        // Listeners might re-enter change detection
+        drop(tempo_map_datas);
+        if let Some(start) = start {
+            handle_change(ChangeEvent::TempoMapChanged(TempoMapChangedEvent {
+                project,
+                start,
+            }));
+        }
+    }
+
+    fn detect_take_fx_param_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let reaper = Reaper::get().medium_reaper();
+        let last_touched_param = match reaper.get_last_touched_fx() {
+            Some(GetLastTouchedFxResult::TakeFx {
+                track_index,
+                item_index,
+                take_index,
+                fx_index,
+                param_index,
+            }) => project.track_by_index(track_index).and_then(|t| unsafe {
+                let raw_item = reaper.get_track_media_item(t.raw(), item_index)?;
+                let raw_take = reaper.get_take(raw_item, take_index)?;
+                Some((raw_take, fx_index, param_index))
+            }),
+            _ => None,
+        };
+        // Polling all take FX parameters is expensive, so we do it only if something could have
+        // changed them
+        let state_change_count = reaper.get_project_state_change_count(project.context());
+        let scan = Some((project.raw(), state_change_count));
+        let state_has_changed = self.last_take_fx_param_scan.replace(scan) != scan;
+        let touch_has_changed =
+            self.last_touched_take_fx_param.replace(last_touched_param) != last_touched_param;
+        let mut events = vec![];
+        {
+            let mut values = self.take_fx_param_values.borrow_mut();
+            if state_has_changed {
+                let old_values = std::mem::take(&mut *values);
+                for take in project.items().flat_map(|item| item.takes()) {
+                    let fx_count = unsafe { reaper.take_fx_get_count(take.raw()) };
+                    for fx_index in 0..fx_count {
+                        let raw_guid = unsafe { reaper.take_fx_get_fx_guid(take.raw(), fx_index) };
+                        let fx_guid = match raw_guid {
+                            Ok(g) => Guid::new(g),
+                            Err(_) => continue,
+                        };
+                        let param_count =
+                            unsafe { reaper.take_fx_get_num_params(take.raw(), fx_index) };
+                        for param_index in 0..param_count {
+                            let key = TakeFxParamKey {
+                                take: take.raw(),
+                                fx_guid,
+                                param_index,
+                            };
+                            let new_value = unsafe {
+                                reaper.take_fx_get_param_normalized(
+                                    take.raw(),
+                                    fx_index,
+                                    param_index,
+                                )
+                            };
+                            values.insert(key, new_value);
+                            events.extend(take_fx_param_change_event(
+                                key,
+                                fx_index,
+                                old_values.get(&key).copied(),
+                                new_value,
+                                last_touched_param,
+                            ));
+                        }
+                    }
+                }
+            } else if touch_has_changed {
+                // Nothing else could have changed, so it's enough to poll the touched parameter
+                let (raw_take, fx_index, param_index) = match last_touched_param {
+                    None => return,
+                    Some(p) => p,
+                };
+                let fx_guid = match unsafe { reaper.take_fx_get_fx_guid(raw_take, fx_index) } {
+                    Ok(g) => Guid::new(g),
+                    Err(_) => return,
+                };
+                let key = TakeFxParamKey {
+                    take: raw_take,
+                    fx_guid,
+                    param_index,
+                };
+                let new_value =
+                    unsafe { reaper.take_fx_get_param_normalized(raw_take, fx_index, param_index) };
+                let old_value = values.insert(key, new_value);
+                events.extend(take_fx_param_change_event(
+                    key,
+                    fx_index,
+                    old_value,
+                    new_value,
+                    last_touched_param,
+                ));
+            } else {
+                return;
+            }
+        }
+        for event in events {
+            handle_change(event);
+        }
+    }
+
+    fn detect_position_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let thresholds = self.position_change_thresholds.get();
+        let cycles = self.cycles_since_last_position_check.get() + 1;
+        if cycles < thresholds.check_interval {
+            self.cycles_since_last_position_check.set(cycles);
+            return;
+        }
+        self.cycles_since_last_position_check.set(0);
+        let new = PositionData {
+            project,
+            edit_cursor_position: project.edit_cursor_position(),
+            time_selection: project.time_selection(),
+            loop_points: project.loop_points(),
+            current_bookmark: project.current_bookmark(),
+        };
+        let mut last = match self.position_data.get() {
+            // The first scan (and the first one after a project switch) just captures the state
+            Some(d) if d.project == project => d,
+            _ => {
+                self.position_data.set(Some(new));
+                return;
+            }
+        };
+        if distance(last.edit_cursor_position, new.edit_cursor_position)
+            >= thresholds.edit_cursor_distance.get()
+        {
+            handle_change(ChangeEvent::EditCursorMoved(EditCursorMovedEvent {
+                project,
+                old_position: last.edit_cursor_position,
+                new_position: new.edit_cursor_position,
+            }));
+            last.edit_cursor_position = new.edit_cursor_position;
+        }
+        let time_range_distance = thresholds.time_range_distance.get();
+        if time_range_has_changed(last.time_selection, new.time_selection, time_range_distance) {
+            handle_change(ChangeEvent::TimeSelectionChanged(
+                TimeSelectionChangedEvent {
+                    project,
+                    old_value: last.time_selection,
+                    new_value: new.time_selection,
+                },
+            ));
+            last.time_selection = new.time_selection;
+        }
+        if time_range_has_changed(last.loop_points, new.loop_points, time_range_distance) {
+            handle_change(ChangeEvent::LoopPointsChanged(LoopPointsChangedEvent {
+                project,
+                old_value: last.loop_points,
+                new_value: new.loop_points,
+            }));
+            last.loop_points = new.loop_points;
+        }
+        if new.current_bookmark != last.current_bookmark {
+            handle_change(ChangeEvent::CurrentRegionChanged(
+                CurrentRegionChangedEvent {
+                    project,
+                    old_value: last.current_bookmark,
+                    new_value: new.current_bookmark,
+                },
+            ));
+            last.current_bookmark = new.current_bookmark;
+        }
+        self.position_data.set(Some(last));
+    }
+
+    fn detect_track_attribute_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let mut watch = self.track_attribute_watch.borrow_mut();
+        if watch.keys.is_empty() {
+            return;
+        }
+        watch.cycles_since_last_check += 1;
+        if watch.cycles_since_last_check < watch.check_interval {
+            return;
+        }
+        watch.cycles_since_last_check = 0;
+        let reaper = Reaper::get().medium_reaper();
+        let new_values: HashMap<_, _> = project
+            .tracks()
+            .map(|t| {
+                let values: Vec<_> = watch
+                    .keys
+                    .iter()
+                    .map(|key| unsafe { reaper.get_media_track_info_value(t.raw(), key.clone()) })
+                    .collect();
+                (t.raw(), values)
+            })
+            .collect();
+        // Tracks which are not known yet (e.g. after a project switch) are just captured
+        let old_values = std::mem::replace(&mut watch.values, new_values);
+        let mut events = vec![];
+        for (media_track, new) in &watch.values {
+            let old = match old_values.get(media_track) {
+                None => continue,
+                Some(v) => v,
+            };
+            for ((key, old_value), new_value) in watch.keys.iter().zip(old).zip(new) {
+                if old_value != new_value {
+                    let track = Track::new(*media_track, Some(project.raw()));
+                    events.push(track_attribute_change_event(
+                        track,
+                        key,
+                        *old_value,
+                        *new_value,
+                    ));
+                }
+            }
+        }
+        // Listeners might want to change the watch
+        drop(watch);
+        for event in events {
+            handle_change(event);
+        }
+    }
+
+    fn detect_bookmark_changes(
+        &self,
+        project: Project,
+        mut handle_change: impl FnMut(ChangeEvent) + Copy,
+    ) {
+        let new_datas = capture_bookmarks(project);
+        let old_datas = match self
+            .bookmark_datas
+            .borrow_mut()
+            .insert(project.raw(), new_datas)
+        {
+            // The first scan just captures the initial state
+            None => return,
+            Some(d) => d,
+        };
+        let bookmark_datas = self.bookmark_datas.borrow();
+        let new_datas = &bookmark_datas[&project.raw()];
+        let mut events = vec![];
+        for (&(bookmark_type, id), old) in &old_datas {
+            if !new_datas.contains_key(&(bookmark_type, id)) {
+                events.push(ChangeEvent::MarkerRemoved(MarkerRemovedEvent {
+                    project,
+                    bookmark_type,
+                    id,
+                }));
+            }
+            let new = match new_datas.get(&(bookmark_type, id)) {
+                None => continue,
+                Some(d) => d,
+            };
+            if new.name != old.name {
+                events.push(ChangeEvent::MarkerRenamed(MarkerRenamedEvent {
+                    project,
+                    bookmark_type,
+                    id,
+                    old_name: old.name.clone(),
+                    new_name: new.name.clone(),
+                }));
+            }
+            if new.position != old.position || new.region_end_position != old.region_end_position {
+                let change_event = match (old.region_end_position, new.region_end_position) {
+                    (Some(old_end), Some(new_end)) => {
+                        ChangeEvent::RegionBoundsChanged(RegionBoundsChangedEvent {
+                            project,
+                            id,
+                            old_start: old.position,
+                            old_end,
+                            new_start: new.position,
+                            new_end,
+                        })
+                    }
+                    _ => ChangeEvent::MarkerMoved(MarkerMovedEvent {
+                        project,
+                        id,
+                        old_position: old.position,
+                        new_position: new.position,
+                    }),
+                };
+                events.push(change_event);
+            }
+            if new.color != old.color {
+                events.push(ChangeEvent::BookmarkColorChanged(
+                    BookmarkColorChangedEvent {
+                        project,
+                        bookmark_type,
+                        id,
+                        old_value: old.color,
+                        new_value: new.color,
+                    },
+                ));
+            }
+        }
+        for &(bookmark_type, id) in new_datas.keys() {
+            if !old_datas.contains_key(&(bookmark_type, id)) {
+                events.push(ChangeEvent::MarkerAdded(MarkerAddedEvent {
+                    project,
+                    bookmark_type,
+                    id,
+                }));
+            }
+        }
+        // Listeners might add, change or remove bookmarks, which makes REAPER call us again
+        drop(bookmark_datas);
+        for event in events {
+            handle_change(event);
+        }
+    }
+
     fn update_media_track_positions_and_route_counts(
         &self,
         project: Project,
@@ -1198,6 +1936,374 @@ impl ChangeDetectionMiddleware {
     }
 }
 
+fn capture_project_tabs() -> Vec<ReaProject> {
+    Reaper::get().projects().map(|p| p.raw()).collect()
+}
+
+/// Compares the given project tabs (each one in tab order).
+///
+/// Opening and closing tabs shifts the other tabs but doesn't count as reordering. Neither do
+/// tabs which just make room for a moved tab. The indexes of reordered tabs refer to the complete
+/// old and new list, respectively.
+fn project_tab_changes(old_tabs: &[ReaProject], new_tabs: &[ReaProject]) -> ProjectTabChanges {
+    let closed = old_tabs
+        .iter()
+        .filter(|p| !new_tabs.contains(p))
+        .copied()
+        .collect();
+    let opened = new_tabs
+        .iter()
+        .filter(|p| !old_tabs.contains(p))
+        .copied()
+        .collect();
+    let index = |tabs: &[ReaProject], project: &ReaProject| {
+        tabs.iter().position(|p| p == project).unwrap() as u32
+    };
+    let remaining_tabs: Vec<_> = new_tabs.iter().filter(|p| old_tabs.contains(p)).collect();
+    let old_indexes: Vec<_> = remaining_tabs.iter().map(|p| index(old_tabs, p)).collect();
+    let unmoved = longest_increasing_subsequence(&old_indexes);
+    let reordered = remaining_tabs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !unmoved.contains(i))
+        .map(|(_, p)| (**p, index(old_tabs, p), index(new_tabs, p)))
+        .collect();
+    ProjectTabChanges {
+        closed,
+        opened,
+        reordered,
+    }
+}
+
+/// Returns the indexes of the longest subsequence of the given values which is strictly
+/// increasing. If there are several ones, the one with the leftmost values wins.
+fn longest_increasing_subsequence(values: &[u32]) -> Vec<usize> {
+    let mut lengths = vec![1; values.len()];
+    let mut predecessors = vec![None; values.len()];
+    for i in 0..values.len() {
+        for j in 0..i {
+            if values[j] < values[i] && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                predecessors[i] = Some(j);
+            }
+        }
+    }
+    let mut result = vec![];
+    let mut current = (0..values.len()).rev().max_by_key(|i| lengths[*i]);
+    while let Some(i) = current {
+        result.push(i);
+        current = predecessors[i];
+    }
+    result.reverse();
+    result
+}
+
+fn is_active(play_state: PlayState) -> bool {
+    play_state.is_playing || play_state.is_paused || play_state.is_recording
+}
+
+fn capture_bookmarks(project: Project) -> BookmarkDataMap {
+    project
+        .bookmarks()
+        .map(|b| {
+            b.with_full_info(|res| {
+                let bookmark_type = if res.region_end_position.is_some() {
+                    BookmarkType::Region
+                } else {
+                    BookmarkType::Marker
+                };
+                let data = BookmarkData {
+                    name: res.name.to_str().to_owned(),
+                    position: res.position,
+                    region_end_position: res.region_end_position,
+                    color: res.color,
+                };
+                ((bookmark_type, res.id), data)
+            })
+        })
+        .collect()
+}
+
+unsafe fn capture_track_items(track: MediaTrack) -> Vec<(MediaItem, ItemData)> {
+    let reaper = Reaper::get().medium_reaper();
+    (0..reaper.count_track_media_items(track))
+        .filter_map(|i| reaper.get_track_media_item(track, i))
+        .map(|raw_item| {
+            let raw_takes: Vec<_> = (0..reaper.count_takes(raw_item))
+                .filter_map(|i| reaper.get_take(raw_item, i))
+                .collect();
+            let take_names = raw_takes
+                .iter()
+                .map(|raw_take| {
+                    let name = reaper.get_take_name(*raw_take, |result| {
+                        result.map(|n| n.to_string()).unwrap_or_default()
+                    });
+                    (*raw_take, name)
+                })
+                .collect();
+            let take_fx_guids = raw_takes
+                .iter()
+                .map(|raw_take| {
+                    let guids = (0..reaper.take_fx_get_count(*raw_take))
+                        .filter_map(|i| reaper.take_fx_get_fx_guid(*raw_take, i).ok())
+                        .map(Guid::new)
+                        .collect();
+                    (*raw_take, guids)
+                })
+                .collect();
+            let data = ItemData {
+                guid: Guid::new(reaper.get_set_media_item_info_get_guid(raw_item)),
+                track,
+                position: PositionInSeconds::new(
+                    reaper.get_media_item_info_value(raw_item, ItemAttributeKey::Position),
+                ),
+                length: DurationInSeconds::new(
+                    reaper.get_media_item_info_value(raw_item, ItemAttributeKey::Length),
+                ),
+                selected: reaper.get_media_item_info_value(raw_item, ItemAttributeKey::UiSel)
+                    != 0.0,
+                active_take: reaper.get_active_take(raw_item),
+                take_names,
+                take_fx_guids,
+            };
+            (raw_item, data)
+        })
+        .collect()
+}
+
+fn hash_track_items(items: &[(MediaItem, ItemData)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for (raw_item, data) in items {
+        raw_item.hash(&mut hasher);
+        data.guid.hash(&mut hasher);
+        data.position.get().to_bits().hash(&mut hasher);
+        data.length.get().to_bits().hash(&mut hasher);
+        data.selected.hash(&mut hasher);
+        data.active_take.hash(&mut hasher);
+        data.take_names.hash(&mut hasher);
+        data.take_fx_guids.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn push_item_change_events(
+    item: Item,
+    old: &ItemData,
+    new: &ItemData,
+    events: &mut Vec<ChangeEvent>,
+) {
+    if new.position != old.position || new.track != old.track {
+        events.push(ChangeEvent::ItemMoved(ItemMovedEvent {
+            item,
+            track_changed: new.track != old.track,
+            old_position: old.position,
+            new_position: new.position,
+        }));
+    }
+    if new.length != old.length {
+        events.push(ChangeEvent::ItemLengthChanged(ItemLengthChangedEvent {
+            item,
+            old_value: old.length,
+            new_value: new.length,
+        }));
+    }
+    if new.selected != old.selected {
+        events.push(ChangeEvent::ItemSelectedChanged(ItemSelectedChangedEvent {
+            item,
+            old_value: old.selected,
+            new_value: new.selected,
+        }));
+    }
+    if new.active_take != old.active_take {
+        events.push(ChangeEvent::ActiveTakeChanged(ActiveTakeChangedEvent {
+            item,
+            take: new.active_take.map(Take::new),
+        }));
+    }
+    for (raw_take, name) in &new.take_names {
+        let old_name = old
+            .take_names
+            .iter()
+            .find(|(t, _)| t == raw_take)
+            .map(|(_, n)| n);
+        if matches!(old_name, Some(n) if n != name) {
+            events.push(ChangeEvent::TakeNameChanged(TakeNameChangedEvent {
+                take: Take::new(*raw_take),
+            }));
+        }
+    }
+    // FX of newly added takes are not reported, just like FX of newly added tracks
+    for (raw_take, new_guids) in &new.take_fx_guids {
+        let old_guids = match old.take_fx_guids.iter().find(|(t, _)| t == raw_take) {
+            None => continue,
+            Some((_, guids)) => guids,
+        };
+        let fx_chain = Take::new(*raw_take).fx_chain();
+        for guid in old_guids.iter().filter(|g| !new_guids.contains(g)) {
+            events.push(ChangeEvent::FxRemoved(FxRemovedEvent {
+                fx: fx_chain.fx_by_guid(guid),
+            }));
+        }
+        for guid in new_guids.iter().filter(|g| !old_guids.contains(g)) {
+            events.push(ChangeEvent::FxAdded(FxAddedEvent {
+                fx: fx_chain.fx_by_guid(guid),
+            }));
+        }
+    }
+}
+
+/// Returns a change event if the value of the given take FX parameter has changed.
+///
+/// Parameters which are seen for the first time haven't changed.
+fn take_fx_param_change_event(
+    key: TakeFxParamKey,
+    fx_index: u32,
+    old_value: Option<ReaperNormalizedFxParamValue>,
+    new_value: ReaperNormalizedFxParamValue,
+    last_touched_param: Option<(MediaItemTake, u32, u32)>,
+) -> Option<ChangeEvent> {
+    if old_value? == new_value {
+        return None;
+    }
+    let fx = Take::new(key.take)
+        .fx_chain()
+        .fx_by_guid_and_index(&key.fx_guid, fx_index);
+    let event = FxParameterValueChangedEvent {
+        touched: last_touched_param == Some((key.take, fx_index, key.param_index)),
+        parameter: fx.parameter_by_index(key.param_index),
+        new_value,
+    };
+    Some(ChangeEvent::FxParameterValueChanged(event))
+}
+
+fn track_attribute_change_event(
+    track: Track,
+    key: &TrackAttributeKey<'static>,
+    old_value: f64,
+    new_value: f64,
+) -> ChangeEvent {
+    match key {
+        TrackAttributeKey::CustomColor => ChangeEvent::TrackColorChanged(TrackColorChangedEvent {
+            track,
+            old_value: custom_color(old_value),
+            new_value: custom_color(new_value),
+        }),
+        TrackAttributeKey::ShowInTcp | TrackAttributeKey::ShowInMixer => {
+            ChangeEvent::TrackVisibilityChanged(TrackVisibilityChangedEvent {
+                track,
+                area: if *key == TrackAttributeKey::ShowInTcp {
+                    TrackArea::Tcp
+                } else {
+                    TrackArea::Mcp
+                },
+                new_value: new_value != 0.0,
+            })
+        }
+        TrackAttributeKey::FolderDepth | TrackAttributeKey::FolderCompact => {
+            ChangeEvent::TrackFolderStateChanged(TrackFolderStateChangedEvent { track })
+        }
+        TrackAttributeKey::Phase => ChangeEvent::TrackPhaseChanged(TrackPhaseChangedEvent {
+            track,
+            new_value: new_value != 0.0,
+        }),
+        TrackAttributeKey::Width => ChangeEvent::TrackWidthChanged(TrackWidthChangedEvent {
+            track,
+            old_value: ReaperWidthValue::new(old_value),
+            new_value: ReaperWidthValue::new(new_value),
+        }),
+        _ => ChangeEvent::TrackAttributeChanged(TrackAttributeChangedEvent {
+            track,
+            key: key.clone(),
+            old_value,
+            new_value,
+        }),
+    }
+}
+
+/// Returns `None` if the track doesn't have a custom color.
+fn custom_color(raw_value: f64) -> Option<NativeColor> {
+    let raw_value = raw_value as u32;
+    // REAPER sets this flag if a custom color is active
+    if raw_value & 0x0100_0000 == 0 {
+        return None;
+    }
+    Some(NativeColor::new(raw_value & 0x00FF_FFFF))
+}
+
+/// Returns the position from which on the tempo map differs or `None` if it's unchanged.
+fn tempo_map_change_start(
+    old_markers: &[GetTempoTimeSigMarkerResult],
+    new_markers: &[GetTempoTimeSigMarkerResult],
+) -> Option<PositionInSeconds> {
+    let first_diff = (0..old_markers.len().max(new_markers.len()))
+        .find(|i| old_markers.get(*i) != new_markers.get(*i))?;
+    // A changed marker also changes the tempo ramp coming from a linear predecessor
+    if let Some(previous) = first_diff.checked_sub(1).and_then(|i| new_markers.get(i)) {
+        if previous.linear_tempo {
+            return Some(previous.position);
+        }
+    }
+    let start = old_markers
+        .get(first_diff)
+        .into_iter()
+        .chain(new_markers.get(first_diff))
+        .map(|m| m.position.get())
+        .fold(f64::INFINITY, f64::min);
+    Some(PositionInSeconds::new(start))
+}
+
+fn capture_time_bases(project: Project) -> Vec<i32> {
+    let reaper = Reaper::get().medium_reaper();
+    ["itemtimebase", "tempoenvtimelock"]
+        .iter()
+        .filter_map(|name| {
+            let offs = reaper.project_config_var_get_offs(*name)?;
+            // Both variables are integers, everything else would be misinterpreted
+            if offs.size as usize != std::mem::size_of::<i32>() {
+                return None;
+            }
+            let value = unsafe {
+                let var =
+                    reaper.project_config_var_addr(Proj(project.raw()), offs.offset) as *const i32;
+                if var.is_null() {
+                    return None;
+                }
+                *var
+            };
+            Some(value)
+        })
+        .collect()
+}
+
+fn distance(a: PositionInSeconds, b: PositionInSeconds) -> f64 {
+    (a.get() - b.get()).abs()
+}
+
+fn time_range_has_changed(
+    old: Option<GetLoopTimeRange2Result>,
+    new: Option<GetLoopTimeRange2Result>,
+    threshold: f64,
+) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            distance(old.start, new.start) >= threshold || distance(old.end, new.end) >= threshold
+        }
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+/// REAPER reports monitoring FX as input FX of the master track.
+fn track_fx_chain(track: &Track, is_input_fx: bool) -> FxChain {
+    if !is_input_fx {
+        track.normal_fx_chain()
+    } else if track.is_master_track() {
+        Reaper::get().monitoring_fx_chain()
+    } else {
+        track.input_fx_chain()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ChangeEvent {
     ProjectSwitched(ProjectSwitchedEvent),
@@ -1234,7 +2340,33 @@ pub enum ChangeEvent {
     PlayStateChanged(PlayStateChangedEvent),
     RepeatStateChanged(RepeatStateChangedEvent),
     ProjectClosed(ProjectClosedEvent),
+    ProjectOpened(ProjectOpenedEvent),
+    ProjectTabReordered(ProjectTabReorderedEvent),
     BookmarksChanged(BookmarksChangedEvent),
+    ItemAdded(ItemAddedEvent),
+    ItemRemoved(ItemRemovedEvent),
+    ItemMoved(ItemMovedEvent),
+    ItemLengthChanged(ItemLengthChangedEvent),
+    ItemSelectedChanged(ItemSelectedChangedEvent),
+    ActiveTakeChanged(ActiveTakeChangedEvent),
+    TakeNameChanged(TakeNameChangedEvent),
+    MarkerAdded(MarkerAddedEvent),
+    MarkerRemoved(MarkerRemovedEvent),
+    MarkerRenamed(MarkerRenamedEvent),
+    MarkerMoved(MarkerMovedEvent),
+    RegionBoundsChanged(RegionBoundsChangedEvent),
+    BookmarkColorChanged(BookmarkColorChangedEvent),
+    EditCursorMoved(EditCursorMovedEvent),
+    TimeSelectionChanged(TimeSelectionChangedEvent),
+    LoopPointsChanged(LoopPointsChangedEvent),
+    CurrentRegionChanged(CurrentRegionChangedEvent),
+    TrackColorChanged(TrackColorChangedEvent),
+    TrackVisibilityChanged(TrackVisibilityChangedEvent),
+    TrackFolderStateChanged(TrackFolderStateChangedEvent),
+    TrackPhaseChanged(TrackPhaseChangedEvent),
+    TrackWidthChanged(TrackWidthChangedEvent),
+    TrackAttributeChanged(TrackAttributeChangedEvent),
+    TempoMapChanged(TempoMapChangedEvent),
 }
 
 #[derive(Clone, Debug)]
@@ -1456,7 +2588,396 @@ pub struct ProjectClosedEvent {
     pub project: Project,
 }
 
+#[derive(Clone, Debug)]
+pub struct ProjectOpenedEvent {
+    pub project: Project,
+}
+
+/// Emitted for each project whose tab has been moved.
+///
+/// Tabs which are just shifted because other tabs have been moved, opened or closed are not
+/// reported.
+#[derive(Clone, Debug)]
+pub struct ProjectTabReorderedEvent {
+    pub project: Project,
+    pub old_index: u32,
+    pub new_index: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct BookmarksChangedEvent {
     pub project: Project,
 }
+
+#[derive(Clone, Debug)]
+pub struct ItemAddedEvent {
+    pub item: Item,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemRemovedEvent {
+    pub project: Project,
+    /// The item itself is not available anymore.
+    pub guid: Guid,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemMovedEvent {
+    pub item: Item,
+    /// Whether the item has been moved to another track.
+    pub track_changed: bool,
+    pub old_position: PositionInSeconds,
+    pub new_position: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemLengthChangedEvent {
+    pub item: Item,
+    pub old_value: DurationInSeconds,
+    pub new_value: DurationInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemSelectedChangedEvent {
+    pub item: Item,
+    pub old_value: bool,
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ActiveTakeChangedEvent {
+    pub item: Item,
+    pub take: Option<Take>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TakeNameChangedEvent {
+    pub take: Take,
+}
+
+/// Fired for markers and regions.
+#[derive(Clone, Debug)]
+pub struct MarkerAddedEvent {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    pub id: BookmarkId,
+}
+
+/// Fired for markers and regions.
+#[derive(Clone, Debug)]
+pub struct MarkerRemovedEvent {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    pub id: BookmarkId,
+}
+
+/// Fired for markers and regions.
+#[derive(Clone, Debug)]
+pub struct MarkerRenamedEvent {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    pub id: BookmarkId,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct MarkerMovedEvent {
+    pub project: Project,
+    pub id: BookmarkId,
+    pub old_position: PositionInSeconds,
+    pub new_position: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct RegionBoundsChangedEvent {
+    pub project: Project,
+    pub id: BookmarkId,
+    pub old_start: PositionInSeconds,
+    pub old_end: PositionInSeconds,
+    pub new_start: PositionInSeconds,
+    pub new_end: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct BookmarkColorChangedEvent {
+    pub project: Project,
+    pub bookmark_type: BookmarkType,
+    pub id: BookmarkId,
+    pub old_value: NativeColor,
+    pub new_value: NativeColor,
+}
+
+#[derive(Clone, Debug)]
+pub struct EditCursorMovedEvent {
+    pub project: Project,
+    pub old_position: PositionInSeconds,
+    pub new_position: PositionInSeconds,
+}
+
+#[derive(Clone, Debug)]
+pub struct TimeSelectionChangedEvent {
+    pub project: Project,
+    pub old_value: Option<GetLoopTimeRange2Result>,
+    pub new_value: Option<GetLoopTimeRange2Result>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LoopPointsChangedEvent {
+    pub project: Project,
+    pub old_value: Option<GetLoopTimeRange2Result>,
+    pub new_value: Option<GetLoopTimeRange2Result>,
+}
+
+/// Fired when the play cursor (or the edit cursor if not playing) enters another region or passes
+/// a marker.
+#[derive(Clone, Debug)]
+pub struct CurrentRegionChangedEvent {
+    pub project: Project,
+    pub old_value: GetLastMarkerAndCurRegionResult,
+    pub new_value: GetLastMarkerAndCurRegionResult,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackColorChangedEvent {
+    pub track: Track,
+    /// `None` if the track didn't have a custom color.
+    pub old_value: Option<NativeColor>,
+    /// `None` if the track doesn't have a custom color anymore.
+    pub new_value: Option<NativeColor>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackVisibilityChangedEvent {
+    pub track: Track,
+    pub area: TrackArea,
+    pub new_value: bool,
+}
+
+/// Fired if the folder depth or the compact state of a folder changed.
+#[derive(Clone, Debug)]
+pub struct TrackFolderStateChangedEvent {
+    pub track: Track,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackPhaseChangedEvent {
+    pub track: Track,
+    /// `true` if the phase is inverted.
+    pub new_value: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackWidthChangedEvent {
+    pub track: Track,
+    pub old_value: ReaperWidthValue,
+    pub new_value: ReaperWidthValue,
+}
+
+/// Fired for watched track attributes which don't have a more specific event.
+#[derive(Clone, Debug)]
+pub struct TrackAttributeChangedEvent {
+    pub track: Track,
+    pub key: TrackAttributeKey<'static>,
+    pub old_value: f64,
+    pub new_value: f64,
+}
+
+/// Fired if tempo markers, time signatures or the project time base changed.
+///
+/// Changing the tempo map shifts everything after the change, so the affected range always extends
+/// to the end of the project.
+#[derive(Clone, Debug)]
+pub struct TempoMapChangedEvent {
+    pub project: Project,
+    /// Start of the affected range.
+    pub start: PositionInSeconds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reaper_medium::PositionInBeats;
+    use std::ptr::NonNull;
+
+    #[test]
+    fn tempo_map_unchanged() {
+        // Given
+        let markers = vec![marker(0.0, 120.0, false), marker(4.0, 90.0, false)];
+        // When
+        let result = tempo_map_change_start(&markers, &markers);
+        // Then
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn tempo_map_marker_changed() {
+        // Given
+        let old = vec![marker(0.0, 120.0, false), marker(4.0, 90.0, false)];
+        let new = vec![marker(0.0, 120.0, false), marker(4.0, 100.0, false)];
+        // When
+        let result = tempo_map_change_start(&old, &new);
+        // Then
+        assert_eq!(result, Some(PositionInSeconds::new(4.0)));
+    }
+
+    #[test]
+    fn tempo_map_marker_moved() {
+        // Given
+        let old = vec![marker(0.0, 120.0, false), marker(4.0, 90.0, false)];
+        let new = vec![marker(0.0, 120.0, false), marker(3.0, 90.0, false)];
+        // When
+        let result = tempo_map_change_start(&old, &new);
+        // Then
+        assert_eq!(result, Some(PositionInSeconds::new(3.0)));
+    }
+
+    #[test]
+    fn tempo_map_marker_added_and_removed() {
+        // Given
+        let old = vec![marker(0.0, 120.0, false)];
+        let new = vec![marker(0.0, 120.0, false), marker(8.0, 90.0, false)];
+        // When
+        let added = tempo_map_change_start(&old, &new);
+        let removed = tempo_map_change_start(&new, &old);
+        let all_removed = tempo_map_change_start(&new, &[]);
+        // Then
+        assert_eq!(added, Some(PositionInSeconds::new(8.0)));
+        assert_eq!(removed, Some(PositionInSeconds::new(8.0)));
+        assert_eq!(all_removed, Some(PositionInSeconds::new(0.0)));
+    }
+
+    #[test]
+    fn tempo_map_marker_after_linear_ramp_changed() {
+        // Given
+        let old = vec![
+            marker(0.0, 120.0, false),
+            marker(2.0, 120.0, true),
+            marker(6.0, 90.0, false),
+        ];
+        let new = vec![
+            marker(0.0, 120.0, false),
+            marker(2.0, 120.0, true),
+            marker(6.0, 80.0, false),
+        ];
+        // When
+        let result = tempo_map_change_start(&old, &new);
+        // Then
+        assert_eq!(result, Some(PositionInSeconds::new(2.0)));
+    }
+
+    #[test]
+    fn project_tabs_unchanged() {
+        // Given
+        let tabs = vec![project(1), project(2)];
+        // When
+        let changes = project_tab_changes(&tabs, &tabs);
+        // Then
+        assert_eq!(changes, ProjectTabChanges::default());
+    }
+
+    #[test]
+    fn project_tabs_opened_and_closed() {
+        // Given
+        let old = vec![project(1), project(2), project(3)];
+        let new = vec![project(2), project(3), project(4)];
+        // When
+        let changes = project_tab_changes(&old, &new);
+        // Then
+        assert_eq!(
+            changes,
+            ProjectTabChanges {
+                closed: vec![project(1)],
+                opened: vec![project(4)],
+                reordered: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn project_tabs_reordered() {
+        // Given
+        let old = vec![project(1), project(2), project(3)];
+        let new = vec![project(2), project(1), project(3)];
+        // When
+        let changes = project_tab_changes(&old, &new);
+        // Then
+        assert_eq!(
+            changes,
+            ProjectTabChanges {
+                closed: vec![],
+                opened: vec![],
+                reordered: vec![(project(1), 0, 1)],
+            }
+        );
+    }
+
+    #[test]
+    fn project_tabs_closed_and_reordered() {
+        // Given
+        let old = vec![project(1), project(2), project(3), project(4)];
+        let new = vec![project(4), project(2), project(3)];
+        // When
+        let changes = project_tab_changes(&old, &new);
+        // Then
+        assert_eq!(
+            changes,
+            ProjectTabChanges {
+                closed: vec![project(1)],
+                opened: vec![],
+                reordered: vec![(project(4), 3, 0)],
+            }
+        );
+    }
+
+    #[test]
+    fn project_tabs_opened_and_reordered() {
+        // Given
+        let old = vec![project(1), project(2)];
+        let new = vec![project(2), project(3), project(1)];
+        // When
+        let changes = project_tab_changes(&old, &new);
+        // Then
+        assert_eq!(
+            changes,
+            ProjectTabChanges {
+                closed: vec![],
+                opened: vec![project(3)],
+                reordered: vec![(project(1), 0, 2)],
+            }
+        );
+    }
+
+    #[test]
+    fn project_tab_moved_over_several_tabs() {
+        // Given
+        let old = vec![project(1), project(2), project(3)];
+        let new = vec![project(3), project(1), project(2)];
+        // When
+        let changes = project_tab_changes(&old, &new);
+        // Then
+        assert_eq!(
+            changes,
+            ProjectTabChanges {
+                closed: vec![],
+                opened: vec![],
+                reordered: vec![(project(3), 2, 0)],
+            }
+        );
+    }
+
+    fn project(id: usize) -> ReaProject {
+        NonNull::new(id as *mut _).unwrap()
+    }
+
+    fn marker(position: f64, tempo: f64, linear_tempo: bool) -> GetTempoTimeSigMarkerResult {
+        GetTempoTimeSigMarkerResult {
+            position: PositionInSeconds::new(position),
+            measure_index: 0,
+            beats_since_measure: PositionInBeats::new(0.0),
+            tempo: Bpm::new(tempo),
+            time_signature: None,
+            linear_tempo,
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{ChangeEvent, FxParameter, Track, TrackRoute};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Collapses high-frequency change events before they reach the consumers.
+///
+/// During automation playback, REAPER reports volume, pan and FX parameter changes in each main
+/// loop cycle. Feed the events emitted by `ChangeDetectionMiddleware::process()` into
+/// [`process()`] and call [`run()`] once in each main loop cycle. Repeated events for the same
+/// target are then delivered as one event which contains the first old value and the latest new
+/// value. All other events are delivered unchanged and in their original order.
+///
+/// Additionally, a minimum interval between two events for the same target can be set per event
+/// type. Events which come in earlier are held back (and coalesced) until the interval has passed.
+///
+/// [`process()`]: #method.process
+/// [`run()`]: #method.run
+#[derive(Debug, Default)]
+pub struct ChangeEventCoalescer {
+    queue: RefCell<Vec<QueuedEvent>>,
+    // Position of the pending event in the queue
+    queue_index: RefCell<HashMap<CoalescingKey, usize>>,
+    disabled_event_types: RefCell<HashSet<CoalescableEventType>>,
+    rate_limits: RefCell<HashMap<CoalescableEventType, Duration>>,
+    last_emissions: RefCell<HashMap<CoalescingKey, Instant>>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CoalescableEventType {
+    TrackVolumeChanged,
+    TrackPanChanged,
+    TrackRouteVolumeChanged,
+    TrackRoutePanChanged,
+    FxParameterValueChanged,
+}
+
+#[derive(Debug)]
+struct QueuedEvent {
+    key: Option<CoalescingKey>,
+    event: ChangeEvent,
+}
+
+/// Identifies the target of a coalescable event.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+enum CoalescingKey {
+    TrackVolume(Track),
+    TrackPan(Track),
+    TrackRouteVolume(TrackRoute),
+    TrackRoutePan(TrackRoute),
+    FxParameterValue(FxParameter),
+}
+
+impl CoalescingKey {
+    fn from_event(event: &ChangeEvent) -> Option<CoalescingKey> {
+        use ChangeEvent::*;
+        let key = match event {
+            TrackVolumeChanged(e) => CoalescingKey::TrackVolume(e.track.clone()),
+            TrackPanChanged(e) => CoalescingKey::TrackPan(e.track.clone()),
+            TrackRouteVolumeChanged(e) => CoalescingKey::TrackRouteVolume(e.route.clone()),
+            TrackRoutePanChanged(e) => CoalescingKey::TrackRoutePan(e.route.clone()),
+            FxParameterValueChanged(e) => CoalescingKey::FxParameterValue(e.parameter.clone()),
+            _ => return None,
+        };
+        Some(key)
+    }
+
+    fn event_type(&self) -> CoalescableEventType {
+        use CoalescingKey::*;
+        match self {
+            TrackVolume(_) => CoalescableEventType::TrackVolumeChanged,
+            TrackPan(_) => CoalescableEventType::TrackPanChanged,
+            TrackRouteVolume(_) => CoalescableEventType::TrackRouteVolumeChanged,
+            TrackRoutePan(_) => CoalescableEventType::TrackRoutePanChanged,
+            FxParameterValue(_) => CoalescableEventType::FxParameterValueChanged,
+        }
+    }
+}
+
+impl ChangeEventCoalescer {
+    pub fn new() -> ChangeEventCoalescer {
+        Default::default()
+    }
+
+    /// Enables or disables coalescing for the given event type. Enabled by default.
+    ///
+    /// Events of a disabled type are delivered one by one (but still in `run()`) and are not rate
+    /// limited.
+    pub fn set_coalescing_enabled(&self, event_type: CoalescableEventType, enabled: bool) {
+        let mut disabled_event_types = self.disabled_event_types.borrow_mut();
+        if enabled {
+            disabled_event_types.remove(&event_type);
+        } else {
+            disabled_event_types.insert(event_type);
+        }
+    }
+
+    /// Sets the minimum interval between two events of the given type for the same target.
+    ///
+    /// Pass `None` to remove the rate limit.
+    pub fn set_rate_limit(&self, event_type: CoalescableEventType, min_interval: Option<Duration>) {
+        let mut rate_limits = self.rate_limits.borrow_mut();
+        match min_interval {
+            None => rate_limits.remove(&event_type),
+            Some(i) => rate_limits.insert(event_type, i),
+        };
+    }
+
+    /// Drops all pending events, e.g. when the consumers are reset anyway.
+    pub fn clear(&self) {
+        self.queue.borrow_mut().clear();
+        self.queue_index.borrow_mut().clear();
+        self.last_emissions.borrow_mut().clear();
+    }
+
+    /// Enqueues the given event, merging it with a pending event for the same target if there is
+    /// one.
+    pub fn process(&self, event: ChangeEvent) {
+        let key = CoalescingKey::from_event(&event)
+            .filter(|k| !self.disabled_event_types.borrow().contains(&k.event_type()));
+        let mut queue = self.queue.borrow_mut();
+        let key = match key {
+            None => {
+                queue.push(QueuedEvent { key: None, event });
+                return;
+            }
+            Some(k) => k,
+        };
+        let mut queue_index = self.queue_index.borrow_mut();
+        match queue_index.get(&key) {
+            Some(i) => merge(&mut queue[*i].event, event),
+            None => {
+                queue_index.insert(key.clone(), queue.len());
+                queue.push(QueuedEvent {
+                    key: Some(key),
+                    event,
+                });
+            }
+        }
+    }
+
+    /// Delivers all pending events which are not held back by a rate limit.
+    ///
+    /// Should be called once in each main loop cycle, after `ChangeDetectionMiddleware::run()`.
+    pub fn run(&self, handle_change: impl FnMut(ChangeEvent)) {
+        self.run_at(Instant::now(), handle_change);
+    }
+
+    fn run_at(&self, now: Instant, mut handle_change: impl FnMut(ChangeEvent)) {
+        let due_events = self.take_due_events(now);
+        self.forget_expired_emissions(now);
+        // Don't hold any borrow while handing out events, consumers might enqueue new ones
+        for e in due_events {
+            handle_change(e);
+        }
+    }
+
+    fn take_due_events(&self, now: Instant) -> Vec<ChangeEvent> {
+        let queue = self.queue.replace(vec![]);
+        let mut queue_index = self.queue_index.borrow_mut();
+        queue_index.clear();
+        let rate_limits = self.rate_limits.borrow();
+        let mut last_emissions = self.last_emissions.borrow_mut();
+        let mut held_back = vec![];
+        let mut due_events = Vec::with_capacity(queue.len());
+        for q in queue {
+            if let Some(key) = q.key {
+                if let Some(min_interval) = rate_limits.get(&key.event_type()) {
+                    let is_too_early = last_emissions
+                        .get(&key)
+                        .map(|last| now.duration_since(*last) < *min_interval)
+                        .unwrap_or(false);
+                    if is_too_early {
+                        queue_index.insert(key.clone(), held_back.len());
+                        held_back.push(QueuedEvent {
+                            key: Some(key),
+                            event: q.event,
+                        });
+                        continue;
+                    }
+                    last_emissions.insert(key, now);
+                }
+            }
+            due_events.push(q.event);
+        }
+        self.queue.replace(held_back);
+        due_events
+    }
+
+    fn forget_expired_emissions(&self, now: Instant) {
+        let rate_limits = self.rate_limits.borrow();
+        self.last_emissions.borrow_mut().retain(|key, last| {
+            rate_limits
+                .get(&key.event_type())
+                .map(|min_interval| now.duration_since(*last) < *min_interval)
+                .unwrap_or(false)
+        });
+    }
+}
+
+/// Keeps the old value of the pending event and takes everything else from the new one.
+fn merge(pending: &mut ChangeEvent, new: ChangeEvent) {
+    use ChangeEvent::*;
+    match (pending, new) {
+        (TrackVolumeChanged(p), TrackVolumeChanged(n)) => {
+            p.touched |= n.touched;
+            p.new_value = n.new_value;
+        }
+        (TrackPanChanged(p), TrackPanChanged(n)) => {
+            p.touched |= n.touched;
+            p.new_value = n.new_value;
+        }
+        (TrackRouteVolumeChanged(p), TrackRouteVolumeChanged(n)) => {
+            p.touched |= n.touched;
+            p.new_value = n.new_value;
+        }
+        (TrackRoutePanChanged(p), TrackRoutePanChanged(n)) => {
+            p.touched |= n.touched;
+            p.new_value = n.new_value;
+        }
+        (FxParameterValueChanged(p), FxParameterValueChanged(n)) => {
+            p.touched |= n.touched;
+            p.new_value = n.new_value;
+        }
+        // Can't happen because the coalescing key determines the event type
+        (p, n) => *p = n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Guid, Project, ProjectClosedEvent, TrackVolumeChangedEvent};
+    use reaper_medium::ReaperVolumeValue;
+    use std::ptr::NonNull;
+
+    #[test]
+    fn merge_changes_for_same_track() {
+        // Given
+        let coalescer = ChangeEventCoalescer::new();
+        let track = track(1);
+        // When
+        coalescer.process(volume_changed(&track, 0.1, 0.2));
+        coalescer.process(volume_changed(&track, 0.2, 0.3));
+        let events = run_and_collect(&coalescer, Instant::now());
+        // Then
+        assert_eq!(events.len(), 1);
+        assert_eq!(volume_change(&events[0]), (track, 0.1, 0.3));
+    }
+
+    #[test]
+    fn keep_order_across_tracks() {
+        // Given
+        let coalescer = ChangeEventCoalescer::new();
+        let first_track = track(1);
+        let second_track = track(2);
+        // When
+        coalescer.process(volume_changed(&first_track, 0.1, 0.2));
+        coalescer.process(ChangeEvent::ProjectClosed(ProjectClosedEvent {
+            project: project(),
+        }));
+        coalescer.process(volume_changed(&second_track, 0.5, 0.6));
+        coalescer.process(volume_changed(&first_track, 0.2, 0.3));
+        let events = run_and_collect(&coalescer, Instant::now());
+        // Then
+        assert_eq!(events.len(), 3);
+        assert_eq!(volume_change(&events[0]), (first_track, 0.1, 0.3));
+        assert!(matches!(events[1], ChangeEvent::ProjectClosed(_)));
+        assert_eq!(volume_change(&events[2]), (second_track, 0.5, 0.6));
+    }
+
+    #[test]
+    fn hold_back_and_release_rate_limited_events() {
+        // Given
+        let coalescer = ChangeEventCoalescer::new();
+        coalescer.set_rate_limit(
+            CoalescableEventType::TrackVolumeChanged,
+            Some(Duration::from_millis(100)),
+        );
+        let track = track(1);
+        let start = Instant::now();
+        // When
+        coalescer.process(volume_changed(&track, 0.1, 0.2));
+        let first_events = run_and_collect(&coalescer, start);
+        coalescer.process(volume_changed(&track, 0.2, 0.3));
+        let early_events = run_and_collect(&coalescer, start + Duration::from_millis(50));
+        coalescer.process(volume_changed(&track, 0.3, 0.4));
+        let released_events = run_and_collect(&coalescer, start + Duration::from_millis(100));
+        // Then
+        assert_eq!(first_events.len(), 1);
+        assert_eq!(volume_change(&first_events[0]), (track.clone(), 0.1, 0.2));
+        assert!(early_events.is_empty());
+        assert_eq!(released_events.len(), 1);
+        assert_eq!(volume_change(&released_events[0]), (track, 0.2, 0.4));
+    }
+
+    fn run_and_collect(coalescer: &ChangeEventCoalescer, now: Instant) -> Vec<ChangeEvent> {
+        let mut events = vec![];
+        coalescer.run_at(now, |e| events.push(e));
+        events
+    }
+
+    fn project() -> Project {
+        // Never dereferenced because the tracks are compared by GUID
+        Project::new(NonNull::dangling())
+    }
+
+    fn track(number: u32) -> Track {
+        let guid = Guid::from_string_without_braces(&format!(
+            "{:08X}-0000-0000-0000-000000000000",
+            number
+        ))
+        .unwrap();
+        Track::from_guid(project(), guid)
+    }
+
+    fn volume_changed(track: &Track, old_value: f64, new_value: f64) -> ChangeEvent {
+        ChangeEvent::TrackVolumeChanged(TrackVolumeChangedEvent {
+            touched: false,
+            track: track.clone(),
+            old_value: ReaperVolumeValue::new(old_value),
+            new_value: ReaperVolumeValue::new(new_value),
+        })
+    }
+
+    fn volume_change(event: &ChangeEvent) -> (Track, f64, f64) {
+        match event {
+            ChangeEvent::TrackVolumeChanged(e) => {
+                (e.track.clone(), e.old_value.get(), e.new_value.get())
+            }
+            _ => panic!("expected track volume change"),
+        }
+    }
+}
@@ -0,0 +1,632 @@
+//! Structural differences between two chunks.
+//!
+//! A diff compares the block trees of two chunks (see [`RppNode`]) and results in a
+//! [`ChunkPatch`], which can be inspected or applied to another chunk.
+//!
+//! [`RppNode`]: struct.RppNode.html
+//! [`ChunkPatch`]: struct.ChunkPatch.html
+use crate::{ChunkRegion, RppItem, RppLine, RppNode};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Identifies a block within a chunk, e.g. `TRACK/FXCHAIN/VST[2]`.
+///
+/// Each segment consists of the tag name and the index among all sibling blocks with the same tag
+/// name. An index of 0 is not displayed. The first segment refers to the root block.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ChunkPath {
+    segments: Vec<ChunkPathSegment>,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ChunkPathSegment {
+    pub tag: String,
+    pub index: u32,
+}
+
+/// A single structural change.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ChunkChange {
+    /// A block has been added at the given path.
+    ///
+    /// The position is the index of the new block among all sibling blocks (no matter which tag
+    /// name they have).
+    NodeAdded {
+        path: ChunkPath,
+        position: usize,
+        node: RppNode,
+    },
+    /// The block at the given path has been removed.
+    NodeRemoved { path: ChunkPath },
+    /// The parameters in the opening line of the block at the given path have changed.
+    ParamsChanged {
+        path: ChunkPath,
+        params: Vec<String>,
+    },
+    /// A simple line has been added to the block at the given path.
+    ///
+    /// The index is the index of the new line among all lines with the same key. The position is
+    /// the index of the new line among all items of the block (simple lines and nested blocks).
+    LineAdded {
+        path: ChunkPath,
+        index: u32,
+        position: usize,
+        line: RppLine,
+    },
+    /// The simple line with the given key and index has been removed from the block at the given
+    /// path.
+    LineRemoved {
+        path: ChunkPath,
+        key: String,
+        index: u32,
+    },
+    /// The values of the simple line with the key of the given line and the given index have
+    /// changed.
+    LineChanged {
+        path: ChunkPath,
+        index: u32,
+        line: RppLine,
+    },
+}
+
+/// A list of structural changes which turns one chunk into another one.
+///
+/// Changes are ordered in a way that they can be applied one after the other.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct ChunkPatch {
+    pub changes: Vec<ChunkChange>,
+}
+
+impl ChunkPath {
+    pub fn new(segments: Vec<ChunkPathSegment>) -> ChunkPath {
+        ChunkPath { segments }
+    }
+
+    pub fn segments(&self) -> &[ChunkPathSegment] {
+        &self.segments
+    }
+
+    /// Returns the path of the block containing this one or `None` if this is the root.
+    pub fn parent(&self) -> Option<ChunkPath> {
+        if self.segments.len() <= 1 {
+            return None;
+        }
+        Some(ChunkPath::new(
+            self.segments[..self.segments.len() - 1].to_vec(),
+        ))
+    }
+
+    fn child(&self, tag: &str, index: u32) -> ChunkPath {
+        let mut segments = self.segments.clone();
+        segments.push(ChunkPathSegment::new(tag, index));
+        ChunkPath::new(segments)
+    }
+}
+
+impl Display for ChunkPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, s) in self.segments.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{}", s)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ChunkPath {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments: Result<Vec<_>, _> = s.split('/').map(|s| s.parse()).collect();
+        Ok(ChunkPath::new(segments?))
+    }
+}
+
+impl ChunkPathSegment {
+    pub fn new(tag: impl Into<String>, index: u32) -> ChunkPathSegment {
+        ChunkPathSegment {
+            tag: tag.into(),
+            index,
+        }
+    }
+}
+
+impl Display for ChunkPathSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.index == 0 {
+            f.write_str(&self.tag)
+        } else {
+            write!(f, "{}[{}]", self.tag, self.index)
+        }
+    }
+}
+
+impl FromStr for ChunkPathSegment {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tag, index) = match s.find('[') {
+            None => (s, 0),
+            Some(pos) => {
+                let index = s[pos + 1..]
+                    .strip_suffix(']')
+                    .ok_or("missing closing bracket")?
+                    .parse()
+                    .map_err(|_| "invalid index")?;
+                (&s[..pos], index)
+            }
+        };
+        if tag.is_empty() {
+            return Err("empty tag name");
+        }
+        Ok(ChunkPathSegment::new(tag, index))
+    }
+}
+
+impl ChunkChange {
+    pub fn path(&self) -> &ChunkPath {
+        use ChunkChange::*;
+        match self {
+            NodeAdded { path, .. }
+            | NodeRemoved { path }
+            | ParamsChanged { path, .. }
+            | LineAdded { path, .. }
+            | LineRemoved { path, .. }
+            | LineChanged { path, .. } => path,
+        }
+    }
+}
+
+impl Display for ChunkChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use ChunkChange::*;
+        let (prefix, line_ref) = match self {
+            NodeAdded { .. } => ('+', None),
+            NodeRemoved { .. } => ('-', None),
+            ParamsChanged { .. } => ('~', None),
+            LineAdded { index, line, .. } => ('+', Some((line_key(line), *index))),
+            LineRemoved { key, index, .. } => ('-', Some((key.as_str(), *index))),
+            LineChanged { index, line, .. } => ('~', Some((line_key(line), *index))),
+        };
+        write!(f, "{} {}", prefix, self.path())?;
+        if let Some((key, index)) = line_ref {
+            // Displayed in the same way as a path segment, e.g. `BYPASS[1]`
+            write!(f, " {}", ChunkPathSegment::new(key, index))?;
+        }
+        Ok(())
+    }
+}
+
+impl ChunkPatch {
+    /// Computes the changes which turn the `old` block tree into the `new` one.
+    ///
+    /// Nested blocks are matched by tag name and position. Simple lines are matched by key (the
+    /// first token) and position among the lines with the same key. Both root blocks must have the
+    /// same tag name.
+    pub fn from_diff(old: &RppNode, new: &RppNode) -> Result<ChunkPatch, &'static str> {
+        if old.tag != new.tag {
+            return Err("root blocks have different tag names");
+        }
+        let mut patch = ChunkPatch::default();
+        let root_path = ChunkPath::new(vec![ChunkPathSegment::new(new.tag.as_str(), 0)]);
+        patch.diff_nodes(&root_path, old, new);
+        Ok(patch)
+    }
+
+    /// Computes the changes between two chunk regions which must each contain exactly one block,
+    /// e.g. two snapshots of `Track::chunk()`.
+    pub fn between(old: &ChunkRegion, new: &ChunkRegion) -> Result<ChunkPatch, &'static str> {
+        let old = RppNode::parse(&old.content())?;
+        let new = RppNode::parse(&new.content())?;
+        ChunkPatch::from_diff(&old, &new)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies this patch to the given block tree.
+    ///
+    /// Fails if a path can't be resolved, in which case some changes might already have been
+    /// applied.
+    pub fn apply_to_node(&self, target: &mut RppNode) -> Result<(), &'static str> {
+        for change in &self.changes {
+            apply_change(target, change)?;
+        }
+        Ok(())
+    }
+
+    /// Applies this patch to the block contained in the given chunk region.
+    ///
+    /// The region content is replaced only if all changes could be applied.
+    pub fn apply_to(&self, target: &ChunkRegion) -> Result<(), &'static str> {
+        let mut node = RppNode::parse(&target.content())?;
+        self.apply_to_node(&mut node)?;
+        target
+            .parent_chunk()
+            .replace_region(target, &node.to_string());
+        Ok(())
+    }
+
+    // Emits changes of nested blocks first so that a LineAdded can rely on the final set of nested
+    // blocks when being inserted. Additions come after all removals and in the order of their
+    // position, so that each one can be inserted at its final position.
+    fn diff_nodes(&mut self, path: &ChunkPath, old: &RppNode, new: &RppNode) {
+        let mut additions = vec![];
+        for tag in child_tags(old, new) {
+            let old_children: Vec<_> = old.children_named(tag).collect();
+            let new_children: Vec<_> = new.children_named(tag).collect();
+            let common_count = old_children.len().min(new_children.len());
+            for (i, (old_child, new_child)) in old_children.iter().zip(&new_children).enumerate() {
+                self.diff_nodes(&path.child(tag, i as u32), old_child, new_child);
+            }
+            for i in (common_count..old_children.len()).rev() {
+                self.changes.push(ChunkChange::NodeRemoved {
+                    path: path.child(tag, i as u32),
+                });
+            }
+            for (i, child) in new_children.iter().enumerate().skip(common_count) {
+                additions.push(ChunkChange::NodeAdded {
+                    path: path.child(tag, i as u32),
+                    position: block_position(new, tag, i).expect("added block not found"),
+                    node: (*child).clone(),
+                });
+            }
+        }
+        additions.sort_by_key(|a| match a {
+            ChunkChange::NodeAdded { position, .. } => *position,
+            _ => unreachable!("not an addition"),
+        });
+        self.changes.extend(additions);
+        if old.params != new.params {
+            self.changes.push(ChunkChange::ParamsChanged {
+                path: path.clone(),
+                params: new.params.clone(),
+            });
+        }
+        self.diff_lines(path, old, new);
+    }
+
+    // Changes refer to the original line indexes, so they come before the removals. Removals of
+    // lines with the same key are emitted from the last one to the first one.
+    fn diff_lines(&mut self, path: &ChunkPath, old: &RppNode, new: &RppNode) {
+        let mut removals = vec![];
+        let mut additions = vec![];
+        for key in line_keys(old, new) {
+            let old_lines: Vec<_> = old.lines().filter(|l| line_key(l) == key).collect();
+            let new_lines: Vec<_> = new.lines().filter(|l| line_key(l) == key).collect();
+            let common_count = old_lines.len().min(new_lines.len());
+            for (i, (old_line, new_line)) in old_lines.iter().zip(&new_lines).enumerate() {
+                if old_line != new_line {
+                    self.changes.push(ChunkChange::LineChanged {
+                        path: path.clone(),
+                        index: i as u32,
+                        line: (*new_line).clone(),
+                    });
+                }
+            }
+            for i in (common_count..old_lines.len()).rev() {
+                removals.push(ChunkChange::LineRemoved {
+                    path: path.clone(),
+                    key: key.to_string(),
+                    index: i as u32,
+                });
+            }
+            for (i, line) in new_lines.iter().enumerate().skip(common_count) {
+                additions.push(ChunkChange::LineAdded {
+                    path: path.clone(),
+                    index: i as u32,
+                    position: line_position(new, key, i).expect("added line not found"),
+                    line: (*line).clone(),
+                });
+            }
+        }
+        additions.sort_by_key(|a| match a {
+            ChunkChange::LineAdded { position, .. } => *position,
+            _ => unreachable!("not an addition"),
+        });
+        self.changes.extend(removals);
+        self.changes.extend(additions);
+    }
+}
+
+impl Display for ChunkPatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+// Tag names of nested blocks of both nodes in order of first appearance.
+fn child_tags<'a>(old: &'a RppNode, new: &'a RppNode) -> Vec<&'a str> {
+    let mut tags: Vec<&str> = vec![];
+    for child in new.children().chain(old.children()) {
+        if !tags.contains(&child.tag.as_str()) {
+            tags.push(&child.tag);
+        }
+    }
+    tags
+}
+
+// Index of the given block among all nested blocks of the given node.
+fn block_position(node: &RppNode, tag: &str, index: usize) -> Option<usize> {
+    node.children()
+        .enumerate()
+        .filter(|(_, c)| c.tag == tag)
+        .nth(index)
+        .map(|(i, _)| i)
+}
+
+// Keys of simple lines of both nodes in order of first appearance.
+fn line_keys<'a>(old: &'a RppNode, new: &'a RppNode) -> Vec<&'a str> {
+    let mut keys: Vec<&str> = vec![];
+    for line in new.lines().chain(old.lines()) {
+        if !keys.contains(&line_key(line)) {
+            keys.push(line_key(line));
+        }
+    }
+    keys
+}
+
+fn line_key(line: &RppLine) -> &str {
+    line.key().unwrap_or_default()
+}
+
+// Index of the given simple line among all items of the given node.
+fn line_position(node: &RppNode, key: &str, index: usize) -> Option<usize> {
+    node.items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| match item {
+            RppItem::Line(l) => line_key(l) == key,
+            RppItem::Node(_) => false,
+        })
+        .nth(index)
+        .map(|(i, _)| i)
+}
+
+fn apply_change(root: &mut RppNode, change: &ChunkChange) -> Result<(), &'static str> {
+    use ChunkChange::*;
+    match change {
+        NodeAdded {
+            path,
+            position,
+            node,
+        } => {
+            let parent_path = path.parent().ok_or("can't add root block")?;
+            let parent = resolve_mut(root, &parent_path)?;
+            // Insert in front of the block which currently occupies the position
+            let insert_pos = parent
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| matches!(item, RppItem::Node(_)))
+                .nth(*position)
+                .map(|(i, _)| i)
+                .unwrap_or(parent.items.len());
+            parent.items.insert(insert_pos, RppItem::Node(node.clone()));
+        }
+        NodeRemoved { path } => {
+            let segment = path.segments.last().ok_or("empty path")?;
+            let parent_path = path.parent().ok_or("can't remove root block")?;
+            let parent = resolve_mut(root, &parent_path)?;
+            let pos = item_index(parent, &segment.tag, segment.index).ok_or("block not found")?;
+            parent.items.remove(pos);
+        }
+        ParamsChanged { path, params } => {
+            let node = resolve_mut(root, path)?;
+            node.params = params.clone();
+        }
+        LineAdded {
+            path,
+            position,
+            line,
+            ..
+        } => {
+            let node = resolve_mut(root, path)?;
+            // The target might contain fewer items than the block the change has been computed from
+            let insert_pos = (*position).min(node.items.len());
+            node.items.insert(insert_pos, RppItem::Line(line.clone()));
+        }
+        LineRemoved { path, key, index } => {
+            let node = resolve_mut(root, path)?;
+            let pos = line_position(node, key, *index as usize).ok_or("line not found")?;
+            node.items.remove(pos);
+        }
+        LineChanged { path, index, line } => {
+            let node = resolve_mut(root, path)?;
+            let pos =
+                line_position(node, line_key(line), *index as usize).ok_or("line not found")?;
+            node.items[pos] = RppItem::Line(line.clone());
+        }
+    };
+    Ok(())
+}
+
+fn resolve_mut<'a>(
+    root: &'a mut RppNode,
+    path: &ChunkPath,
+) -> Result<&'a mut RppNode, &'static str> {
+    let (first, rest) = path.segments.split_first().ok_or("empty path")?;
+    if first.tag != root.tag || first.index != 0 {
+        return Err("root block doesn't match path");
+    }
+    let mut current = root;
+    for segment in rest {
+        current = current
+            .children_mut()
+            .filter(|c| c.tag == segment.tag)
+            .nth(segment.index as usize)
+            .ok_or("block not found")?;
+    }
+    Ok(current)
+}
+
+fn item_index(parent: &RppNode, tag: &str, index: u32) -> Option<usize> {
+    parent
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| match item {
+            RppItem::Node(n) => n.tag == tag,
+            RppItem::Line(_) => false,
+        })
+        .nth(index as usize)
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chunk;
+
+    const OLD_TRACK_CHUNK: &str = r#"<TRACK {8F9D2B6C-57A5-4B6B-A3C7-0B3C9B1A4C5E}
+  NAME "My track"
+  VOLPAN 1 0 -1 -1 1
+  <FXCHAIN
+    SHOW 0
+    BYPASS 0 0 0
+    <JS utility/volume ""
+      0 - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+    >
+    FXID {2E1F0A5B-96E1-4D5A-8C3A-1B2D6E0F9A11}
+    WAK 0 0
+  >
+>
+"#;
+
+    const NEW_TRACK_CHUNK: &str = r#"<TRACK {8F9D2B6C-57A5-4B6B-A3C7-0B3C9B1A4C5E}
+  NAME "Renamed"
+  VOLPAN 1 0 -1 -1 1
+  <FXCHAIN
+    SHOW 0
+    BYPASS 0 0 0
+    <JS utility/volume ""
+      -6 - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+    >
+    FXID {2E1F0A5B-96E1-4D5A-8C3A-1B2D6E0F9A11}
+    WAK 0 0
+    BYPASS 1 0 0
+    <JS utility/volume ""
+      0 - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+    >
+    FXID {6C0B7F3E-2A4D-4E8B-9F1C-5D3A7B2E8C40}
+    WAK 0 0
+  >
+>
+"#;
+
+    #[test]
+    fn paths() {
+        let path: ChunkPath = "TRACK/FXCHAIN/VST[2]".parse().unwrap();
+        assert_eq!(
+            path.segments(),
+            &[
+                ChunkPathSegment::new("TRACK", 0),
+                ChunkPathSegment::new("FXCHAIN", 0),
+                ChunkPathSegment::new("VST", 2)
+            ]
+        );
+        assert_eq!(path.to_string(), "TRACK/FXCHAIN/VST[2]");
+        assert_eq!(path.parent().unwrap().to_string(), "TRACK/FXCHAIN");
+        assert!("TRACK/VST[x]".parse::<ChunkPath>().is_err());
+        assert!("TRACK//VST".parse::<ChunkPath>().is_err());
+    }
+
+    #[test]
+    fn diff() {
+        let old = RppNode::parse(OLD_TRACK_CHUNK).unwrap();
+        let new = RppNode::parse(NEW_TRACK_CHUNK).unwrap();
+        let patch = ChunkPatch::from_diff(&old, &new).unwrap();
+        assert_eq!(
+            patch.to_string(),
+            "- TRACK/FXCHAIN/JS 0\n\
+             + TRACK/FXCHAIN/JS -6\n\
+             + TRACK/FXCHAIN/JS[1]\n\
+             + TRACK/FXCHAIN BYPASS[1]\n\
+             + TRACK/FXCHAIN FXID[1]\n\
+             + TRACK/FXCHAIN WAK[1]\n\
+             ~ TRACK NAME\n"
+        );
+        assert!(ChunkPatch::from_diff(&old, &old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply() {
+        let old = RppNode::parse(OLD_TRACK_CHUNK).unwrap();
+        let new = RppNode::parse(NEW_TRACK_CHUNK).unwrap();
+        let patch = ChunkPatch::from_diff(&old, &new).unwrap();
+        let mut target = old.clone();
+        patch.apply_to_node(&mut target).unwrap();
+        assert_eq!(target, new);
+        assert_eq!(target.to_string(), NEW_TRACK_CHUNK);
+        // Reverse
+        let patch = ChunkPatch::from_diff(&new, &old).unwrap();
+        patch.apply_to_node(&mut target).unwrap();
+        assert_eq!(target.to_string(), OLD_TRACK_CHUNK);
+    }
+
+    #[test]
+    fn apply_keeps_unrelated_changes_of_target() {
+        // Given
+        let old = RppNode::parse("<TRACK a\n  NAME a\n  VOLPAN 1 0\n  PEAKCOL 1\n>\n").unwrap();
+        let new = RppNode::parse("<TRACK b\n  NAME b\n  VOLPAN 1 0\n>\n").unwrap();
+        let mut target =
+            RppNode::parse("<TRACK a\n  NAME a\n  VOLPAN 0.5 0\n  PEAKCOL 1\n  MUTESOLO 1\n>\n")
+                .unwrap();
+        // When
+        let patch = ChunkPatch::from_diff(&old, &new).unwrap();
+        patch.apply_to_node(&mut target).unwrap();
+        // Then
+        assert_eq!(
+            patch.to_string(),
+            "~ TRACK\n~ TRACK NAME\n- TRACK PEAKCOL\n"
+        );
+        assert_eq!(
+            target.to_string(),
+            "<TRACK b\n  NAME b\n  VOLPAN 0.5 0\n  MUTESOLO 1\n>\n"
+        );
+    }
+
+    #[test]
+    fn add_in_front_of_block_with_other_tag() {
+        // Given
+        let old = RppNode::parse("<TRACK\n  NAME a\n  <ITEM\n  >\n>\n").unwrap();
+        let new = RppNode::parse("<TRACK\n  NAME a\n  <FXCHAIN\n  >\n  <ITEM\n  >\n>\n").unwrap();
+        // When
+        let patch = ChunkPatch::from_diff(&old, &new).unwrap();
+        let mut target = old.clone();
+        patch.apply_to_node(&mut target).unwrap();
+        // Then
+        assert_eq!(patch.to_string(), "+ TRACK/FXCHAIN\n");
+        assert_eq!(target, new);
+    }
+
+    #[test]
+    fn add_and_remove_blocks_with_different_tags() {
+        // Given
+        let old = RppNode::parse("<X\n  <R\n  >\n  <A\n  >\n>\n").unwrap();
+        let new = RppNode::parse("<X\n  <A\n  >\n  <B\n  >\n  <A\n  >\n>\n").unwrap();
+        // When
+        let patch = ChunkPatch::from_diff(&old, &new).unwrap();
+        let mut target = old.clone();
+        patch.apply_to_node(&mut target).unwrap();
+        // Then
+        assert_eq!(target, new);
+    }
+
+    #[test]
+    fn apply_to_chunk_region() {
+        let old = Chunk::new(OLD_TRACK_CHUNK.to_string());
+        let new = Chunk::new(NEW_TRACK_CHUNK.to_string());
+        let patch = ChunkPatch::between(&old.region(), &new.region()).unwrap();
+        let target = Chunk::new(OLD_TRACK_CHUNK.to_string());
+        patch.apply_to(&target.region()).unwrap();
+        assert_eq!(target.to_string(), NEW_TRACK_CHUNK);
+    }
+}
@@ -0,0 +1,794 @@
+//! Recording and replaying of control surface events, e.g. for reproducing bugs.
+use crate::{ControlSurfaceEvent, ControlSurfaceMiddleware, Guid, Project, Track};
+use reaper_medium::{
+    AutomationMode, Bpm, ExtResetArgs, ExtSetBpmAndPlayRateArgs, ExtSetFocusedFxArgs,
+    ExtSetFxChangeArgs, ExtSetFxEnabledArgs, ExtSetFxOpenArgs, ExtSetFxParamArgs,
+    ExtSetInputMonitorArgs, ExtSetLastTouchedFxArgs, ExtSetPanExArgs,
+    ExtSetProjectMarkerChangeArgs, ExtSetRecvPanArgs, ExtSetRecvVolumeArgs, ExtSetSendPanArgs,
+    ExtSetSendVolumeArgs, ExtSupportsExtendedTouchArgs, ExtTrackFxPresetChangedArgs,
+    GetTouchStateArgs, InputMonitoringMode, IsKeyDownArgs, MediaTrack, OnTrackSelectionArgs, Pan,
+    PlaybackSpeedFactor, QualifiedFxLocation, ReaperNormalizedFxParamValue, ReaperPanValue,
+    ReaperStr, ReaperStringArg, ReaperVolumeValue, SetAutoModeArgs, SetPlayStateArgs,
+    SetRepeatStateArgs, SetSurfaceMuteArgs, SetSurfacePanArgs, SetSurfaceRecArmArgs,
+    SetSurfaceSelectedArgs, SetSurfaceSoloArgs, SetSurfaceVolumeArgs, SetTrackTitleArgs,
+    TrackFxChainType, TrackFxLocation, VersionDependentFxLocation, VersionDependentTrackFxLocation,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Control surface events in the order in which they reached a [`ControlSurfaceEventRecorder`].
+///
+/// [`ControlSurfaceEventRecorder`]: struct.ControlSurfaceEventRecorder.html
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct ControlSurfaceEventRecording {
+    pub entries: Vec<RecordedControlSurfaceEvent>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct RecordedControlSurfaceEvent {
+    /// Time since the start of the recording.
+    pub time: Duration,
+    /// Number of main loop cycles (`run()` invocations) since the start of the recording.
+    pub cycle: u64,
+    pub event: OwnedControlSurfaceEvent,
+}
+
+/// A track as it was resolved at the time of recording.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct RecordedTrack {
+    pub guid: Guid,
+    /// `None` for the master track.
+    pub index: Option<u32>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct RecordedFxLocation {
+    /// Parent track.
+    pub track: RecordedTrack,
+    pub fx_location: VersionDependentFxLocation,
+}
+
+/// A part of a recording which couldn't be replayed.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ReplayFailure {
+    TrackNotFound { entry_index: usize, track: Guid },
+}
+
+/// Owned and serializable counterpart of [`ControlSurfaceEvent`].
+///
+/// Tracks are identified by their GUIDs instead of pointers.
+///
+/// [`ControlSurfaceEvent`]: enum.ControlSurfaceEvent.html
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub enum OwnedControlSurfaceEvent {
+    CloseNoReset,
+    SetTrackListChange,
+    SetSurfaceVolume {
+        track: RecordedTrack,
+        volume: ReaperVolumeValue,
+    },
+    SetSurfacePan {
+        track: RecordedTrack,
+        pan: ReaperPanValue,
+    },
+    SetSurfaceMute {
+        track: RecordedTrack,
+        is_mute: bool,
+    },
+    SetSurfaceSelected {
+        track: RecordedTrack,
+        is_selected: bool,
+    },
+    SetSurfaceSolo {
+        track: RecordedTrack,
+        is_solo: bool,
+    },
+    SetSurfaceRecArm {
+        track: RecordedTrack,
+        is_armed: bool,
+    },
+    SetPlayState {
+        is_playing: bool,
+        is_paused: bool,
+        is_recording: bool,
+    },
+    SetRepeatState {
+        is_enabled: bool,
+    },
+    SetTrackTitle {
+        track: RecordedTrack,
+        name: String,
+    },
+    SetAutoMode {
+        mode: AutomationMode,
+    },
+    ResetCachedVolPanStates,
+    OnTrackSelection {
+        track: RecordedTrack,
+    },
+    ExtSetInputMonitor {
+        track: RecordedTrack,
+        mode: InputMonitoringMode,
+    },
+    ExtSetFxParam {
+        track: RecordedTrack,
+        fx_index: u32,
+        param_index: u32,
+        param_value: ReaperNormalizedFxParamValue,
+    },
+    ExtSetFxParamRecFx {
+        track: RecordedTrack,
+        fx_index: u32,
+        param_index: u32,
+        param_value: ReaperNormalizedFxParamValue,
+    },
+    ExtSetFxEnabled {
+        track: RecordedTrack,
+        fx_location: VersionDependentTrackFxLocation,
+        is_enabled: bool,
+    },
+    ExtSetSendVolume {
+        track: RecordedTrack,
+        send_index: u32,
+        volume: ReaperVolumeValue,
+    },
+    ExtSetSendPan {
+        track: RecordedTrack,
+        send_index: u32,
+        pan: ReaperPanValue,
+    },
+    ExtSetRecvVolume {
+        track: RecordedTrack,
+        receive_index: u32,
+        volume: ReaperVolumeValue,
+    },
+    ExtSetRecvPan {
+        track: RecordedTrack,
+        receive_index: u32,
+        pan: ReaperPanValue,
+    },
+    ExtSetPanExt {
+        track: RecordedTrack,
+        pan: Pan,
+    },
+    ExtSetFocusedFx {
+        fx_location: Option<RecordedFxLocation>,
+    },
+    ExtSetLastTouchedFx {
+        fx_location: Option<RecordedFxLocation>,
+    },
+    ExtSetFxOpen {
+        track: RecordedTrack,
+        fx_location: VersionDependentTrackFxLocation,
+        is_open: bool,
+    },
+    ExtSetFxChange {
+        track: RecordedTrack,
+        fx_chain_type: Option<TrackFxChainType>,
+    },
+    ExtSetBpmAndPlayRate {
+        tempo: Option<Bpm>,
+        play_rate: Option<PlaybackSpeedFactor>,
+    },
+    ExtTrackFxPresetChanged {
+        track: RecordedTrack,
+        fx_location: TrackFxLocation,
+    },
+    ExtReset,
+    ExtSetProjectMarkerChange,
+}
+
+impl ControlSurfaceEventRecording {
+    /// Reads the given recording file.
+    pub fn load(path: &Path) -> io::Result<ControlSurfaceEventRecording> {
+        let text = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes this recording to the given file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = serde_yaml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, text)
+    }
+
+    /// Passes all recorded events to the given middleware, invoking its `run()` method as often as
+    /// it was invoked during the recording.
+    ///
+    /// The recorded timing is not reproduced. `resolve_track` maps recorded tracks to track
+    /// pointers. Within REAPER, [`RecordedTrack::resolve()`] does that. Outside of REAPER, e.g.
+    /// when replaying against a stand-in middleware, any unique pointers can be handed out.
+    /// Events whose tracks can't be resolved are skipped and reported as error. All other events
+    /// are replayed nevertheless.
+    ///
+    /// [`RecordedTrack::resolve()`]: struct.RecordedTrack.html#method.resolve
+    pub fn replay(
+        &self,
+        middleware: &mut impl ControlSurfaceMiddleware,
+        mut resolve_track: impl FnMut(&RecordedTrack) -> Option<MediaTrack>,
+    ) -> Result<(), Vec<ReplayFailure>> {
+        let mut failures = vec![];
+        let mut cycle = 0;
+        for (i, entry) in self.entries.iter().enumerate() {
+            while cycle < entry.cycle {
+                middleware.run();
+                cycle += 1;
+            }
+            let result = entry
+                .event
+                .with_event(&mut resolve_track, |e| middleware.handle_event(e));
+            if let Err(track) = result {
+                failures.push(ReplayFailure::TrackNotFound {
+                    entry_index: i,
+                    track: track.guid,
+                });
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+impl RecordedTrack {
+    pub fn capture(media_track: MediaTrack) -> RecordedTrack {
+        let track = Track::new(media_track, None);
+        RecordedTrack {
+            guid: *track.guid(),
+            index: track.index(),
+        }
+    }
+
+    /// Looks up the track with the recorded GUID in the given project.
+    pub fn resolve(&self, project: Project) -> Option<MediaTrack> {
+        let track = match self.index {
+            None => project.master_track(),
+            Some(_) => project.track_by_guid(&self.guid),
+        };
+        if !track.is_available() {
+            return None;
+        }
+        Some(track.raw())
+    }
+}
+
+impl RecordedFxLocation {
+    fn capture(location: QualifiedFxLocation) -> RecordedFxLocation {
+        RecordedFxLocation {
+            track: RecordedTrack::capture(location.track),
+            fx_location: location.fx_location,
+        }
+    }
+}
+
+impl OwnedControlSurfaceEvent {
+    /// Resolves the tracks in the given event. Must be called in the main thread.
+    pub fn capture(event: ControlSurfaceEvent) -> OwnedControlSurfaceEvent {
+        use ControlSurfaceEvent as E;
+        use OwnedControlSurfaceEvent as O;
+        let track = RecordedTrack::capture;
+        match event {
+            E::CloseNoReset => O::CloseNoReset,
+            E::SetTrackListChange => O::SetTrackListChange,
+            E::SetSurfaceVolume(args) => O::SetSurfaceVolume {
+                track: track(args.track),
+                volume: args.volume,
+            },
+            E::SetSurfacePan(args) => O::SetSurfacePan {
+                track: track(args.track),
+                pan: args.pan,
+            },
+            E::SetSurfaceMute(args) => O::SetSurfaceMute {
+                track: track(args.track),
+                is_mute: args.is_mute,
+            },
+            E::SetSurfaceSelected(args) => O::SetSurfaceSelected {
+                track: track(args.track),
+                is_selected: args.is_selected,
+            },
+            E::SetSurfaceSolo(args) => O::SetSurfaceSolo {
+                track: track(args.track),
+                is_solo: args.is_solo,
+            },
+            E::SetSurfaceRecArm(args) => O::SetSurfaceRecArm {
+                track: track(args.track),
+                is_armed: args.is_armed,
+            },
+            E::SetPlayState(args) => O::SetPlayState {
+                is_playing: args.is_playing,
+                is_paused: args.is_paused,
+                is_recording: args.is_recording,
+            },
+            E::SetRepeatState(args) => O::SetRepeatState {
+                is_enabled: args.is_enabled,
+            },
+            E::SetTrackTitle(args) => O::SetTrackTitle {
+                track: track(args.track),
+                name: args.name.to_str().to_owned(),
+            },
+            E::SetAutoMode(args) => O::SetAutoMode { mode: args.mode },
+            E::ResetCachedVolPanStates => O::ResetCachedVolPanStates,
+            E::OnTrackSelection(args) => O::OnTrackSelection {
+                track: track(args.track),
+            },
+            E::ExtSetInputMonitor(args) => O::ExtSetInputMonitor {
+                track: track(args.track),
+                mode: args.mode,
+            },
+            E::ExtSetFxParam(args) => O::ExtSetFxParam {
+                track: track(args.track),
+                fx_index: args.fx_index,
+                param_index: args.param_index,
+                param_value: args.param_value,
+            },
+            E::ExtSetFxParamRecFx(args) => O::ExtSetFxParamRecFx {
+                track: track(args.track),
+                fx_index: args.fx_index,
+                param_index: args.param_index,
+                param_value: args.param_value,
+            },
+            E::ExtSetFxEnabled(args) => O::ExtSetFxEnabled {
+                track: track(args.track),
+                fx_location: args.fx_location,
+                is_enabled: args.is_enabled,
+            },
+            E::ExtSetSendVolume(args) => O::ExtSetSendVolume {
+                track: track(args.track),
+                send_index: args.send_index,
+                volume: args.volume,
+            },
+            E::ExtSetSendPan(args) => O::ExtSetSendPan {
+                track: track(args.track),
+                send_index: args.send_index,
+                pan: args.pan,
+            },
+            E::ExtSetRecvVolume(args) => O::ExtSetRecvVolume {
+                track: track(args.track),
+                receive_index: args.receive_index,
+                volume: args.volume,
+            },
+            E::ExtSetRecvPan(args) => O::ExtSetRecvPan {
+                track: track(args.track),
+                receive_index: args.receive_index,
+                pan: args.pan,
+            },
+            E::ExtSetPanExt(args) => O::ExtSetPanExt {
+                track: track(args.track),
+                pan: args.pan,
+            },
+            E::ExtSetFocusedFx(args) => O::ExtSetFocusedFx {
+                fx_location: args.fx_location.map(RecordedFxLocation::capture),
+            },
+            E::ExtSetLastTouchedFx(args) => O::ExtSetLastTouchedFx {
+                fx_location: args.fx_location.map(RecordedFxLocation::capture),
+            },
+            E::ExtSetFxOpen(args) => O::ExtSetFxOpen {
+                track: track(args.track),
+                fx_location: args.fx_location,
+                is_open: args.is_open,
+            },
+            E::ExtSetFxChange(args) => O::ExtSetFxChange {
+                track: track(args.track),
+                fx_chain_type: args.fx_chain_type,
+            },
+            E::ExtSetBpmAndPlayRate(args) => O::ExtSetBpmAndPlayRate {
+                tempo: args.tempo,
+                play_rate: args.play_rate,
+            },
+            E::ExtTrackFxPresetChanged(args) => O::ExtTrackFxPresetChanged {
+                track: track(args.track),
+                fx_location: args.fx_location,
+            },
+            E::ExtReset(_) => O::ExtReset,
+            E::ExtSetProjectMarkerChange(_) => O::ExtSetProjectMarkerChange,
+        }
+    }
+
+    /// Converts this event back into a borrowed control surface event and passes it to `f`.
+    ///
+    /// Returns the first track which couldn't be resolved as error.
+    pub fn with_event<'a, R>(
+        &'a self,
+        mut resolve_track: impl FnMut(&RecordedTrack) -> Option<MediaTrack>,
+        f: impl FnOnce(ControlSurfaceEvent) -> R,
+    ) -> Result<R, &'a RecordedTrack> {
+        use ControlSurfaceEvent as E;
+        use OwnedControlSurfaceEvent as O;
+        let mut track = |t: &'a RecordedTrack| resolve_track(t).ok_or(t);
+        let event = match self {
+            O::CloseNoReset => E::CloseNoReset,
+            O::SetTrackListChange => E::SetTrackListChange,
+            O::SetSurfaceVolume { track: t, volume } => E::SetSurfaceVolume(SetSurfaceVolumeArgs {
+                track: track(t)?,
+                volume: *volume,
+            }),
+            O::SetSurfacePan { track: t, pan } => E::SetSurfacePan(SetSurfacePanArgs {
+                track: track(t)?,
+                pan: *pan,
+            }),
+            O::SetSurfaceMute { track: t, is_mute } => E::SetSurfaceMute(SetSurfaceMuteArgs {
+                track: track(t)?,
+                is_mute: *is_mute,
+            }),
+            O::SetSurfaceSelected {
+                track: t,
+                is_selected,
+            } => E::SetSurfaceSelected(SetSurfaceSelectedArgs {
+                track: track(t)?,
+                is_selected: *is_selected,
+            }),
+            O::SetSurfaceSolo { track: t, is_solo } => E::SetSurfaceSolo(SetSurfaceSoloArgs {
+                track: track(t)?,
+                is_solo: *is_solo,
+            }),
+            O::SetSurfaceRecArm { track: t, is_armed } => {
+                E::SetSurfaceRecArm(SetSurfaceRecArmArgs {
+                    track: track(t)?,
+                    is_armed: *is_armed,
+                })
+            }
+            O::SetPlayState {
+                is_playing,
+                is_paused,
+                is_recording,
+            } => E::SetPlayState(SetPlayStateArgs {
+                is_playing: *is_playing,
+                is_paused: *is_paused,
+                is_recording: *is_recording,
+            }),
+            O::SetRepeatState { is_enabled } => E::SetRepeatState(SetRepeatStateArgs {
+                is_enabled: *is_enabled,
+            }),
+            O::SetTrackTitle { track: t, name } => {
+                // The event borrows the name, so it must be handed out within this scope
+                let name = ReaperStringArg::from(name.as_str()).into_inner();
+                let event = E::SetTrackTitle(SetTrackTitleArgs {
+                    track: track(t)?,
+                    name: &name,
+                });
+                return Ok(f(event));
+            }
+            O::SetAutoMode { mode } => E::SetAutoMode(SetAutoModeArgs { mode: *mode }),
+            O::ResetCachedVolPanStates => E::ResetCachedVolPanStates,
+            O::OnTrackSelection { track: t } => {
+                E::OnTrackSelection(OnTrackSelectionArgs { track: track(t)? })
+            }
+            O::ExtSetInputMonitor { track: t, mode } => {
+                E::ExtSetInputMonitor(ExtSetInputMonitorArgs {
+                    track: track(t)?,
+                    mode: *mode,
+                })
+            }
+            O::ExtSetFxParam {
+                track: t,
+                fx_index,
+                param_index,
+                param_value,
+            } => E::ExtSetFxParam(ExtSetFxParamArgs {
+                track: track(t)?,
+                fx_index: *fx_index,
+                param_index: *param_index,
+                param_value: *param_value,
+            }),
+            O::ExtSetFxParamRecFx {
+                track: t,
+                fx_index,
+                param_index,
+                param_value,
+            } => E::ExtSetFxParamRecFx(ExtSetFxParamArgs {
+                track: track(t)?,
+                fx_index: *fx_index,
+                param_index: *param_index,
+                param_value: *param_value,
+            }),
+            O::ExtSetFxEnabled {
+                track: t,
+                fx_location,
+                is_enabled,
+            } => E::ExtSetFxEnabled(ExtSetFxEnabledArgs {
+                track: track(t)?,
+                fx_location: *fx_location,
+                is_enabled: *is_enabled,
+            }),
+            O::ExtSetSendVolume {
+                track: t,
+                send_index,
+                volume,
+            } => E::ExtSetSendVolume(ExtSetSendVolumeArgs {
+                track: track(t)?,
+                send_index: *send_index,
+                volume: *volume,
+            }),
+            O::ExtSetSendPan {
+                track: t,
+                send_index,
+                pan,
+            } => E::ExtSetSendPan(ExtSetSendPanArgs {
+                track: track(t)?,
+                send_index: *send_index,
+                pan: *pan,
+            }),
+            O::ExtSetRecvVolume {
+                track: t,
+                receive_index,
+                volume,
+            } => E::ExtSetRecvVolume(ExtSetRecvVolumeArgs {
+                track: track(t)?,
+                receive_index: *receive_index,
+                volume: *volume,
+            }),
+            O::ExtSetRecvPan {
+                track: t,
+                receive_index,
+                pan,
+            } => E::ExtSetRecvPan(ExtSetRecvPanArgs {
+                track: track(t)?,
+                receive_index: *receive_index,
+                pan: *pan,
+            }),
+            O::ExtSetPanExt { track: t, pan } => E::ExtSetPanExt(ExtSetPanExArgs {
+                track: track(t)?,
+                pan: *pan,
+            }),
+            O::ExtSetFocusedFx { fx_location } => {
+                let fx_location = match fx_location {
+                    None => None,
+                    Some(l) => Some(QualifiedFxLocation {
+                        track: track(&l.track)?,
+                        fx_location: l.fx_location,
+                    }),
+                };
+                E::ExtSetFocusedFx(ExtSetFocusedFxArgs { fx_location })
+            }
+            O::ExtSetLastTouchedFx { fx_location } => {
+                let fx_location = match fx_location {
+                    None => None,
+                    Some(l) => Some(QualifiedFxLocation {
+                        track: track(&l.track)?,
+                        fx_location: l.fx_location,
+                    }),
+                };
+                E::ExtSetLastTouchedFx(ExtSetLastTouchedFxArgs { fx_location })
+            }
+            O::ExtSetFxOpen {
+                track: t,
+                fx_location,
+                is_open,
+            } => E::ExtSetFxOpen(ExtSetFxOpenArgs {
+                track: track(t)?,
+                fx_location: *fx_location,
+                is_open: *is_open,
+            }),
+            O::ExtSetFxChange {
+                track: t,
+                fx_chain_type,
+            } => E::ExtSetFxChange(ExtSetFxChangeArgs {
+                track: track(t)?,
+                fx_chain_type: *fx_chain_type,
+            }),
+            O::ExtSetBpmAndPlayRate { tempo, play_rate } => {
+                E::ExtSetBpmAndPlayRate(ExtSetBpmAndPlayRateArgs {
+                    tempo: *tempo,
+                    play_rate: *play_rate,
+                })
+            }
+            O::ExtTrackFxPresetChanged {
+                track: t,
+                fx_location,
+            } => E::ExtTrackFxPresetChanged(ExtTrackFxPresetChangedArgs {
+                track: track(t)?,
+                fx_location: *fx_location,
+            }),
+            O::ExtReset => E::ExtReset(ExtResetArgs),
+            O::ExtSetProjectMarkerChange => {
+                E::ExtSetProjectMarkerChange(ExtSetProjectMarkerChangeArgs)
+            }
+        };
+        Ok(f(event))
+    }
+}
+
+/// Records all events which reach the given middleware before passing them on.
+///
+/// Meant to be used as middleware of a `MiddlewareControlSurface`.
+#[derive(Debug)]
+pub struct ControlSurfaceEventRecorder<M> {
+    inner: M,
+    start_time: Instant,
+    cycle: u64,
+    recording: RefCell<ControlSurfaceEventRecording>,
+}
+
+impl<M: ControlSurfaceMiddleware> ControlSurfaceEventRecorder<M> {
+    pub fn new(inner: M) -> ControlSurfaceEventRecorder<M> {
+        ControlSurfaceEventRecorder {
+            inner,
+            start_time: Instant::now(),
+            cycle: 0,
+            recording: Default::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.inner
+    }
+
+    /// Returns the events recorded so far and continues with an empty recording.
+    ///
+    /// Cycles and times of subsequent events are still relative to the start of the first
+    /// recording.
+    pub fn take_recording(&self) -> ControlSurfaceEventRecording {
+        self.recording.replace(Default::default())
+    }
+
+    /// Writes the events recorded so far to the given file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        self.recording.borrow().save(path)
+    }
+}
+
+impl<M: ControlSurfaceMiddleware> ControlSurfaceMiddleware for ControlSurfaceEventRecorder<M> {
+    fn run(&mut self) {
+        self.cycle += 1;
+        self.inner.run();
+    }
+
+    fn handle_event(&self, event: ControlSurfaceEvent) -> bool {
+        let entry = RecordedControlSurfaceEvent {
+            time: self.start_time.elapsed(),
+            cycle: self.cycle,
+            event: OwnedControlSurfaceEvent::capture(event),
+        };
+        self.recording.borrow_mut().entries.push(entry);
+        self.inner.handle_event(event)
+    }
+
+    fn get_type_string(&self) -> Option<&ReaperStr> {
+        self.inner.get_type_string()
+    }
+
+    fn get_desc_string(&self) -> Option<&ReaperStr> {
+        self.inner.get_desc_string()
+    }
+
+    fn get_config_string(&self) -> Option<&ReaperStr> {
+        self.inner.get_config_string()
+    }
+
+    fn get_touch_state(&self, args: GetTouchStateArgs) -> bool {
+        self.inner.get_touch_state(args)
+    }
+
+    fn is_key_down(&self, args: IsKeyDownArgs) -> bool {
+        self.inner.is_key_down(args)
+    }
+
+    fn ext_supports_extended_touch(&self, args: ExtSupportsExtendedTouchArgs) -> i32 {
+        self.inner.ext_supports_extended_touch(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr::NonNull;
+
+    #[test]
+    fn save_load_and_replay() {
+        // Given
+        let known_track = recorded_track("00000001-0000-0000-0000-000000000000", Some(0));
+        let unknown_track = recorded_track("00000002-0000-0000-0000-000000000000", Some(1));
+        let recording = ControlSurfaceEventRecording {
+            entries: vec![
+                entry(0, OwnedControlSurfaceEvent::SetTrackListChange),
+                entry(
+                    2,
+                    OwnedControlSurfaceEvent::SetSurfaceVolume {
+                        track: known_track.clone(),
+                        volume: ReaperVolumeValue::new(0.5),
+                    },
+                ),
+                entry(
+                    2,
+                    OwnedControlSurfaceEvent::OnTrackSelection {
+                        track: unknown_track.clone(),
+                    },
+                ),
+                entry(
+                    3,
+                    OwnedControlSurfaceEvent::SetRepeatState { is_enabled: true },
+                ),
+            ],
+        };
+        let media_track: MediaTrack = NonNull::dangling();
+        let mut middleware = StubMiddleware::default();
+        let path = std::env::temp_dir().join(format!(
+            "reaper-rs-control-surface-recording-{}.yaml",
+            std::process::id()
+        ));
+        // When
+        recording.save(&path).unwrap();
+        let loaded = ControlSurfaceEventRecording::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let deserialized = loaded.unwrap();
+        let result = deserialized.replay(&mut middleware, |t| {
+            if t.guid == known_track.guid {
+                Some(media_track)
+            } else {
+                None
+            }
+        });
+        // Then
+        assert_eq!(deserialized, recording);
+        assert_eq!(
+            result,
+            Err(vec![ReplayFailure::TrackNotFound {
+                entry_index: 2,
+                track: unknown_track.guid,
+            }])
+        );
+        let expected_calls: Vec<String> = vec![
+            format!("{:?}", ControlSurfaceEvent::SetTrackListChange),
+            "run".to_owned(),
+            "run".to_owned(),
+            format!(
+                "{:?}",
+                ControlSurfaceEvent::SetSurfaceVolume(SetSurfaceVolumeArgs {
+                    track: media_track,
+                    volume: ReaperVolumeValue::new(0.5),
+                })
+            ),
+            "run".to_owned(),
+            format!(
+                "{:?}",
+                ControlSurfaceEvent::SetRepeatState(SetRepeatStateArgs { is_enabled: true })
+            ),
+        ];
+        assert_eq!(middleware.calls.into_inner(), expected_calls);
+    }
+
+    #[derive(Default)]
+    struct StubMiddleware {
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl ControlSurfaceMiddleware for StubMiddleware {
+        fn run(&mut self) {
+            self.calls.get_mut().push("run".to_owned());
+        }
+
+        fn handle_event(&self, event: ControlSurfaceEvent) -> bool {
+            self.calls.borrow_mut().push(format!("{:?}", event));
+            true
+        }
+    }
+
+    fn recorded_track(guid: &str, index: Option<u32>) -> RecordedTrack {
+        RecordedTrack {
+            guid: Guid::from_string_without_braces(guid).unwrap(),
+            index,
+        }
+    }
+
+    fn entry(cycle: u64, event: OwnedControlSurfaceEvent) -> RecordedControlSurfaceEvent {
+        RecordedControlSurfaceEvent {
+            time: Duration::from_millis(cycle * 30),
+            cycle,
+            event,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{Chunk, Reaper};
+use reaper_medium::{ChunkCacheHint, TrackEnvelope};
+use std::convert::TryInto;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Envelope {
+    raw: TrackEnvelope,
+}
+
+impl Envelope {
+    pub fn new(raw: TrackEnvelope) -> Envelope {
+        Envelope { raw }
+    }
+
+    pub fn raw(self) -> TrackEnvelope {
+        self.raw
+    }
+
+    pub fn chunk(
+        self,
+        max_chunk_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> Result<Chunk, &'static str> {
+        let chunk_content = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_envelope_state_chunk(self.raw, max_chunk_size, cache_hint)
+                .map_err(|_| "Couldn't load envelope chunk")?
+        };
+        Ok(chunk_content.into())
+    }
+
+    pub fn set_chunk(self, chunk: Chunk, cache_hint: ChunkCacheHint) -> Result<(), &'static str> {
+        let string: String = chunk.try_into()?;
+        unsafe {
+            Reaper::get()
+                .medium_reaper
+                .set_envelope_state_chunk(self.raw, string, cache_hint)
+                .map_err(|_| "Couldn't set envelope chunk")
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! Stream-based event API which works on stable Rust.
+//!
+//! This is an alternative to the reactive API of `reaper-rx`. The streams are meant to be consumed
+//! by futures spawned via [`FutureSupport`](struct.FutureSupport.html), which are driven by the
+//! [`FutureMiddleware`](struct.FutureMiddleware.html).
+use crate::change_detection_middleware::*;
+use crate::{Action, Reaper};
+use crossbeam_channel::{Receiver, Sender};
+use futures::channel::mpsc;
+use futures::{future, Stream, StreamExt};
+use helgoboss_midi::{RawShortMessage, ShortMessage, ShortMessageType};
+use reaper_medium::{
+    ActionValueChange, CommandId, HookPostCommand, HookPostCommand2, MidiFrameOffset,
+    MidiInputDeviceId, OnAudioBufferArgs, ReaProject, RealTimeAudioThreadScope, SectionContext,
+    WindowContext,
+};
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+// Streams beyond this number make the audio thread allocate when they are registered
+const INITIAL_MIDI_STREAM_CAPACITY: usize = 10;
+
+/// Distributes values to any number of streams.
+struct Broadcaster<T> {
+    senders: RefCell<Vec<mpsc::UnboundedSender<T>>>,
+}
+
+impl<T: Clone> Broadcaster<T> {
+    fn subscribe(&self) -> mpsc::UnboundedReceiver<T> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.senders.borrow_mut().push(sender);
+        receiver
+    }
+
+    fn send(&self, value: T) {
+        // Senders of dropped streams are removed
+        self.senders
+            .borrow_mut()
+            .retain(|s| s.unbounded_send(value.clone()).is_ok());
+    }
+}
+
+impl<T> Default for Broadcaster<T> {
+    fn default() -> Self {
+        Broadcaster {
+            senders: Default::default(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Broadcaster<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Broadcaster")
+            .field("stream_count", &self.senders.borrow().len())
+            .finish()
+    }
+}
+
+/// Provides streams of change events.
+///
+/// Doesn't detect changes itself. Pass each event emitted by the
+/// [`ChangeDetectionMiddleware`](struct.ChangeDetectionMiddleware.html) to
+/// [`handle_change()`](#method.handle_change).
+#[derive(Clone, Debug, Default)]
+pub struct ChangeEventStreams {
+    change_events: Rc<Broadcaster<ChangeEvent>>,
+}
+
+impl ChangeEventStreams {
+    pub fn handle_change(&self, event: ChangeEvent) {
+        self.change_events.send(event);
+    }
+
+    /// Emits all change events.
+    pub fn change_events(&self) -> impl Stream<Item = ChangeEvent> {
+        self.change_events.subscribe()
+    }
+}
+
+/// Generates one stream method per change event category.
+macro_rules! change_event_streams {
+    ($($method:ident => $variant:ident($event:ident)),* $(,)?) => {
+        impl ChangeEventStreams {
+            $(
+                pub fn $method(&self) -> impl Stream<Item = $event> {
+                    self.change_events().filter_map(|e| {
+                        future::ready(match e {
+                            ChangeEvent::$variant(e) => Some(e),
+                            _ => None,
+                        })
+                    })
+                }
+            )*
+        }
+    };
+}
+
+change_event_streams! {
+    project_switched => ProjectSwitched(ProjectSwitchedEvent),
+    track_volume_changed => TrackVolumeChanged(TrackVolumeChangedEvent),
+    track_pan_changed => TrackPanChanged(TrackPanChangedEvent),
+    track_route_volume_changed => TrackRouteVolumeChanged(TrackRouteVolumeChangedEvent),
+    track_route_pan_changed => TrackRoutePanChanged(TrackRoutePanChangedEvent),
+    track_added => TrackAdded(TrackAddedEvent),
+    track_removed => TrackRemoved(TrackRemovedEvent),
+    tracks_reordered => TracksReordered(TracksReorderedEvent),
+    receive_count_changed => ReceiveCountChanged(ReceiveCountChangedEvent),
+    hardware_output_send_count_changed => HardwareOutputSendCountChanged(HardwareOutputSendCountChangedEvent),
+    track_send_count_changed => TrackSendCountChanged(TrackSendCountChangedEvent),
+    track_name_changed => TrackNameChanged(TrackNameChangedEvent),
+    track_input_changed => TrackInputChanged(TrackInputChangedEvent),
+    track_input_monitoring_changed => TrackInputMonitoringChanged(TrackInputMonitoringChangedEvent),
+    track_arm_changed => TrackArmChanged(TrackArmChangedEvent),
+    track_mute_changed => TrackMuteChanged(TrackMuteChangedEvent),
+    track_solo_changed => TrackSoloChanged(TrackSoloChangedEvent),
+    track_selected_changed => TrackSelectedChanged(TrackSelectedChangedEvent),
+    track_automation_mode_changed => TrackAutomationModeChanged(TrackAutomationModeChangedEvent),
+    fx_added => FxAdded(FxAddedEvent),
+    fx_removed => FxRemoved(FxRemovedEvent),
+    fx_enabled_changed => FxEnabledChanged(FxEnabledChangedEvent),
+    fx_opened => FxOpened(FxOpenedEvent),
+    fx_closed => FxClosed(FxClosedEvent),
+    fx_focused => FxFocused(FxFocusedEvent),
+    fx_reordered => FxReordered(FxReorderedEvent),
+    fx_parameter_value_changed => FxParameterValueChanged(FxParameterValueChangedEvent),
+    fx_preset_changed => FxPresetChanged(FxPresetChangedEvent),
+    master_tempo_changed => MasterTempoChanged(MasterTempoChangedEvent),
+    master_playrate_changed => MasterPlayrateChanged(MasterPlayrateChangedEvent),
+    global_automation_override_changed => GlobalAutomationOverrideChanged(GlobalAutomationOverrideChangedEvent),
+    play_state_changed => PlayStateChanged(PlayStateChangedEvent),
+    repeat_state_changed => RepeatStateChanged(RepeatStateChangedEvent),
+    project_closed => ProjectClosed(ProjectClosedEvent),
+    project_opened => ProjectOpened(ProjectOpenedEvent),
+    project_tab_reordered => ProjectTabReordered(ProjectTabReorderedEvent),
+    bookmarks_changed => BookmarksChanged(BookmarksChangedEvent),
+    item_added => ItemAdded(ItemAddedEvent),
+    item_removed => ItemRemoved(ItemRemovedEvent),
+    item_moved => ItemMoved(ItemMovedEvent),
+    item_length_changed => ItemLengthChanged(ItemLengthChangedEvent),
+    item_selected_changed => ItemSelectedChanged(ItemSelectedChangedEvent),
+    active_take_changed => ActiveTakeChanged(ActiveTakeChangedEvent),
+    take_name_changed => TakeNameChanged(TakeNameChangedEvent),
+    marker_added => MarkerAdded(MarkerAddedEvent),
+    marker_removed => MarkerRemoved(MarkerRemovedEvent),
+    marker_renamed => MarkerRenamed(MarkerRenamedEvent),
+    marker_moved => MarkerMoved(MarkerMovedEvent),
+    region_bounds_changed => RegionBoundsChanged(RegionBoundsChangedEvent),
+    bookmark_color_changed => BookmarkColorChanged(BookmarkColorChangedEvent),
+    edit_cursor_moved => EditCursorMoved(EditCursorMovedEvent),
+    time_selection_changed => TimeSelectionChanged(TimeSelectionChangedEvent),
+    loop_points_changed => LoopPointsChanged(LoopPointsChangedEvent),
+    current_region_changed => CurrentRegionChanged(CurrentRegionChangedEvent),
+    track_color_changed => TrackColorChanged(TrackColorChangedEvent),
+    track_visibility_changed => TrackVisibilityChanged(TrackVisibilityChangedEvent),
+    track_folder_state_changed => TrackFolderStateChanged(TrackFolderStateChangedEvent),
+    track_phase_changed => TrackPhaseChanged(TrackPhaseChangedEvent),
+    track_width_changed => TrackWidthChanged(TrackWidthChangedEvent),
+    track_attribute_changed => TrackAttributeChanged(TrackAttributeChangedEvent),
+    tempo_map_changed => TempoMapChanged(TempoMapChangedEvent),
+}
+
+/// Provides a stream of invoked actions.
+#[derive(Clone, Debug, Default)]
+pub struct ActionStreams {
+    action_invoked: Rc<Broadcaster<Rc<Action>>>,
+}
+
+impl ActionStreams {
+    pub fn action_invoked(&self) -> impl Stream<Item = Rc<Action>> {
+        self.action_invoked.subscribe()
+    }
+}
+
+pub trait ActionStreamsProvider {
+    fn action_streams() -> &'static ActionStreams;
+}
+
+// Called by REAPER directly (using a delegate function)!
+// Only for main section
+pub struct ActionStreamsHookPostCommand<P: ActionStreamsProvider> {
+    p: PhantomData<P>,
+}
+
+impl<P: ActionStreamsProvider> HookPostCommand for ActionStreamsHookPostCommand<P> {
+    fn call(command_id: CommandId, _flag: i32) {
+        let action = Reaper::get()
+            .main_section()
+            .action_by_command_id(command_id);
+        P::action_streams().action_invoked.send(Rc::new(action));
+    }
+}
+
+// Called by REAPER directly (using a delegate function)!
+// Processes main section only.
+pub struct ActionStreamsHookPostCommand2<P: ActionStreamsProvider> {
+    p: PhantomData<P>,
+}
+
+impl<P: ActionStreamsProvider> HookPostCommand2 for ActionStreamsHookPostCommand2<P> {
+    fn call(
+        section: SectionContext,
+        command_id: CommandId,
+        _: ActionValueChange,
+        _: WindowContext,
+        _: ReaProject,
+    ) {
+        if section != SectionContext::MainSection {
+            return;
+        }
+        let action = Reaper::get()
+            .main_section()
+            .action_by_command_id(command_id);
+        P::action_streams().action_invoked.send(Rc::new(action));
+    }
+}
+
+/// A short MIDI message received from a MIDI input device.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct IncomingMidiEvent {
+    pub device_id: MidiInputDeviceId,
+    pub frame_offset: MidiFrameOffset,
+    pub msg: RawShortMessage,
+}
+
+/// Provides a stream of incoming MIDI messages.
+///
+/// The messages are sent from the real-time audio thread by the
+/// [`MidiStreamMiddleware`](struct.MidiStreamMiddleware.html), so they can be consumed in any
+/// thread.
+#[derive(Clone, Debug)]
+pub struct MidiStreams {
+    // New subscriptions are passed to the audio thread via channel so it never needs to lock
+    subscription_sender: Sender<mpsc::UnboundedSender<IncomingMidiEvent>>,
+    subscription_receiver: Receiver<mpsc::UnboundedSender<IncomingMidiEvent>>,
+}
+
+impl Default for MidiStreams {
+    fn default() -> Self {
+        let (subscription_sender, subscription_receiver) = crossbeam_channel::unbounded();
+        MidiStreams {
+            subscription_sender,
+            subscription_receiver,
+        }
+    }
+}
+
+impl MidiStreams {
+    pub fn midi_message_received(&self) -> impl Stream<Item = IncomingMidiEvent> {
+        let (sender, receiver) = mpsc::unbounded();
+        // Can't fail because we hold a receiver ourselves
+        let _ = self.subscription_sender.send(sender);
+        receiver
+    }
+}
+
+pub struct MidiStreamMiddleware {
+    medium_reaper: reaper_medium::Reaper<RealTimeAudioThreadScope>,
+    streams: MidiStreams,
+    senders: Vec<mpsc::UnboundedSender<IncomingMidiEvent>>,
+}
+
+impl MidiStreamMiddleware {
+    pub fn new(
+        medium_reaper: reaper_medium::Reaper<RealTimeAudioThreadScope>,
+        streams: MidiStreams,
+    ) -> MidiStreamMiddleware {
+        MidiStreamMiddleware {
+            medium_reaper,
+            streams,
+            senders: Vec::with_capacity(INITIAL_MIDI_STREAM_CAPACITY),
+        }
+    }
+
+    /// To be called from the audio hook.
+    ///
+    /// Sending allocates, so it's best to keep the number of streams low.
+    pub fn on_audio_buffer(&mut self, args: OnAudioBufferArgs) {
+        if args.is_post {
+            return;
+        }
+        self.senders
+            .extend(self.streams.subscription_receiver.try_iter());
+        if self.senders.is_empty() {
+            return;
+        }
+        let senders = &mut self.senders;
+        for i in 0..self.medium_reaper.get_max_midi_inputs() {
+            let device_id = MidiInputDeviceId::new(i as u8);
+            self.medium_reaper.get_midi_input(device_id, |input| {
+                let input = match input {
+                    None => return,
+                    Some(i) => i,
+                };
+                for evt in input.get_read_buf().enum_items(0) {
+                    if evt.is_long() {
+                        continue;
+                    }
+                    let msg = evt.message();
+                    if msg.r#type() == ShortMessageType::ActiveSensing {
+                        continue;
+                    }
+                    let incoming_evt = IncomingMidiEvent {
+                        device_id,
+                        frame_offset: evt.frame_offset(),
+                        msg: msg.to_other(),
+                    };
+                    // Senders of dropped streams are removed
+                    senders.retain(|s| s.unbounded_send(incoming_evt).is_ok());
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[test]
+    fn broadcast_to_all_streams() {
+        // Given
+        let broadcaster = Broadcaster::default();
+        let mut first = broadcaster.subscribe();
+        let mut second = broadcaster.subscribe();
+        // When
+        broadcaster.send(5);
+        // Then
+        assert_eq!(first.next().now_or_never(), Some(Some(5)));
+        assert_eq!(second.next().now_or_never(), Some(Some(5)));
+        // Nothing more has been sent
+        assert_eq!(first.next().now_or_never(), None);
+    }
+
+    #[test]
+    fn remove_dropped_streams() {
+        // Given
+        let broadcaster = Broadcaster::default();
+        let mut first = broadcaster.subscribe();
+        let second = broadcaster.subscribe();
+        // When
+        drop(second);
+        broadcaster.send(5);
+        // Then
+        assert_eq!(broadcaster.senders.borrow().len(), 1);
+        assert_eq!(first.next().now_or_never(), Some(Some(5)));
+    }
+}
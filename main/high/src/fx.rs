@@ -7,10 +7,10 @@ use crate::fx_chain::FxChain;
 use crate::fx_parameter::FxParameter;
 use crate::guid::Guid;
 use crate::option_util::OptionExt;
-use crate::{ChunkRegion, FxChainContext, Project, Reaper, Track};
+use crate::{ChunkRegion, FxChainContext, FxStateChunk, Project, Reaper, Track};
 use reaper_medium::{
     FxPresetRef, FxShowInstruction, Hwnd, ReaperFunctionError, ReaperString, ReaperStringArg,
-    TrackFxLocation,
+    TakeFxShowInstruction, TrackFxLocation,
 };
 use std::hash::{Hash, Hasher};
 
@@ -85,7 +85,12 @@ impl Fx {
         self.load_if_necessary_or_complain();
         let buffer_size = 256;
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_fx_name(take.raw(), self.index(), buffer_size)
+                    .expect("Couldn't get take FX name")
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -146,7 +151,11 @@ impl Fx {
     pub fn parameter_count(&self) -> u32 {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_num_params(take.raw(), self.index())
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -160,7 +169,11 @@ impl Fx {
 
     pub fn is_enabled(&self) -> bool {
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_enabled(take.raw(), self.index())
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -178,7 +191,14 @@ impl Fx {
         buffer_size: u32,
     ) -> Result<Vec<u8>, ReaperFunctionError> {
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_get_named_config_parm(
+                    take.raw(),
+                    self.index(),
+                    name,
+                    buffer_size,
+                )
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -196,7 +216,14 @@ impl Fx {
         buffer: &[u8],
     ) -> Result<(), ReaperFunctionError> {
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_set_named_config_parm(
+                    take.raw(),
+                    self.index(),
+                    name,
+                    buffer,
+                )
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -221,7 +248,7 @@ impl Fx {
         FxParameter::new(self.clone(), index)
     }
 
-    /// Will return None if monitoring FX.
+    /// Will return None if monitoring FX or take FX.
     ///
     /// In some scenarios it makes sense to fall back to the master track of the current project.
     pub fn track(&self) -> Option<&Track> {
@@ -324,10 +351,25 @@ impl Fx {
         self.replace_track_chunk_region(self.state_chunk()?, chunk)
     }
 
+    /// Returns the decoded tag chunk, giving access to header fields and the plug-in state.
+    pub fn typed_tag_chunk(&self) -> Result<FxStateChunk, &'static str> {
+        FxStateChunk::parse(&self.tag_chunk()?.content())
+    }
+
+    /// Writes back a (modified) decoded tag chunk.
+    // TODO-low Supports track FX only
+    pub fn set_typed_tag_chunk(&self, chunk: &FxStateChunk) -> Result<(), &'static str> {
+        self.set_tag_chunk(&chunk.to_string())
+    }
+
     pub fn floating_window(&self) -> Option<Hwnd> {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_floating_window(take.raw(), self.index())
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -341,7 +383,11 @@ impl Fx {
 
     pub fn window_is_open(&self) -> bool {
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_open(take.raw(), self.index())
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -360,7 +406,12 @@ impl Fx {
     pub fn show_in_floating_window(&self) {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_show(
+                    take.raw(),
+                    TakeFxShowInstruction::ShowFloatingWindow(self.index()),
+                );
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -376,7 +427,12 @@ impl Fx {
     pub fn hide_floating_window(&self) {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_show(
+                    take.raw(),
+                    TakeFxShowInstruction::HideFloatingWindow(self.index()),
+                );
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -392,7 +448,11 @@ impl Fx {
     pub fn show_in_chain(&self) {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_show(take.raw(), TakeFxShowInstruction::ShowChain(self.index()));
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -433,7 +493,13 @@ impl Fx {
 
     fn set_enabled(&self, enabled: bool) {
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_set_enabled(
+                    take.raw(),
+                    self.index(),
+                    enabled,
+                );
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -473,7 +539,14 @@ impl Fx {
     pub fn preset_count(&self) -> Result<u32, ReaperFunctionError> {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => {
+                let res = unsafe {
+                    Reaper::get()
+                        .medium_reaper()
+                        .take_fx_get_preset_index(take.raw(), self.index())?
+                };
+                Ok(res.count)
+            }
             _ => {
                 let (track, location) = self.track_and_location();
                 let res = unsafe {
@@ -489,7 +562,14 @@ impl Fx {
     pub fn preset_index(&self) -> Result<Option<u32>, ReaperFunctionError> {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => {
+                let res = unsafe {
+                    Reaper::get()
+                        .medium_reaper()
+                        .take_fx_get_preset_index(take.raw(), self.index())?
+                };
+                Ok(res.index)
+            }
             _ => {
                 let (track, location) = self.track_and_location();
                 let res = unsafe {
@@ -505,7 +585,13 @@ impl Fx {
     pub fn activate_preset(&self, preset: FxPresetRef) {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                let _ = Reaper::get().medium_reaper().take_fx_set_preset_by_index(
+                    take.raw(),
+                    self.index(),
+                    preset,
+                );
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -522,7 +608,14 @@ impl Fx {
     pub fn preset_is_dirty(&self) -> bool {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => {
+                let result = unsafe {
+                    Reaper::get()
+                        .medium_reaper()
+                        .take_fx_get_preset(take.raw(), self.index(), 0)
+                };
+                !result.state_matches_preset
+            }
             _ => {
                 let (track, location) = self.track_and_location();
                 let result = unsafe {
@@ -538,7 +631,12 @@ impl Fx {
     pub fn preset_name(&self) -> Option<ReaperString> {
         self.load_if_necessary_or_complain();
         match self.chain.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_preset(take.raw(), self.index(), 2000)
+                    .name
+            },
             _ => {
                 let (track, location) = self.track_and_location();
                 unsafe {
@@ -570,7 +668,12 @@ fn get_track_and_location(chain: &FxChain, index: u32) -> (Track, TrackFxLocatio
 
 pub fn get_fx_guid(chain: &FxChain, index: u32) -> Option<Guid> {
     let raw_guid = match chain.context() {
-        FxChainContext::Take(_) => todo!(),
+        FxChainContext::Take(take) => unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .take_fx_get_fx_guid(take.raw(), index)
+                .ok()
+        },
         _ => {
             let (track, location) = get_track_and_location(chain, index);
             unsafe {
@@ -1,13 +1,16 @@
 use crate::fx::{get_fx_guid, Fx};
 use crate::guid::Guid;
 use crate::{
-    get_track_fx_location, Chunk, ChunkRegion, Project, Reaper, Take, Track, MAX_TRACK_CHUNK_SIZE,
+    get_track_fx_location, parse_rpp_items, write_rpp_items, Chunk, ChunkRegion, Project, Reaper,
+    RppItem, RppLine, RppNode, Take, Track, MAX_TRACK_CHUNK_SIZE,
 };
 
 use reaper_medium::{
     AddFxBehavior, ChunkCacheHint, FxChainVisibility, FxShowInstruction, ReaperStringArg,
-    TrackFxChainType, TransferBehavior,
+    TakeFxShowInstruction, TrackFxChainType, TransferBehavior,
 };
+use std::fs;
+use std::path::Path;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum FxChainContext {
@@ -25,6 +28,15 @@ pub enum FxChainContext {
     Take(Take),
 }
 
+/// Determines where new FX are inserted into an FX chain.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InsertPosition {
+    Start,
+    End,
+    /// Before the FX at the given index. Same as `End` if the index is out of range.
+    Before(u32),
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FxChain {
     context: FxChainContext,
@@ -54,7 +66,10 @@ impl FxChain {
     }
 
     pub fn project(&self) -> Option<Project> {
-        self.track().map(|t| t.project())
+        match &self.context {
+            FxChainContext::Take(take) => take.project(),
+            _ => self.track().map(|t| t.project()),
+        }
     }
 
     pub fn fx_count(&self) -> u32 {
@@ -71,7 +86,7 @@ impl FxChain {
                 let track = Reaper::get().current_project().master_track();
                 unsafe { reaper.track_fx_get_rec_count(track.raw()) }
             }
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe { reaper.take_fx_get_count(take.raw()) },
         }
     }
 
@@ -89,13 +104,17 @@ impl FxChain {
                 let track = Reaper::get().current_project().master_track();
                 unsafe { reaper.track_fx_get_rec_chain_visible(track.raw()) }
             }
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe { reaper.take_fx_get_chain_visible(take.raw()) },
         }
     }
 
     pub fn hide(&self) {
         match self.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_show(take.raw(), TakeFxShowInstruction::HideChain);
+            },
             _ => {
                 let track = self.track_or_master_track();
                 let instruction = FxShowInstruction::HideChain(if self.is_input_fx() {
@@ -118,7 +137,13 @@ impl FxChain {
         let reaper = Reaper::get().medium_reaper();
         if reaper.low().pointers().TrackFX_CopyToTrack.is_some() {
             match self.context() {
-                FxChainContext::Take(_) => todo!(),
+                FxChainContext::Take(take) => unsafe {
+                    reaper.take_fx_copy_to_take(
+                        (take.raw(), fx.index()),
+                        (take.raw(), new_index),
+                        TransferBehavior::Move,
+                    );
+                },
                 _ => {
                     let (track, location) = fx.track_and_location();
                     unsafe {
@@ -193,7 +218,11 @@ impl FxChain {
         let reaper = Reaper::get().medium_reaper();
         if reaper.low().pointers().TrackFX_Delete.is_some() {
             match self.context() {
-                FxChainContext::Take(_) => todo!(),
+                FxChainContext::Take(take) => unsafe {
+                    reaper
+                        .take_fx_delete(take.raw(), fx.index())
+                        .map_err(|_| "couldn't delete take FX")?
+                },
                 _ => {
                     let (track, location) = fx.track_and_location();
                     unsafe {
@@ -314,12 +343,107 @@ DOCKED 0
         Ok(())
     }
 
+    /// Writes all FX of this chain to the given file in REAPER's FX chain format (`.RfxChain`).
+    ///
+    /// Relative paths are resolved against REAPER's resource path.
+    ///
+    /// Only track FX chains (including input FX chains) are supported because the FX are taken
+    /// from the track chunk. Take FX chains and the monitoring FX chain result in an error.
+    pub fn export_to_file(&self, path: impl AsRef<Path>) -> Result<(), &'static str> {
+        self.require_track_fx_chain()?;
+        let items = match self.chunk()? {
+            None => vec![],
+            Some(region) => RppNode::parse(&region.content())?.items,
+        };
+        // Window state is not part of an FX chain preset
+        let fx_items: Vec<_> = items
+            .into_iter()
+            .filter(|item| match item {
+                RppItem::Line(l) => !FX_CHAIN_WINDOW_KEYS.contains(&l.key().unwrap_or_default()),
+                RppItem::Node(_) => true,
+            })
+            .collect();
+        let mut content = String::new();
+        write_rpp_items(&mut content, &fx_items).map_err(|_| "couldn't format FX chain")?;
+        fs::write(Reaper::get().resolve_resource_path(path), content)
+            .map_err(|_| "couldn't write FX chain file")
+    }
+
+    /// Inserts all FX contained in the given FX chain file (`.RfxChain`) and returns them.
+    ///
+    /// Relative paths are resolved against REAPER's resource path. Take FX chains and the
+    /// monitoring FX chain are not supported, see [`export_to_file()`].
+    ///
+    /// [`export_to_file()`]: #method.export_to_file
+    pub fn import_from_file(
+        &self,
+        path: impl AsRef<Path>,
+        position: InsertPosition,
+    ) -> Result<Vec<Fx>, &'static str> {
+        self.require_track_fx_chain()?;
+        let content = fs::read_to_string(Reaper::get().resolve_resource_path(path))
+            .map_err(|_| "couldn't read FX chain file")?;
+        let mut fx_items = parse_rpp_items(&content)?;
+        // REAPER assigns new FX GUIDs. Otherwise importing the same file twice would result in
+        // duplicate GUIDs.
+        fx_items.retain(|item| match item {
+            RppItem::Line(l) => l.key().is_some() && l.key() != Some("FXID"),
+            RppItem::Node(_) => true,
+        });
+        let imported_count = count_fx_entries(&fx_items);
+        if imported_count == 0 {
+            return Ok(vec![]);
+        }
+        let mut chain_node = match self.chunk()? {
+            Some(region) => RppNode::parse(&region.content())?,
+            None => {
+                let mut node = RppNode::new(self.chunk_tag_name(), vec![]);
+                for line in &["WNDRECT 0 144 1082 736", "SHOW 0", "LASTSEL 1", "DOCKED 0"] {
+                    node.push_line(RppLine::parse(line));
+                }
+                node
+            }
+        };
+        let fx_count = count_fx_entries(&chain_node.items);
+        let start_index = match position {
+            InsertPosition::Start => 0,
+            InsertPosition::End => fx_count,
+            InsertPosition::Before(i) => i.min(fx_count),
+        };
+        let item_index = chain_node
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| is_fx_entry_start(item))
+            .nth(start_index as usize)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| chain_node.items.len());
+        chain_node.items.splice(item_index..item_index, fx_items);
+        self.set_chunk(chain_node.to_string().trim_end())?;
+        let fxs = (start_index..start_index + imported_count)
+            .filter_map(|i| self.fx_by_index(i))
+            .collect();
+        Ok(fxs)
+    }
+
     fn find_chunk_region(&self, track_chunk: Chunk) -> Option<ChunkRegion> {
         track_chunk
             .region()
             .find_first_tag_named(0, self.chunk_tag_name())
     }
 
+    // The master track chunk doesn't contain the monitoring FX chain
+    fn require_track_fx_chain(&self) -> Result<(), &'static str> {
+        match &self.context {
+            FxChainContext::Track { track, is_input_fx }
+                if !(*is_input_fx && track.is_master_track()) =>
+            {
+                Ok(())
+            }
+            _ => Err("only track FX chains are supported"),
+        }
+    }
+
     fn chunk_tag_name(&self) -> &'static str {
         if self.is_input_fx() {
             "FXCHAIN_REC"
@@ -330,7 +454,8 @@ DOCKED 0
 
     pub fn first_instrument_fx(&self) -> Option<Fx> {
         match self.context() {
-            FxChainContext::Take(_) => todo!(),
+            // REAPER doesn't offer a way to query instruments in take FX chains
+            FxChainContext::Take(_) => None,
             FxChainContext::Monitoring => None,
             FxChainContext::Track { track, is_input_fx } => {
                 if *is_input_fx {
@@ -357,7 +482,12 @@ DOCKED 0
         original_fx_name: impl Into<ReaperStringArg<'a>>,
     ) -> Option<Fx> {
         let fx_index = match self.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_add_by_name_add(take.raw(), original_fx_name, AddFxBehavior::AlwaysAdd)
+                    .ok()?
+            },
             _ => unsafe {
                 Reaper::get()
                     .medium_reaper()
@@ -389,16 +519,16 @@ DOCKED 0
         match self.context() {
             FxChainContext::Monitoring => Reaper::get().current_project().master_track(),
             FxChainContext::Track { track, .. } => track.clone(),
-            FxChainContext::Take(take) => take.track().clone(),
+            FxChainContext::Take(_) => panic!("not possible for take FX"),
         }
     }
 
     pub fn track(&self) -> Option<&Track> {
         match &self.context {
             FxChainContext::Track { track, .. } => Some(track),
-            // TODO-low This is dangerous. Some chunk functions which call track assume this is
-            //  a track FX when this returns a track. Clean them up!
-            FxChainContext::Take(take) => Some(take.track()),
+            // Chunk functions rely on this returning a track for track FX chains only. Use
+            // `Take::track()` to get the track of a take FX chain.
+            FxChainContext::Take(_) => None,
             FxChainContext::Monitoring => None,
         }
     }
@@ -415,7 +545,11 @@ DOCKED 0
 
     pub fn first_fx_by_name<'a>(&self, name: impl Into<ReaperStringArg<'a>>) -> Option<Fx> {
         let fx_index = match self.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_add_by_name_query(take.raw(), name)?
+            },
             FxChainContext::Track { track, .. } => unsafe {
                 Reaper::get().medium_reaper().track_fx_add_by_name_query(
                     track.raw(),
@@ -484,9 +618,23 @@ DOCKED 0
 
     pub fn is_available(&self) -> bool {
         match self.context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => take.is_available(),
             FxChainContext::Monitoring => true,
             FxChainContext::Track { track, .. } => track.is_available(),
         }
     }
 }
+
+const FX_CHAIN_WINDOW_KEYS: [&str; 4] = ["WNDRECT", "SHOW", "LASTSEL", "DOCKED"];
+
+// Each FX entry in an FX chain chunk starts with a BYPASS line
+fn is_fx_entry_start(item: &RppItem) -> bool {
+    match item {
+        RppItem::Line(l) => l.key() == Some("BYPASS"),
+        RppItem::Node(_) => false,
+    }
+}
+
+fn count_fx_entries(items: &[RppItem]) -> u32 {
+    items.iter().filter(|item| is_fx_entry_start(item)).count() as u32
+}
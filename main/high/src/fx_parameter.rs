@@ -5,7 +5,7 @@ use reaper_medium::{
     GetParameterStepSizesResult, ReaperFunctionError, ReaperNormalizedFxParamValue, ReaperString,
 };
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FxParameter {
     fx: Fx,
     index: u32,
@@ -21,7 +21,14 @@ impl FxParameter {
         reaper_value: impl Into<ReaperNormalizedFxParamValue>,
     ) -> Result<(), ReaperFunctionError> {
         match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_set_param_normalized(
+                    take.raw(),
+                    self.fx().index(),
+                    self.index,
+                    reaper_value.into(),
+                )
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
@@ -38,7 +45,13 @@ impl FxParameter {
 
     pub fn reaper_normalized_value(&self) -> ReaperNormalizedFxParamValue {
         match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_get_param_normalized(
+                    take.raw(),
+                    self.fx().index(),
+                    self.index,
+                )
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
@@ -62,7 +75,12 @@ impl FxParameter {
 
     pub fn name(&self) -> ReaperString {
         match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_param_name(take.raw(), self.fx().index(), self.index, 256)
+                    .expect("Couldn't get FX parameter name")
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
@@ -86,38 +104,52 @@ impl FxParameter {
     }
 
     pub fn step_sizes(&self) -> Option<GetParameterStepSizesResult> {
-        match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+        let result = match self.chain().context() {
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_parameter_step_sizes(take.raw(), self.fx().index(), self.index)?
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
-                let result = unsafe {
+                unsafe {
                     Reaper::get()
                         .medium_reaper()
                         .track_fx_get_parameter_step_sizes(track.raw(), location, self.index)?
-                };
-                // Try to fix some invalid results (which are most likely invalid because of messy
-                // plug-ins, not because of REAPER itself)
-                if let GetParameterStepSizesResult::Normal { normal_step, .. } = result {
-                    if normal_step.is_infinite() {
-                        // There was a bug (REAPER <= 6.12) which makes JS FX "Bypass" and "Wet"
-                        // parameters return an infinite step size. This
-                        // isn't correct, therefore we fix it here.
-                        return None;
-                    }
-                    if normal_step == 0.0 {
-                        // Some plug-ins report a parameter as discrete but then report a step size
-                        // of zero, which is of course pointless.
-                        return None;
-                    }
                 }
-                Some(result)
+            }
+        };
+        // Try to fix some invalid results (which are most likely invalid because of messy
+        // plug-ins, not because of REAPER itself)
+        if let GetParameterStepSizesResult::Normal { normal_step, .. } = result {
+            if normal_step.is_infinite() {
+                // There was a bug (REAPER <= 6.12) which makes JS FX "Bypass" and "Wet"
+                // parameters return an infinite step size. This
+                // isn't correct, therefore we fix it here.
+                return None;
+            }
+            if normal_step == 0.0 {
+                // Some plug-ins report a parameter as discrete but then report a step size
+                // of zero, which is of course pointless.
+                return None;
             }
         }
+        Some(result)
     }
 
     pub fn formatted_value(&self) -> ReaperString {
         match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_get_formatted_param_value(
+                        take.raw(),
+                        self.fx().index(),
+                        self.index,
+                        256,
+                    )
+                    .expect("Couldn't format FX param value")
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
@@ -143,7 +175,17 @@ impl FxParameter {
         reaper_value: ReaperNormalizedFxParamValue,
     ) -> Result<ReaperString, ReaperFunctionError> {
         match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .take_fx_format_param_value_normalized(
+                        take.raw(),
+                        self.fx().index(),
+                        self.index,
+                        reaper_value,
+                        256,
+                    )
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
@@ -193,7 +235,13 @@ impl FxParameter {
     // Doesn't necessarily return normalized values
     pub fn value_range(&self) -> FxParameterValueRange {
         let result = match self.chain().context() {
-            FxChainContext::Take(_) => todo!(),
+            FxChainContext::Take(take) => unsafe {
+                Reaper::get().medium_reaper().take_fx_get_param_ex(
+                    take.raw(),
+                    self.fx().index(),
+                    self.index,
+                )
+            },
             _ => {
                 let (track, location) = self.fx().track_and_location();
                 unsafe {
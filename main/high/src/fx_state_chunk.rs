@@ -0,0 +1,487 @@
+use crate::{encode_base64_lines, RppItem, RppLine, RppNode, RPP_BASE64_BYTES_PER_LINE};
+use std::convert::TryInto;
+
+const VST_MAGIC: u32 = 0xfeed_5eee;
+const VST_MAGIC_WITH_PROGRAM_CHUNK: u32 = 0xfeed_5eef;
+const VST_HEADER_TAIL_LENGTH: usize = 8;
+
+/// A decoded FX tag chunk, e.g. `<VST "VST: ReaEQ (Cockos)" reaeq.dll ...>`.
+///
+/// Can be obtained via [`Fx::typed_tag_chunk()`] or parsed offline (e.g. from an [`RppFx`]). Lines
+/// and values which are not modified are written back exactly as they were.
+///
+/// [`Fx::typed_tag_chunk()`]: struct.Fx.html#method.typed_tag_chunk
+/// [`RppFx`]: struct.RppFx.html
+#[derive(Clone, PartialEq, Debug)]
+pub enum FxStateChunk {
+    /// VST2 or VST3 plug-in.
+    Vst(VstStateChunk),
+    /// JSFX.
+    Js(JsStateChunk),
+    /// Audio Unit.
+    Au(AuStateChunk),
+}
+
+/// Decoded state of a VST2 or VST3 plug-in.
+#[derive(Clone, PartialEq, Debug)]
+pub struct VstStateChunk {
+    /// Display name, e.g. `VST: ReaEQ (Cockos)` or `VST3i: Surge (Surge Synth Team)`.
+    pub display_name: String,
+    /// Plug-in file, e.g. `reaeq.dll` or `Surge.vst3`.
+    pub file: String,
+    /// Name given by the user, empty if the FX has not been renamed.
+    pub custom_name: String,
+    /// Plug-in identifier as written by REAPER, e.g. `1919247729<56535472656571726561657100000000>`
+    /// (VST2) or `1397572658{ABCDEF019182FAEB...}` (VST3).
+    pub unique_id: String,
+    /// Input pin mappings, one bit mask (of REAPER track channels) per plug-in input.
+    pub input_pins: Vec<u64>,
+    /// Output pin mappings, one bit mask (of REAPER track channels) per plug-in output.
+    pub output_pins: Vec<u64>,
+    /// The plug-in's own state data (as returned by the plug-in).
+    pub state: Vec<u8>,
+    /// Name of the current program/preset, if available.
+    pub preset_name: Option<String>,
+    vst_id: i32,
+    magic: u32,
+    header_tail: Vec<u8>,
+    // Everything after the preset name.
+    trailer_tail: Vec<u8>,
+    node: RppNode,
+}
+
+/// Decoded state of a JSFX.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsStateChunk {
+    /// Path of the effect relative to the effects directory, e.g. `utility/volume`.
+    pub file: String,
+    /// Name given by the user, empty if the FX has not been renamed.
+    pub custom_name: String,
+    /// Slider values, `None` for unused sliders.
+    ///
+    /// Tokens after the slider values which are not understood (e.g. written by newer REAPER
+    /// versions) are ignored and written back as they were.
+    pub slider_values: Vec<Option<f64>>,
+    node: RppNode,
+}
+
+/// Decoded state of an Audio Unit.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuStateChunk {
+    /// Display name, e.g. `AU: AUDelay (Apple)`.
+    pub display_name: String,
+    /// Component identification as written by REAPER.
+    pub component: String,
+    /// Name given by the user, empty if the FX has not been renamed.
+    pub custom_name: String,
+    /// The complete decoded binary state.
+    pub state: Vec<u8>,
+    node: RppNode,
+}
+
+impl FxStateChunk {
+    /// Parses a complete FX tag chunk (as returned by [`Fx::tag_chunk()`]).
+    ///
+    /// [`Fx::tag_chunk()`]: struct.Fx.html#method.tag_chunk
+    pub fn parse(tag_chunk: &str) -> Result<FxStateChunk, &'static str> {
+        FxStateChunk::from_node(RppNode::parse(tag_chunk)?)
+    }
+
+    /// Interprets the given FX block.
+    pub fn from_node(node: RppNode) -> Result<FxStateChunk, &'static str> {
+        let chunk = match node.tag.as_str() {
+            "VST" => FxStateChunk::Vst(VstStateChunk::from_node(node)?),
+            "JS" => FxStateChunk::Js(JsStateChunk::from_node(node)?),
+            "AU" => FxStateChunk::Au(AuStateChunk::from_node(node)?),
+            _ => return Err("unsupported FX type"),
+        };
+        Ok(chunk)
+    }
+
+    /// Returns the FX block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        match self {
+            FxStateChunk::Vst(c) => c.to_node(),
+            FxStateChunk::Js(c) => c.to_node(),
+            FxStateChunk::Au(c) => c.to_node(),
+        }
+    }
+}
+
+impl std::fmt::Display for FxStateChunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_node().write_to(f)
+    }
+}
+
+impl VstStateChunk {
+    /// Interprets the given `VST` block.
+    pub fn from_node(node: RppNode) -> Result<VstStateChunk, &'static str> {
+        if node.tag != "VST" {
+            return Err("not a VST block");
+        }
+        let data = node.decode_base64_lines()?;
+        let mut reader = ByteReader::new(&data);
+        let vst_id = reader.read_i32()?;
+        let magic = reader.read_u32()?;
+        if magic != VST_MAGIC && magic != VST_MAGIC_WITH_PROGRAM_CHUNK {
+            return Err("unknown VST state format");
+        }
+        let input_pins = reader.read_pins()?;
+        let output_pins = reader.read_pins()?;
+        let state_size = reader.read_u32()? as usize;
+        let header_tail = reader.read_bytes(VST_HEADER_TAIL_LENGTH)?.to_vec();
+        let state = reader.read_bytes(state_size)?.to_vec();
+        let (preset_name, trailer_tail) = parse_vst_trailer(reader.remaining());
+        let param = |i: usize| node.params.get(i).cloned().unwrap_or_default();
+        let chunk = VstStateChunk {
+            display_name: param(0),
+            file: param(1),
+            custom_name: param(3),
+            unique_id: param(4),
+            input_pins,
+            output_pins,
+            state,
+            preset_name,
+            vst_id,
+            magic,
+            header_tail,
+            trailer_tail,
+            node,
+        };
+        Ok(chunk)
+    }
+
+    /// Returns whether this is a VST3 plug-in.
+    pub fn is_vst3(&self) -> bool {
+        self.unique_id.contains('{')
+    }
+
+    /// Returns the VST unique ID as contained in the binary header.
+    pub fn vst_id(&self) -> i32 {
+        self.vst_id
+    }
+
+    /// Returns the FX block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        set_param(&mut node.params, 0, &self.display_name);
+        set_param(&mut node.params, 1, &self.file);
+        set_param(&mut node.params, 3, &self.custom_name);
+        set_param(&mut node.params, 4, &self.unique_id);
+        let segments = self.segments();
+        let original = self.node.decode_base64_lines().unwrap_or_default();
+        if segments.concat() != original {
+            // Each segment is encoded separately, just like REAPER does it.
+            node.items.retain(|i| matches!(i, RppItem::Node(_)));
+            for segment in segments {
+                for line in encode_base64_lines(&segment, RPP_BASE64_BYTES_PER_LINE) {
+                    node.push_line(line);
+                }
+            }
+        }
+        node
+    }
+
+    fn segments(&self) -> Vec<Vec<u8>> {
+        let mut header = vec![];
+        header.extend(&self.vst_id.to_le_bytes());
+        header.extend(&self.magic.to_le_bytes());
+        for pins in &[&self.input_pins, &self.output_pins] {
+            header.extend(&(pins.len() as u32).to_le_bytes());
+            for pin in pins.iter() {
+                header.extend(&pin.to_le_bytes());
+            }
+        }
+        header.extend(&(self.state.len() as u32).to_le_bytes());
+        header.extend(&self.header_tail);
+        let mut trailer = vec![];
+        if let Some(name) = &self.preset_name {
+            trailer.push(0);
+            trailer.extend(name.as_bytes());
+            trailer.push(0);
+        }
+        trailer.extend(&self.trailer_tail);
+        vec![header, self.state.clone(), trailer]
+    }
+}
+
+// The trailer usually consists of a zero byte, the null-terminated program name and a few further
+// bytes.
+fn parse_vst_trailer(trailer: &[u8]) -> (Option<String>, Vec<u8>) {
+    if trailer.first() != Some(&0) {
+        return (None, trailer.to_vec());
+    }
+    let rest = &trailer[1..];
+    match rest.iter().position(|b| *b == 0) {
+        Some(end) => match std::str::from_utf8(&rest[..end]) {
+            Ok(name) => (Some(name.to_string()), rest[end + 1..].to_vec()),
+            Err(_) => (None, trailer.to_vec()),
+        },
+        None => (None, trailer.to_vec()),
+    }
+}
+
+impl JsStateChunk {
+    /// Interprets the given `JS` block.
+    pub fn from_node(node: RppNode) -> Result<JsStateChunk, &'static str> {
+        if node.tag != "JS" {
+            return Err("not a JS block");
+        }
+        let slider_values = parse_leading_slider_values(&node);
+        let chunk = JsStateChunk {
+            file: node.params.first().cloned().unwrap_or_default(),
+            custom_name: node.params.get(1).cloned().unwrap_or_default(),
+            slider_values,
+            node,
+        };
+        Ok(chunk)
+    }
+
+    /// Returns the FX block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        set_param(&mut node.params, 0, &self.file);
+        set_param(&mut node.params, 1, &self.custom_name);
+        let slider_token_count = parse_leading_slider_values(&node).len();
+        if slider_token_count == self.slider_values.len() {
+            // Keep the original line structure and formatting of unchanged values.
+            let mut values = self.slider_values.iter();
+            for token in node.lines_mut().flat_map(|l| l.tokens.iter_mut()) {
+                let value = match values.next() {
+                    None => break,
+                    Some(v) => v,
+                };
+                if parse_slider_value(token).as_ref() != Some(value) {
+                    *token = format_slider_value(*value);
+                }
+            }
+        } else {
+            let unknown_tokens: Vec<String> = node
+                .lines()
+                .flat_map(|l| l.tokens.iter())
+                .skip(slider_token_count)
+                .cloned()
+                .collect();
+            node.items.retain(|i| matches!(i, RppItem::Node(_)));
+            let tokens = self
+                .slider_values
+                .iter()
+                .map(|v| format_slider_value(*v))
+                .chain(unknown_tokens)
+                .collect();
+            node.items.insert(0, RppItem::Line(RppLine::new(tokens)));
+        }
+        node
+    }
+}
+
+// Returns `None` if the token is not a slider value.
+/// Parses slider values up to the first token which is not a slider value.
+fn parse_leading_slider_values(node: &RppNode) -> Vec<Option<f64>> {
+    let mut values = Vec::new();
+    for token in node.lines().flat_map(|l| l.tokens.iter()) {
+        match parse_slider_value(token) {
+            None => break,
+            Some(v) => values.push(v),
+        }
+    }
+    values
+}
+
+fn parse_slider_value(token: &str) -> Option<Option<f64>> {
+    if token == "-" {
+        return Some(None);
+    }
+    token.parse().ok().map(Some)
+}
+
+fn format_slider_value(value: Option<f64>) -> String {
+    match value {
+        None => "-".to_string(),
+        Some(v) => format!("{:.6}", v),
+    }
+}
+
+impl AuStateChunk {
+    /// Interprets the given `AU` block.
+    pub fn from_node(node: RppNode) -> Result<AuStateChunk, &'static str> {
+        if node.tag != "AU" {
+            return Err("not an AU block");
+        }
+        let param = |i: usize| node.params.get(i).cloned().unwrap_or_default();
+        let chunk = AuStateChunk {
+            display_name: param(0),
+            component: param(1),
+            custom_name: param(2),
+            state: node.decode_base64_lines()?,
+            node,
+        };
+        Ok(chunk)
+    }
+
+    /// Returns the FX block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        set_param(&mut node.params, 0, &self.display_name);
+        set_param(&mut node.params, 1, &self.component);
+        set_param(&mut node.params, 2, &self.custom_name);
+        if self.node.decode_base64_lines().ok().as_ref() != Some(&self.state) {
+            node.set_base64_lines(&self.state);
+        }
+        node
+    }
+}
+
+fn set_param(params: &mut Vec<String>, index: usize, value: &str) {
+    while params.len() <= index {
+        params.push(String::new());
+    }
+    if params[index] != value {
+        params[index] = value.to_string();
+    }
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { data, pos: 0 }
+    }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], &'static str> {
+        let end = self.pos + count;
+        if end > self.data.len() {
+            return Err("VST state too short");
+        }
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, &'static str> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, &'static str> {
+        let bytes = self.read_bytes(4)?;
+        Ok(i32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, &'static str> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_pins(&mut self) -> Result<Vec<u64>, &'static str> {
+        let count = self.read_u32()?;
+        (0..count).map(|_| self.read_u64()).collect()
+    }
+
+    fn remaining(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VST_CHUNK: &str = r#"<VST "VST: ReaEQ (Cockos)" reaeq.dll 0 "" 1919247729<56535472656571726561657100000000> ""
+  cWVlcu9e7f4CAAAAAQAAAAAAAAACAAAAAAAAAAIAAAABAAAAAAAAAAIAAAAAAAAACAAAAAEAAAAAABAA
+  AQIDBAUGBwg=
+  AFN0b2NrAAAAAAA=
+>"#;
+
+    const JS_CHUNK: &str = r#"<JS utility/volume ""
+  -6.000000 0.000000 - - -
+>"#;
+
+    #[test]
+    fn decode_vst() {
+        let chunk = match FxStateChunk::parse(VST_CHUNK).unwrap() {
+            FxStateChunk::Vst(c) => c,
+            _ => panic!("not VST"),
+        };
+        assert_eq!(chunk.display_name, "VST: ReaEQ (Cockos)");
+        assert_eq!(chunk.file, "reaeq.dll");
+        assert_eq!(chunk.vst_id(), 1919247729);
+        assert!(!chunk.is_vst3());
+        assert_eq!(chunk.input_pins, vec![1, 2]);
+        assert_eq!(chunk.output_pins, vec![1, 2]);
+        assert_eq!(chunk.state, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(chunk.preset_name.as_deref(), Some("Stock"));
+        assert_eq!(chunk.to_node().to_string(), VST_CHUNK);
+    }
+
+    #[test]
+    fn encode_vst() {
+        let mut chunk = match FxStateChunk::parse(VST_CHUNK).unwrap() {
+            FxStateChunk::Vst(c) => c,
+            _ => panic!("not VST"),
+        };
+        chunk.state = vec![9; 200];
+        chunk.output_pins = vec![4, 8];
+        chunk.preset_name = Some("Custom".to_string());
+        let encoded = chunk.to_node().to_string();
+        let decoded = match FxStateChunk::parse(&encoded).unwrap() {
+            FxStateChunk::Vst(c) => c,
+            _ => panic!("not VST"),
+        };
+        assert_eq!(decoded.state, vec![9; 200]);
+        assert_eq!(decoded.input_pins, vec![1, 2]);
+        assert_eq!(decoded.output_pins, vec![4, 8]);
+        assert_eq!(decoded.preset_name.as_deref(), Some("Custom"));
+        // Header, 3 state lines, trailer
+        assert_eq!(decoded.to_node().lines().count(), 5);
+    }
+
+    #[test]
+    fn js() {
+        let mut chunk = match FxStateChunk::parse(JS_CHUNK).unwrap() {
+            FxStateChunk::Js(c) => c,
+            _ => panic!("not JS"),
+        };
+        assert_eq!(chunk.file, "utility/volume");
+        assert_eq!(chunk.custom_name, "");
+        assert_eq!(
+            chunk.slider_values,
+            vec![Some(-6.0), Some(0.0), None, None, None]
+        );
+        assert_eq!(chunk.to_node().to_string(), JS_CHUNK);
+        chunk.slider_values[0] = Some(-3.0);
+        assert_eq!(
+            chunk.to_node().to_string(),
+            JS_CHUNK.replace("-6.000000", "-3.000000")
+        );
+    }
+
+    #[test]
+    fn js_with_unknown_trailing_tokens() {
+        // Given
+        let text = "<JS utility/volume \"My volume\"\n  -6.000000 - \"Some preset\" x\n>";
+        // When
+        let mut chunk = match FxStateChunk::parse(text).unwrap() {
+            FxStateChunk::Js(c) => c,
+            _ => panic!("not JS"),
+        };
+        // Then
+        assert_eq!(chunk.custom_name, "My volume");
+        assert_eq!(chunk.slider_values, vec![Some(-6.0), None]);
+        assert_eq!(chunk.to_node().to_string(), text);
+        chunk.slider_values[1] = Some(1.0);
+        assert_eq!(
+            chunk.to_node().to_string(),
+            text.replace(" - ", " 1.000000 ")
+        );
+        chunk.slider_values.push(Some(2.0));
+        assert_eq!(
+            chunk.to_node().to_string(),
+            text.replace(" - ", " 1.000000 2.000000 ")
+        );
+    }
+}
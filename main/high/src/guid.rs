@@ -1,13 +1,14 @@
 use reaper_low::raw::GUID;
 
-use crate::Reaper;
-
 use reaper_medium::ReaperStringArg;
 use std::fmt;
 use std::fmt::Formatter;
 use std::str;
 use std::str::FromStr;
 
+// Length of a GUID string without braces, e.g. `E64BB283-FB17-4702-ACFA-2DDB7E38F14F`.
+const GUID_STRING_LENGTH: usize = 36;
+
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Guid {
     internal: GUID,
@@ -18,32 +19,63 @@ impl Guid {
         Guid { internal }
     }
 
+    pub fn raw(&self) -> GUID {
+        self.internal
+    }
+
+    // Parsing and formatting is done in Rust (not via REAPER's `stringToGuid()` and
+    // `guidToString()`), so GUIDs can also be used when REAPER is not running, e.g. in tests or
+    // when loading serialized data.
     pub fn from_string_with_braces<'a>(
         text: impl Into<ReaperStringArg<'a>>,
     ) -> Result<Guid, &'static str> {
-        Reaper::get()
-            .medium_reaper()
-            .string_to_guid(text)
-            .map(Guid::new)
-            .map_err(|_| "invalid GUID")
+        let text = text.into().into_inner();
+        let text = text
+            .to_str()
+            .strip_prefix('{')
+            .and_then(|t| t.strip_suffix('}'))
+            .ok_or("GUID must be enclosed in braces")?;
+        Self::from_string_without_braces(text)
     }
 
     pub fn from_string_without_braces(text: &str) -> Result<Guid, &'static str> {
-        Self::from_string_with_braces(format!("{{{}}}", text).as_str())
+        let groups: Vec<&str> = text.split('-').collect();
+        let is_valid = text.len() == GUID_STRING_LENGTH
+            && groups
+                .iter()
+                .map(|g| g.len())
+                .eq([8, 4, 4, 4, 12].iter().copied())
+            && text.chars().all(|c| c == '-' || c.is_ascii_hexdigit());
+        if !is_valid {
+            return Err("invalid GUID");
+        }
+        let parse_u16 = |t: &str| u16::from_str_radix(t, 16).expect("hex digits checked");
+        let tail = format!("{}{}", groups[3], groups[4]);
+        let mut data_4 = [0u8; 8];
+        for (i, byte) in data_4.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&tail[i * 2..i * 2 + 2], 16).expect("hex digits checked");
+        }
+        let internal = GUID {
+            Data1: u32::from_str_radix(groups[0], 16).expect("hex digits checked"),
+            Data2: parse_u16(groups[1]),
+            Data3: parse_u16(groups[2]),
+            Data4: data_4,
+        };
+        Ok(Guid::new(internal))
     }
 
+    /// Returns the GUID in REAPER's format, e.g. `{E64BB283-FB17-4702-ACFA-2DDB7E38F14F}`.
     pub fn to_string_with_braces(&self) -> String {
-        Reaper::get()
-            .medium_reaper()
-            .guid_to_string(&self.internal)
-            .into_string()
+        format!("{{{}}}", self.to_string_without_braces())
     }
 
     pub fn to_string_without_braces(&self) -> String {
-        let mut s = self.to_string_with_braces();
-        s.remove(0);
-        s.truncate(36);
-        s
+        let g = &self.internal;
+        let d = &g.Data4;
+        format!(
+            "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+            g.Data1, g.Data2, g.Data3, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+        )
     }
 }
 
@@ -64,3 +96,71 @@ impl FromStr for Guid {
         }
     }
 }
+
+// Serialized as string with braces, e.g. "{1A3B...}".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Guid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string_with_braces())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Guid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        // Given
+        let text = "{E64BB283-FB17-4702-ACFA-2DDB7E38F14F}";
+        // When
+        let guid = Guid::from_string_with_braces(text).unwrap();
+        // Then
+        let raw = guid.raw();
+        assert_eq!(raw.Data1, 0xE64B_B283);
+        assert_eq!(raw.Data2, 0xFB17);
+        assert_eq!(raw.Data3, 0x4702);
+        assert_eq!(raw.Data4, [0xAC, 0xFA, 0x2D, 0xDB, 0x7E, 0x38, 0xF1, 0x4F]);
+        assert_eq!(guid.to_string_with_braces(), text);
+        assert_eq!(
+            guid.to_string_without_braces(),
+            "E64BB283-FB17-4702-ACFA-2DDB7E38F14F"
+        );
+        assert_eq!(text.to_lowercase().parse::<Guid>().unwrap(), guid);
+        assert_eq!(text[1..37].parse::<Guid>().unwrap(), guid);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Guid::from_string_with_braces("{hey}").is_err());
+        assert!(Guid::from_string_with_braces("E64BB283-FB17-4702-ACFA-2DDB7E38F14F").is_err());
+        for text in &[
+            "E64BB283FB17-4702-ACFA-2DDB7E38F14F0",
+            "E64BB283-FB17-4702-ACFA-2DDB7E38F14G",
+            "+64BB283-FB17-4702-ACFA-2DDB7E38F14F",
+        ] {
+            assert!(text.parse::<Guid>().is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        // Given
+        let guid: Guid = "{E64BB283-FB17-4702-ACFA-2DDB7E38F14F}".parse().unwrap();
+        // When
+        let json = serde_json::to_string(&guid).unwrap();
+        // Then
+        assert_eq!(json, r#""{E64BB283-FB17-4702-ACFA-2DDB7E38F14F}""#);
+        assert_eq!(serde_json::from_str::<Guid>(&json).unwrap(), guid);
+        assert!(serde_json::from_str::<Guid>(r#""{hey}""#).is_err());
+    }
+}
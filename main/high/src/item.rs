@@ -1,5 +1,8 @@
-use crate::{Project, Reaper, Take};
-use reaper_medium::MediaItem;
+use crate::{Chunk, Guid, Project, Reaper, Take, Track};
+use reaper_medium::{
+    ChunkCacheHint, DurationInSeconds, ItemAttributeKey, MediaItem, PositionInSeconds,
+};
+use std::convert::TryInto;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct Item {
@@ -24,8 +27,87 @@ impl Item {
         Some(Project::new(raw_project))
     }
 
+    pub fn guid(self) -> Guid {
+        let internal = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_set_media_item_info_get_guid(self.raw)
+        };
+        Guid::new(internal)
+    }
+
+    pub fn track(self) -> Option<Track> {
+        let raw_track = unsafe { Reaper::get().medium_reaper.get_media_item_track(self.raw)? };
+        let raw_project = self.project().map(|p| p.raw());
+        Some(Track::new(raw_track, raw_project))
+    }
+
+    pub fn position(self) -> PositionInSeconds {
+        let value = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_info_value(self.raw, ItemAttributeKey::Position)
+        };
+        PositionInSeconds::new(value)
+    }
+
+    pub fn length(self) -> DurationInSeconds {
+        let value = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_info_value(self.raw, ItemAttributeKey::Length)
+        };
+        DurationInSeconds::new(value)
+    }
+
+    pub fn is_selected(self) -> bool {
+        let value = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_info_value(self.raw, ItemAttributeKey::UiSel)
+        };
+        value != 0.0
+    }
+
     pub fn active_take(self) -> Option<Take> {
         let raw_take = unsafe { Reaper::get().medium_reaper.get_active_take(self.raw)? };
         Some(Take::new(raw_take))
     }
+
+    pub fn take_count(self) -> u32 {
+        unsafe { Reaper::get().medium_reaper.count_takes(self.raw) }
+    }
+
+    pub fn take_by_index(self, index: u32) -> Option<Take> {
+        let raw_take = unsafe { Reaper::get().medium_reaper.get_take(self.raw, index)? };
+        Some(Take::new(raw_take))
+    }
+
+    pub fn takes(self) -> impl Iterator<Item = Take> + ExactSizeIterator + 'static {
+        (0..self.take_count()).map(move |i| self.take_by_index(i).unwrap())
+    }
+
+    pub fn chunk(
+        self,
+        max_chunk_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> Result<Chunk, &'static str> {
+        let chunk_content = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_item_state_chunk(self.raw, max_chunk_size, cache_hint)
+                .map_err(|_| "Couldn't load item chunk")?
+        };
+        Ok(chunk_content.into())
+    }
+
+    pub fn set_chunk(self, chunk: Chunk, cache_hint: ChunkCacheHint) -> Result<(), &'static str> {
+        let string: String = chunk.try_into()?;
+        unsafe {
+            Reaper::get()
+                .medium_reaper
+                .set_item_state_chunk(self.raw, string, cache_hint)
+                .map_err(|_| "Couldn't set item chunk")
+        }
+    }
 }
@@ -15,6 +15,10 @@
 //! # Ok::<_, Box<dyn std::error::Error>>(())
 //! ```
 
+// Renamed in Cargo.toml so that the "serde" feature can enable serialization in reaper-medium too
+#[cfg(feature = "serde")]
+extern crate serde_crate as serde;
+
 #[macro_use]
 mod regex_util;
 
@@ -36,6 +40,9 @@ pub use reaper::*;
 mod main_task_middleware;
 pub use main_task_middleware::*;
 
+mod scene;
+pub use scene::*;
+
 mod main_future_middleware;
 pub use main_future_middleware::*;
 
@@ -72,6 +79,9 @@ pub use guid::*;
 mod fx_chain;
 pub use fx_chain::*;
 
+mod fx_state_chunk;
+pub use fx_state_chunk::*;
+
 mod midi_input_device;
 pub use midi_input_device::*;
 
@@ -96,12 +106,30 @@ pub use tempo::*;
 mod chunk;
 pub use chunk::*;
 
+mod chunk_diff;
+pub use chunk_diff::*;
+
+mod rpp;
+pub use rpp::*;
+
+mod rpp_project;
+pub use rpp_project::*;
+
 mod item;
 pub use item::*;
 
+mod envelope;
+pub use envelope::*;
+
+mod object_ref;
+pub use object_ref::*;
+
 mod source;
 pub use source::*;
 
+mod peaks;
+pub use peaks::*;
+
 mod action_character;
 pub use action_character::*;
 
@@ -110,6 +138,11 @@ mod meter_middleware;
 #[cfg(feature = "serde")]
 pub use meter_middleware::*;
 
+#[cfg(feature = "serde")]
+mod project_snapshot;
+#[cfg(feature = "serde")]
+pub use project_snapshot::*;
+
 mod undo_block;
 
 mod normalized_value;
@@ -117,9 +150,20 @@ mod normalized_value;
 mod middleware_control_surface;
 pub use middleware_control_surface::*;
 
+#[cfg(feature = "control-surface-recording")]
+mod control_surface_recording;
+#[cfg(feature = "control-surface-recording")]
+pub use control_surface_recording::*;
+
 mod change_detection_middleware;
 pub use change_detection_middleware::*;
 
+mod change_event_coalescer;
+pub use change_event_coalescer::*;
+
+mod event_streams;
+pub use event_streams::*;
+
 mod option_util;
 
 mod bookmark;
@@ -165,12 +165,13 @@ impl MainTaskMiddleware {
     }
 
     pub fn run(&mut self) {
-        // Process plain main thread tasks in queue
-        for task in self
+        // Process plain main thread tasks in queue. Tasks which are scheduled while processing
+        // (e.g. rescheduled ones) are processed in the next cycle.
+        let task_count = self
             .main_thread_task_receiver
-            .try_iter()
-            .take(DEFAULT_MAIN_THREAD_TASK_BULK_SIZE)
-        {
+            .len()
+            .min(DEFAULT_MAIN_THREAD_TASK_BULK_SIZE);
+        for task in self.main_thread_task_receiver.try_iter().take(task_count) {
             match task.desired_execution_time {
                 None => (task.op)(),
                 Some(t) => {
@@ -18,6 +18,7 @@ pub struct MeterMiddleware {
 }
 
 #[derive(Debug, Default, Serialize)]
+#[serde(crate = "serde_crate")]
 pub struct MeterMiddlewareMetrics {
     run: CustomResponseTime,
     close_no_reset: CustomResponseTime,
@@ -4,7 +4,11 @@ use reaper_medium::{MidiInput, MidiInputDeviceId, ReaperString};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
 pub struct MidiInputDevice {
     id: MidiInputDeviceId,
 }
@@ -4,7 +4,11 @@ use reaper_medium::{MidiOutput, MidiOutputDeviceId, ReaperString};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate", transparent)
+)]
 pub struct MidiOutputDevice {
     id: MidiOutputDeviceId,
 }
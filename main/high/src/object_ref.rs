@@ -0,0 +1,210 @@
+//! Stable references to objects within a project.
+use crate::{
+    Envelope, Fx, FxChain, FxChainContext, FxParameter, Guid, Item, Project, Reaper, Take, Track,
+};
+use reaper_medium::EnvChunkName;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifies a track independently of its position.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum TrackRef {
+    /// The master track (its GUID is not stable across projects).
+    Master,
+    Guid(Guid),
+}
+
+/// Identifies an FX chain independently of the position of its track.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum FxChainRef {
+    /// The global monitoring FX chain.
+    Monitoring,
+    Track {
+        track: TrackRef,
+        is_input_fx: bool,
+    },
+    Take(Guid),
+}
+
+/// Identifies an object within a project independently of its position.
+///
+/// Tracks, FX, items and takes are identified by their GUIDs, envelopes by their chunk name
+/// (e.g. `VOLENV2`), so a reference keeps pointing to the same object after reordering.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(crate = "serde_crate")
+)]
+pub enum ObjectRef {
+    Track(TrackRef),
+    Fx {
+        chain: FxChainRef,
+        fx: Guid,
+    },
+    FxParameter {
+        chain: FxChainRef,
+        fx: Guid,
+        index: u32,
+    },
+    Item(Guid),
+    Take(Guid),
+    TrackEnvelope {
+        track: TrackRef,
+        chunk_name: String,
+    },
+}
+
+/// A live object to which an `ObjectRef` has been resolved.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ResolvedObject {
+    Track(Track),
+    Fx(Fx),
+    FxParameter(FxParameter),
+    Item(Item),
+    Take(Take),
+    Envelope(Envelope),
+}
+
+impl TrackRef {
+    pub fn from_track(track: &Track) -> TrackRef {
+        if track.is_master_track() {
+            TrackRef::Master
+        } else {
+            TrackRef::Guid(*track.guid())
+        }
+    }
+
+    pub fn resolve(self, project: Project) -> Result<Track, &'static str> {
+        let track = match self {
+            TrackRef::Master => project.master_track(),
+            TrackRef::Guid(guid) => project.track_by_guid(&guid),
+        };
+        if !track.is_available() {
+            return Err("track not found");
+        }
+        Ok(track)
+    }
+}
+
+impl FxChainRef {
+    pub fn from_fx_chain(chain: &FxChain) -> FxChainRef {
+        match chain.context() {
+            FxChainContext::Monitoring => FxChainRef::Monitoring,
+            FxChainContext::Track { track, is_input_fx } => FxChainRef::Track {
+                track: TrackRef::from_track(track),
+                is_input_fx: *is_input_fx,
+            },
+            FxChainContext::Take(take) => FxChainRef::Take(take.guid()),
+        }
+    }
+
+    pub fn resolve(self, project: Project) -> Result<FxChain, &'static str> {
+        let chain = match self {
+            FxChainRef::Monitoring => Reaper::get().monitoring_fx_chain(),
+            FxChainRef::Track { track, is_input_fx } => {
+                let track = track.resolve(project)?;
+                if is_input_fx {
+                    track.input_fx_chain()
+                } else {
+                    track.normal_fx_chain()
+                }
+            }
+            FxChainRef::Take(guid) => project
+                .take_by_guid(&guid)
+                .ok_or("take not found")?
+                .fx_chain(),
+        };
+        Ok(chain)
+    }
+}
+
+impl ObjectRef {
+    pub fn from_track(track: &Track) -> ObjectRef {
+        ObjectRef::Track(TrackRef::from_track(track))
+    }
+
+    /// Returns `None` if the FX doesn't exist anymore.
+    pub fn from_fx(fx: &Fx) -> Option<ObjectRef> {
+        let object_ref = ObjectRef::Fx {
+            chain: FxChainRef::from_fx_chain(fx.chain()),
+            fx: fx.guid()?,
+        };
+        Some(object_ref)
+    }
+
+    /// Returns `None` if the FX doesn't exist anymore.
+    pub fn from_fx_parameter(param: &FxParameter) -> Option<ObjectRef> {
+        let fx = param.fx();
+        let object_ref = ObjectRef::FxParameter {
+            chain: FxChainRef::from_fx_chain(fx.chain()),
+            fx: fx.guid()?,
+            index: param.index(),
+        };
+        Some(object_ref)
+    }
+
+    pub fn from_item(item: Item) -> ObjectRef {
+        ObjectRef::Item(item.guid())
+    }
+
+    pub fn from_take(take: &Take) -> ObjectRef {
+        ObjectRef::Take(take.guid())
+    }
+
+    pub fn from_track_envelope(track: &Track, chunk_name: impl Into<String>) -> ObjectRef {
+        ObjectRef::TrackEnvelope {
+            track: TrackRef::from_track(track),
+            chunk_name: chunk_name.into(),
+        }
+    }
+
+    /// Looks up the referenced object in the given project.
+    ///
+    /// Returns an error describing which part of the reference couldn't be found.
+    pub fn resolve(&self, project: Project) -> Result<ResolvedObject, &'static str> {
+        let resolved = match self {
+            ObjectRef::Track(track) => ResolvedObject::Track(track.resolve(project)?),
+            ObjectRef::Fx { chain, fx } => ResolvedObject::Fx(resolve_fx(project, *chain, fx)?),
+            ObjectRef::FxParameter { chain, fx, index } => {
+                let fx = resolve_fx(project, *chain, fx)?;
+                if *index >= fx.parameter_count() {
+                    return Err("FX parameter not found");
+                }
+                ResolvedObject::FxParameter(fx.parameter_by_index(*index))
+            }
+            ObjectRef::Item(guid) => {
+                ResolvedObject::Item(project.item_by_guid(guid).ok_or("item not found")?)
+            }
+            ObjectRef::Take(guid) => {
+                ResolvedObject::Take(project.take_by_guid(guid).ok_or("take not found")?)
+            }
+            ObjectRef::TrackEnvelope { track, chunk_name } => {
+                let track = track.resolve(project)?;
+                let envelope = track
+                    .envelope_by_chunk_name(EnvChunkName::custom(chunk_name.as_str()))
+                    .ok_or("envelope not found")?;
+                ResolvedObject::Envelope(envelope)
+            }
+        };
+        Ok(resolved)
+    }
+}
+
+fn resolve_fx(project: Project, chain: FxChainRef, guid: &Guid) -> Result<Fx, &'static str> {
+    let fx = chain.resolve(project)?.fx_by_guid(guid);
+    if !fx.is_available() {
+        return Err("FX not found");
+    }
+    Ok(fx)
+}
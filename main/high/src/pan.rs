@@ -3,6 +3,7 @@ use reaper_medium::ReaperPanValue;
 use std::fmt;
 use std::str::FromStr;
 
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Pan {
     normalized_value: f64,
 }
@@ -45,3 +46,41 @@ impl fmt::Display for Pan {
         write!(f, "{}", pan_string)
     }
 }
+
+// Serialized as REAPER pan value (from -1.0 to 1.0).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Pan {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.reaper_value().get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pan {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = f64::deserialize(deserializer)?;
+        if !(-1.0..=1.0).contains(&value) {
+            return Err(serde::de::Error::custom(
+                "pan value must be between -1.0 and 1.0",
+            ));
+        }
+        Ok(Pan::from_normalized_value((value + 1.0) / 2.0))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        // Given
+        let pan = Pan::from_normalized_value(0.25);
+        // When
+        let json = serde_json::to_string(&pan).unwrap();
+        // Then
+        assert_eq!(json, "-0.5");
+        assert_eq!(serde_json::from_str::<Pan>(&json).unwrap(), pan);
+        assert!(serde_json::from_str::<Pan>("1.5").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{Reaper, ReaperSource, Take};
+use reaper_medium::{
+    BorrowedPcmSourcePeakTransfer, DurationInSeconds, GetPeaksResult, Hz, OwnedPeakBuilder,
+    OwnedPeakGetter, PcmSource, PeakExtraType, PositionInSeconds, SpectralPeak,
+};
+use std::path::{Path, PathBuf};
+
+/// REAPER can't return more than this number of peak samples per channel in one go.
+const MAX_SAMPLE_COUNT_PER_CHANNEL: u32 = 0x000f_ffff;
+
+const PEAK_FILE_NAME_BUFFER_SIZE: u32 = 2048;
+
+/// Reads peaks (e.g. for drawing waveforms) from a REAPER-managed source or a take.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PeakReader {
+    target: PeakReaderTarget,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+enum PeakReaderTarget {
+    Source(ReaperSource),
+    Take(Take),
+}
+
+impl PeakReader {
+    /// Reads peaks directly from the given source.
+    pub fn from_source(source: ReaperSource) -> PeakReader {
+        PeakReader {
+            target: PeakReaderTarget::Source(source),
+        }
+    }
+
+    /// Reads peaks from the given take (taking take properties such as offset and play rate into
+    /// account).
+    pub fn from_take(take: Take) -> PeakReader {
+        PeakReader {
+            target: PeakReaderTarget::Take(take),
+        }
+    }
+
+    /// Reads the peaks for the given time range at the given resolution.
+    ///
+    /// The resolution is the number of peaks per second. Spectral information is only returned if
+    /// requested and available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source or take is not valid anymore or if the requested range would result in
+    /// more peaks than REAPER can deliver at once.
+    pub fn read(
+        &self,
+        start: PositionInSeconds,
+        duration: DurationInSeconds,
+        resolution: Hz,
+        channel_count: u32,
+        include_spectral: bool,
+    ) -> Peaks {
+        let sample_count_per_channel = (duration.get() * resolution.get()).ceil() as u32;
+        assert!(
+            sample_count_per_channel <= MAX_SAMPLE_COUNT_PER_CHANNEL,
+            "too many peaks requested at once"
+        );
+        let extra_type = if include_spectral {
+            Some(PeakExtraType::Spectral)
+        } else {
+            None
+        };
+        let block_size = (channel_count as usize)
+            .checked_mul(sample_count_per_channel as usize)
+            .expect("too many peaks requested at once");
+        let block_count = if include_spectral { 3 } else { 2 };
+        let mut buffer = vec![0.0; block_size * block_count];
+        let reaper = &Reaper::get().medium_reaper;
+        let result = match self.target {
+            PeakReaderTarget::Source(source) => {
+                if !source.is_valid() {
+                    panic!("PCM source pointer is not valid anymore in REAPER")
+                }
+                unsafe {
+                    reaper.pcm_source_get_peaks(
+                        source.raw(),
+                        resolution,
+                        start,
+                        channel_count,
+                        sample_count_per_channel,
+                        extra_type,
+                        &mut buffer,
+                    )
+                }
+            }
+            PeakReaderTarget::Take(take) => {
+                if !take.is_available() {
+                    panic!("take pointer is not valid anymore in REAPER")
+                }
+                unsafe {
+                    reaper.get_media_item_take_peaks(
+                        take.raw(),
+                        resolution,
+                        start,
+                        channel_count,
+                        sample_count_per_channel,
+                        extra_type,
+                        &mut buffer,
+                    )
+                }
+            }
+        };
+        Peaks::from_buffer(buffer, block_size, channel_count, result)
+    }
+}
+
+/// Peaks as returned by [`PeakReader`].
+///
+/// [`PeakReader`]: struct.PeakReader.html
+#[derive(Clone, PartialEq, Debug)]
+pub struct Peaks {
+    channel_count: u32,
+    frame_count: u32,
+    maximums: Vec<f64>,
+    minimums: Vec<f64>,
+    spectral: Option<Vec<SpectralPeak>>,
+}
+
+impl Peaks {
+    fn from_buffer(
+        mut buffer: Vec<f64>,
+        block_size: usize,
+        channel_count: u32,
+        result: GetPeaksResult,
+    ) -> Peaks {
+        // Don't trust REAPER blindly, a bogus sample count must not make us read out of bounds
+        let max_frame_count = block_size.checked_div(channel_count as usize).unwrap_or(0);
+        let frame_count = (result.sample_count as usize).min(max_frame_count);
+        let actual_size = channel_count as usize * frame_count;
+        let spectral = if result.extra_type_available && buffer.len() >= 3 * block_size {
+            let extra = &buffer[2 * block_size..2 * block_size + actual_size];
+            Some(extra.iter().map(|v| SpectralPeak::from_raw(*v)).collect())
+        } else {
+            None
+        };
+        let minimums = buffer[block_size..block_size + actual_size].to_vec();
+        buffer.truncate(actual_size);
+        Peaks {
+            channel_count,
+            frame_count: frame_count as u32,
+            maximums: buffer,
+            minimums,
+            spectral,
+        }
+    }
+
+    /// Returns the number of channels.
+    pub fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Returns the number of peaks per channel which have actually been delivered.
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+
+    /// Returns the maximum of the given peak frame and channel.
+    pub fn maximum(&self, frame: u32, channel: u32) -> f64 {
+        self.maximums[self.index(frame, channel)]
+    }
+
+    /// Returns the minimum of the given peak frame and channel.
+    pub fn minimum(&self, frame: u32, channel: u32) -> f64 {
+        self.minimums[self.index(frame, channel)]
+    }
+
+    /// Returns the spectral information of the given peak frame and channel if available.
+    pub fn spectral(&self, frame: u32, channel: u32) -> Option<SpectralPeak> {
+        let index = self.index(frame, channel);
+        self.spectral.as_ref().map(|s| s[index])
+    }
+
+    /// Returns all maximums, interleaved by channel.
+    pub fn maximums(&self) -> &[f64] {
+        &self.maximums
+    }
+
+    /// Returns all minimums, interleaved by channel.
+    pub fn minimums(&self) -> &[f64] {
+        &self.minimums
+    }
+
+    /// Returns all spectral peaks, interleaved by channel, if available.
+    pub fn spectral_peaks(&self) -> Option<&[SpectralPeak]> {
+        self.spectral.as_deref()
+    }
+
+    fn index(&self, frame: u32, channel: u32) -> usize {
+        assert!(frame < self.frame_count, "peak frame out of range");
+        assert!(channel < self.channel_count, "channel out of range");
+        (frame * self.channel_count + channel) as usize
+    }
+}
+
+/// Makes it easy to support REAPER peak files (`.reapeaks`) in a [`CustomPcmSource`]
+/// implementation.
+///
+/// Just delegate the peak-related methods of your source to the equally named methods of this
+/// struct.
+///
+/// [`CustomPcmSource`]: /reaper_medium/trait.CustomPcmSource.html
+#[derive(Debug)]
+pub struct CustomSourcePeaks {
+    media_file: PathBuf,
+    sample_rate: Hz,
+    channel_count: u32,
+    builder: Option<OwnedPeakBuilder>,
+    getter: Option<OwnedPeakGetter>,
+}
+
+impl CustomSourcePeaks {
+    /// Creates peak support for a source which reads the given media file.
+    pub fn new(media_file: PathBuf, sample_rate: Hz, channel_count: u32) -> CustomSourcePeaks {
+        CustomSourcePeaks {
+            media_file,
+            sample_rate,
+            channel_count,
+            builder: None,
+            getter: None,
+        }
+    }
+
+    /// Returns the media file for which peaks are built.
+    pub fn media_file(&self) -> &Path {
+        &self.media_file
+    }
+
+    /// Returns the location of the peak file.
+    pub fn peak_file(&self) -> PathBuf {
+        Reaper::get()
+            .medium_reaper
+            .get_peak_file_name(&self.media_file, PEAK_FILE_NAME_BUFFER_SIZE)
+    }
+
+    /// Returns whether the peak file exists and is newer than the media file.
+    pub fn peak_file_is_up_to_date(&self) -> bool {
+        let modified = |path: &Path| path.metadata().and_then(|m| m.modified()).ok();
+        match (modified(&self.peak_file()), modified(&self.media_file)) {
+            (Some(peak_file_time), Some(media_file_time)) => peak_file_time >= media_file_time,
+            _ => false,
+        }
+    }
+
+    /// To be called from [`CustomPcmSource::peaks_build_begin()`].
+    ///
+    /// If a source is given, REAPER pulls the samples from that source during [`peaks_build_run()`]
+    /// (pass the pointer of the REAPER counterpart of your custom source). If not, you need to
+    /// feed the samples yourself via [`process_samples_interleaved()`].
+    ///
+    /// Returns `false` if building isn't necessary because the peak file is up-to-date.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid source.
+    ///
+    /// [`CustomPcmSource::peaks_build_begin()`]:
+    /// /reaper_medium/trait.CustomPcmSource.html#tymethod.peaks_build_begin
+    /// [`peaks_build_run()`]: #method.peaks_build_run
+    /// [`process_samples_interleaved()`]: #method.process_samples_interleaved
+    pub unsafe fn peaks_build_begin(&mut self, source: Option<PcmSource>) -> bool {
+        if self.builder.is_some() {
+            return true;
+        }
+        if self.peak_file_is_up_to_date() {
+            return false;
+        }
+        // The peak file is going to be rewritten, so don't read the old one anymore.
+        self.getter = None;
+        self.builder = Reaper::get()
+            .medium_reaper
+            .peak_build_create(
+                source,
+                &self.media_file,
+                self.sample_rate,
+                self.channel_count,
+            )
+            .ok();
+        self.builder.is_some()
+    }
+
+    /// Feeds interleaved samples into the peak builder.
+    ///
+    /// Does nothing if building has not begun.
+    pub fn process_samples_interleaved(&mut self, samples: &[f64]) {
+        if let Some(builder) = &self.builder {
+            builder.process_samples_interleaved(samples, self.channel_count);
+        }
+    }
+
+    /// To be called from [`CustomPcmSource::peaks_build_run()`].
+    ///
+    /// Returns `true` if building should continue.
+    ///
+    /// [`CustomPcmSource::peaks_build_run()`]:
+    /// /reaper_medium/trait.CustomPcmSource.html#tymethod.peaks_build_run
+    pub fn peaks_build_run(&mut self) -> bool {
+        match &self.builder {
+            None => false,
+            Some(b) => b.run(),
+        }
+    }
+
+    /// To be called from [`CustomPcmSource::peaks_build_finish()`].
+    ///
+    /// Finishes writing the peak file.
+    ///
+    /// [`CustomPcmSource::peaks_build_finish()`]:
+    /// /reaper_medium/trait.CustomPcmSource.html#tymethod.peaks_build_finish
+    pub fn peaks_build_finish(&mut self) {
+        self.builder = None;
+    }
+
+    /// To be called from [`CustomPcmSource::peaks_clear()`].
+    ///
+    /// [`CustomPcmSource::peaks_clear()`]:
+    /// /reaper_medium/trait.CustomPcmSource.html#tymethod.peaks_clear
+    pub fn peaks_clear(&mut self, delete_file: bool) {
+        self.builder = None;
+        self.getter = None;
+        if delete_file {
+            let _ = std::fs::remove_file(self.peak_file());
+        }
+    }
+
+    /// To be called from [`CustomPcmSource::get_peak_info()`].
+    ///
+    /// Serves the peaks from the builder while building is in progress and from the peak file
+    /// otherwise.
+    ///
+    /// [`CustomPcmSource::get_peak_info()`]:
+    /// /reaper_medium/trait.CustomPcmSource.html#tymethod.get_peak_info
+    pub fn get_peak_info(&mut self, block: &BorrowedPcmSourcePeakTransfer) {
+        if let Some(builder) = &self.builder {
+            builder.get_peak_info(block);
+            return;
+        }
+        if self.getter.is_none() {
+            self.getter = Reaper::get()
+                .medium_reaper
+                .peak_get_create(&self.media_file, self.sample_rate, self.channel_count)
+                .ok();
+        }
+        if let Some(getter) = &self.getter {
+            getter.get_peak_info(block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reaper_medium::PeakTransferMode;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn from_buffer_with_bogus_sample_count() {
+        // Given
+        let buffer = vec![1.0, 2.0, 3.0, 4.0, -1.0, -2.0, -3.0, -4.0];
+        let result = GetPeaksResult {
+            sample_count: u32::MAX,
+            output_mode: PeakTransferMode::Peaks,
+            extra_type_available: false,
+        };
+        // When
+        let peaks = Peaks::from_buffer(buffer, 4, 2, result);
+        // Then
+        assert_eq!(peaks.frame_count(), 2);
+        assert_eq!(peaks.maximum(1, 1), 4.0);
+        assert_eq!(peaks.minimum(1, 1), -4.0);
+    }
+}
@@ -28,3 +28,21 @@ impl PlayRate {
             .master_normalize_play_rate_normalize(self.factor)
     }
 }
+
+// Serialized as playback speed factor.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PlayRate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.factor.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PlayRate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use std::convert::TryFrom;
+        let factor = f64::deserialize(deserializer)?;
+        let factor = PlaybackSpeedFactor::try_from(factor).map_err(serde::de::Error::custom)?;
+        Ok(PlayRate { factor })
+    }
+}
@@ -1,6 +1,7 @@
 use crate::guid::Guid;
 use crate::{
-    BasicBookmarkInfo, BookmarkType, IndexBasedBookmark, Item, PlayRate, Reaper, Tempo, Track,
+    parse_rpp_items, BasicBookmarkInfo, BookmarkType, Chunk, IndexBasedBookmark, Item, PlayRate,
+    Reaper, RppItem, RppNode, Take, Tempo, Track,
 };
 
 use reaper_medium::ProjectContext::{CurrentProject, Proj};
@@ -11,6 +12,7 @@ use reaper_medium::{
     SetEditCurPosOptions, TimeMap2TimeToBeatsResult, TimeRangeType, TrackDefaultsBehavior,
     TrackLocation, UndoBehavior,
 };
+use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -123,6 +125,38 @@ impl Project {
         Some(Item::new(raw_item))
     }
 
+    pub fn item_count(self) -> u32 {
+        self.complain_if_not_available();
+        Reaper::get()
+            .medium_reaper()
+            .count_media_items(Proj(self.rea_project))
+    }
+
+    pub fn item_by_index(self, index: u32) -> Option<Item> {
+        self.complain_if_not_available();
+        let raw_item = Reaper::get()
+            .medium_reaper()
+            .get_media_item(Proj(self.rea_project), index)?;
+        Some(Item::new(raw_item))
+    }
+
+    pub fn items(self) -> impl Iterator<Item = Item> + ExactSizeIterator + 'static {
+        (0..self.item_count()).map(move |i| self.item_by_index(i).unwrap())
+    }
+
+    // REAPER doesn't provide a function for this, so it's a linear search.
+    pub fn item_by_guid(self, guid: &Guid) -> Option<Item> {
+        self.items().find(|item| item.guid() == *guid)
+    }
+
+    pub fn take_by_guid(self, guid: &Guid) -> Option<Take> {
+        self.complain_if_not_available();
+        let raw_take = Reaper::get()
+            .medium_reaper()
+            .get_media_item_take_by_guid(Proj(self.rea_project), &guid.raw())?;
+        Some(Take::new(raw_take))
+    }
+
     pub fn unselect_all_tracks(self) {
         // TODO-low No project context
         unsafe {
@@ -161,6 +195,38 @@ impl Project {
         self.insert_track_at(self.track_count())
     }
 
+    /// Adds the tracks contained in the given track template file (`.RTrackTemplate`) at the end
+    /// of the track list and returns them.
+    ///
+    /// Relative paths are resolved against REAPER's resource path.
+    pub fn insert_track_template(self, path: impl AsRef<Path>) -> Result<Vec<Track>, &'static str> {
+        self.complain_if_not_available();
+        let content = fs::read_to_string(Reaper::get().resolve_resource_path(path))
+            .map_err(|_| "couldn't read track template file")?;
+        let track_nodes: Vec<_> = parse_rpp_items(&content)?
+            .into_iter()
+            .filter_map(|item| match item {
+                RppItem::Node(n) if n.tag == "TRACK" => Some(n),
+                _ => None,
+            })
+            .collect();
+        if track_nodes.is_empty() {
+            return Err("track template doesn't contain tracks");
+        }
+        let first_track_index = self.track_count();
+        // Add all tracks before setting their chunks because receives can refer to tracks which
+        // come later in the template
+        let tracks: Vec<_> = track_nodes.iter().map(|_| self.add_track()).collect();
+        for (track, mut node) in tracks.iter().zip(track_nodes) {
+            // Keep the GUID of the new track and let REAPER assign new FX, item and envelope
+            // GUIDs. Otherwise inserting the same template twice would result in duplicate GUIDs.
+            node.params = vec![track.guid().to_string_with_braces()];
+            prepare_track_template_node(&mut node, first_track_index);
+            track.set_chunk(Chunk::new(node.to_string()))?;
+        }
+        Ok(tracks)
+    }
+
     pub fn remove_track(self, track: &Track) {
         unsafe {
             Reaper::get().medium_reaper().delete_track(track.raw());
@@ -560,9 +626,74 @@ impl Project {
     }
 }
 
+/// Makes the given track chunk of a track template insertable at the given track index.
+///
+/// The source track indexes of receives are relative to the template, so they are shifted.
+fn prepare_track_template_node(node: &mut RppNode, first_track_index: u32) {
+    remove_guid_lines(node);
+    for line in node.lines_mut().filter(|l| l.key() == Some("AUXRECV")) {
+        if let Some(index) = line.parse_value::<u32>(0) {
+            line.tokens[1] = (first_track_index + index).to_string();
+        }
+    }
+}
+
+/// Removes the FX, item, take and envelope GUIDs.
+///
+/// GUIDs of sources are kept because pooled MIDI items are linked via them.
+fn remove_guid_lines(node: &mut RppNode) {
+    for key in &["FXID", "IGUID", "EGUID"] {
+        node.remove_attribute(key);
+    }
+    if node.tag == "TRACK" || node.tag == "ITEM" {
+        node.remove_attribute("GUID");
+    }
+    for child in node.children_mut().filter(|c| c.tag != "SOURCE") {
+        remove_guid_lines(child);
+    }
+}
+
 pub struct FindBookmarkResult {
     pub index: u32,
     pub index_within_type: u32,
     pub bookmark: IndexBasedBookmark,
     pub basic_info: BasicBookmarkInfo,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepare_track_template() {
+        // Given
+        let mut node = RppNode::parse(
+            r#"<TRACK
+  AUXRECV 1 0 1 0 0 0 0 0 0 -1:U 0 -1 ''
+  <FXCHAIN
+    FXID {11111111-1111-1111-1111-111111111111}
+  >
+  <ITEM
+    IGUID {22222222-2222-2222-2222-222222222222}
+    GUID {33333333-3333-3333-3333-333333333333}
+    <SOURCE MIDIPOOL
+      POOLEDEVTS {44444444-4444-4444-4444-444444444444}
+      GUID {55555555-5555-5555-5555-555555555555}
+    >
+  >
+>"#,
+        )
+        .unwrap();
+        // When
+        prepare_track_template_node(&mut node, 3);
+        // Then
+        assert_eq!(node.attribute("AUXRECV").unwrap()[0], "4");
+        assert_eq!(node.find_child("FXCHAIN").unwrap().attribute("FXID"), None);
+        let item = node.find_child("ITEM").unwrap();
+        assert_eq!(item.attribute("IGUID"), None);
+        assert_eq!(item.attribute("GUID"), None);
+        let source = item.find_child("SOURCE").unwrap();
+        assert!(source.attribute("POOLEDEVTS").is_some());
+        assert!(source.attribute("GUID").is_some());
+    }
+}
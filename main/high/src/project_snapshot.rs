@@ -0,0 +1,273 @@
+//! Serializable snapshots of the mixer state of a project.
+use crate::{Fx, FxChain, Guid, Pan, Project, Track, TrackRoutePartner, Volume, Width};
+use reaper_medium::{FxPresetRef, ReaperNormalizedFxParamValue};
+use serde::{Deserialize, Serialize};
+
+/// Mixer state of a project.
+///
+/// Tracks, sends and FX are identified by their GUIDs, so the snapshot can be restored even if
+/// tracks or FX have been reordered in the meantime.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct ProjectSnapshot {
+    pub master_track: TrackSnapshot,
+    pub tracks: Vec<TrackSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct TrackSnapshot {
+    pub guid: Guid,
+    /// `None` for the master track.
+    pub name: Option<String>,
+    pub volume: Volume,
+    pub pan: Pan,
+    pub width: Width,
+    pub muted: bool,
+    pub solo: bool,
+    /// Sends to other tracks (hardware outputs are not included).
+    pub sends: Vec<SendSnapshot>,
+    pub fxs: Vec<FxSnapshot>,
+    pub input_fxs: Vec<FxSnapshot>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct SendSnapshot {
+    /// GUID of the destination track.
+    pub destination: Guid,
+    pub volume: Volume,
+    pub pan: Pan,
+    pub muted: bool,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "serde_crate")]
+pub struct FxSnapshot {
+    pub guid: Guid,
+    /// Just informational, not restored.
+    pub name: String,
+    pub enabled: bool,
+    pub preset_index: Option<u32>,
+    /// Just informational, not restored.
+    pub preset_name: Option<String>,
+    /// REAPER-normalized values of all parameters.
+    pub parameter_values: Vec<f64>,
+}
+
+/// A part of a snapshot which couldn't be restored.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SnapshotRestoreFailure {
+    TrackNotFound { track: Guid },
+    SendNotFound { track: Guid, destination: Guid },
+    SendNotRestored { track: Guid, destination: Guid },
+    FxNotFound { track: Guid, fx: Guid },
+    FxParameterNotFound { fx: Guid, index: u32 },
+    FxParameterNotRestored { fx: Guid, index: u32 },
+}
+
+impl ProjectSnapshot {
+    pub fn capture(project: Project) -> ProjectSnapshot {
+        ProjectSnapshot {
+            master_track: TrackSnapshot::capture(&project.master_track()),
+            tracks: project
+                .tracks()
+                .map(|t| TrackSnapshot::capture(&t))
+                .collect(),
+        }
+    }
+
+    /// Restores the snapshot as far as possible.
+    ///
+    /// Parts which couldn't be restored (e.g. because a track doesn't exist anymore) are skipped
+    /// and reported as error. All other parts are restored nevertheless.
+    pub fn restore(&self, project: Project) -> Result<(), Vec<SnapshotRestoreFailure>> {
+        let mut failures = vec![];
+        // The master track GUID is not stable across projects
+        self.master_track
+            .restore_to(project, &project.master_track(), &mut failures);
+        for track_snapshot in &self.tracks {
+            let track = project.track_by_guid(&track_snapshot.guid);
+            if track.is_available() {
+                track_snapshot.restore_to(project, &track, &mut failures);
+            } else {
+                failures.push(SnapshotRestoreFailure::TrackNotFound {
+                    track: track_snapshot.guid,
+                });
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+impl TrackSnapshot {
+    pub fn capture(track: &Track) -> TrackSnapshot {
+        TrackSnapshot {
+            guid: *track.guid(),
+            name: track.name().map(|n| n.into_string()),
+            volume: track.volume(),
+            pan: track.pan(),
+            width: track.width(),
+            muted: track.is_muted(),
+            solo: track.is_solo(),
+            sends: track
+                .sends()
+                .filter_map(|send| match send.partner()? {
+                    TrackRoutePartner::Track(destination) => Some(SendSnapshot {
+                        destination: *destination.guid(),
+                        volume: send.volume(),
+                        pan: send.pan(),
+                        muted: send.is_muted(),
+                    }),
+                    TrackRoutePartner::HardwareOutput(_) => None,
+                })
+                .collect(),
+            fxs: capture_fxs(&track.normal_fx_chain()),
+            input_fxs: capture_fxs(&track.input_fx_chain()),
+        }
+    }
+
+    fn restore_to(
+        &self,
+        project: Project,
+        track: &Track,
+        failures: &mut Vec<SnapshotRestoreFailure>,
+    ) {
+        if let Some(name) = &self.name {
+            track.set_name(name.as_str());
+        }
+        track.set_volume(self.volume);
+        track.set_pan(self.pan);
+        track.set_width(self.width);
+        if self.muted {
+            track.mute();
+        } else {
+            track.unmute();
+        }
+        if self.solo {
+            track.solo();
+        } else {
+            track.unsolo();
+        }
+        for send_snapshot in &self.sends {
+            let destination_track = project.track_by_guid(&send_snapshot.destination);
+            let send = if destination_track.is_available() {
+                track.find_send_by_destination_track(&destination_track)
+            } else {
+                None
+            };
+            let send = match send {
+                None => {
+                    failures.push(SnapshotRestoreFailure::SendNotFound {
+                        track: self.guid,
+                        destination: send_snapshot.destination,
+                    });
+                    continue;
+                }
+                Some(s) => s,
+            };
+            let result = send
+                .set_volume(send_snapshot.volume)
+                .and_then(|_| send.set_pan(send_snapshot.pan));
+            if result.is_err() {
+                failures.push(SnapshotRestoreFailure::SendNotRestored {
+                    track: self.guid,
+                    destination: send_snapshot.destination,
+                });
+            }
+            if send_snapshot.muted {
+                send.mute();
+            } else {
+                send.unmute();
+            }
+        }
+        restore_fxs(self.guid, &track.normal_fx_chain(), &self.fxs, failures);
+        restore_fxs(
+            self.guid,
+            &track.input_fx_chain(),
+            &self.input_fxs,
+            failures,
+        );
+    }
+}
+
+impl FxSnapshot {
+    pub fn capture(fx: &Fx) -> Option<FxSnapshot> {
+        let snapshot = FxSnapshot {
+            guid: fx.guid()?,
+            name: fx.name().into_string(),
+            enabled: fx.is_enabled(),
+            preset_index: fx.preset_index().ok().flatten(),
+            preset_name: fx.preset_name().map(|n| n.into_string()),
+            parameter_values: fx
+                .parameters()
+                .map(|p| p.reaper_normalized_value().get())
+                .collect(),
+        };
+        Some(snapshot)
+    }
+
+    fn restore_to(&self, fx: &Fx, failures: &mut Vec<SnapshotRestoreFailure>) {
+        if self.enabled {
+            fx.enable();
+        } else {
+            fx.disable();
+        }
+        // Activate preset first because it changes parameter values
+        if let Some(preset_index) = self.preset_index {
+            if fx.preset_index().ok().flatten() != Some(preset_index) {
+                fx.activate_preset(FxPresetRef::Preset(preset_index));
+            }
+        }
+        let parameter_count = fx.parameter_count();
+        for (i, value) in self.parameter_values.iter().enumerate() {
+            let index = i as u32;
+            if index >= parameter_count {
+                failures.push(SnapshotRestoreFailure::FxParameterNotFound {
+                    fx: self.guid,
+                    index,
+                });
+                continue;
+            }
+            let result = fx
+                .parameter_by_index(index)
+                .set_reaper_normalized_value(ReaperNormalizedFxParamValue::new(*value));
+            if result.is_err() {
+                failures.push(SnapshotRestoreFailure::FxParameterNotRestored {
+                    fx: self.guid,
+                    index,
+                });
+            }
+        }
+    }
+}
+
+fn capture_fxs(chain: &FxChain) -> Vec<FxSnapshot> {
+    chain
+        .fxs()
+        .filter_map(|fx| FxSnapshot::capture(&fx))
+        .collect()
+}
+
+fn restore_fxs(
+    track_guid: Guid,
+    chain: &FxChain,
+    fx_snapshots: &[FxSnapshot],
+    failures: &mut Vec<SnapshotRestoreFailure>,
+) {
+    for fx_snapshot in fx_snapshots {
+        let fx = chain.fx_by_guid(&fx_snapshot.guid);
+        if fx.is_available() {
+            fx_snapshot.restore_to(&fx, failures);
+        } else {
+            failures.push(SnapshotRestoreFailure::FxNotFound {
+                track: track_guid,
+                fx: fx_snapshot.guid,
+            });
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! channels etc. Although they end up in the same struct, this gives a little bit of structure.
 use crate::{
     Action, Fx, FxChain, FxParameter, Guid, MidiInputDevice, MidiOutputDevice, Project, Reaper,
-    Section,
+    Section, Take,
 };
 use helgoboss_midi::ShortMessage;
 use reaper_medium::{
@@ -11,7 +11,7 @@ use reaper_medium::{
     MidiOutputDeviceId, ProjectRef, ReaperStringArg, ReaperVersion, SectionId,
     StuffMidiMessageTarget, TrackLocation,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 impl Reaper {
     /// Gives access to the medium-level Reaper instance.
@@ -78,13 +78,38 @@ impl Reaper {
         self.medium_reaper.get_resource_path(|p| p.to_owned())
     }
 
+    /// Resolves the given path relative to the resource path. Absolute paths are returned as is.
+    pub fn resolve_resource_path(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.resource_path().join(path)
+    }
+
     // Attention: Returns normal fx only, not input fx!
     // This is not reliable! After REAPER start no focused Fx can be found!
     pub fn focused_fx(&self) -> Option<Fx> {
         self.medium_reaper().get_focused_fx().and_then(|res| {
             use reaper_medium::GetFocusedFxResult::*;
             match res {
-                TakeFx { .. } => None, // TODO-low implement
+                TakeFx {
+                    track_index,
+                    item_index,
+                    take_index,
+                    fx_index,
+                } => {
+                    // We don't know the project so we must check each project
+                    self.projects()
+                        .filter_map(|p| {
+                            let track = p.track_by_index(track_index)?;
+                            let raw_take = unsafe {
+                                let raw_item = self
+                                    .medium_reaper()
+                                    .get_track_media_item(track.raw(), item_index)?;
+                                self.medium_reaper().get_take(raw_item, take_index)?
+                            };
+                            let fx = Take::new(raw_take).fx_chain().fx_by_index(fx_index)?;
+                            if fx.window_is_open() { Some(fx) } else { None }
+                        })
+                        .next()
+                }
                 TrackFx {
                     track_location,
                     fx_location,
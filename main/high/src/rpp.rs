@@ -0,0 +1,688 @@
+//! Structural access to REAPER's RPP format, which is used for project files (`.RPP`), track
+//! templates and state chunks.
+//!
+//! In contrast to [`Chunk`], this parses the text into a tree of [`RppNode`]s. It doesn't need
+//! REAPER to be running. Writing an unmodified tree produces the same bytes as the ones which were
+//! parsed, except that a leading byte order mark and blank lines after the block are dropped. Lines
+//! which have been modified are written in the same way REAPER would write them.
+//!
+//! [`Chunk`]: struct.Chunk.html
+//! [`RppNode`]: struct.RppNode.html
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+const QUOTE_CHARS: [char; 3] = ['"', '\'', '`'];
+const INDENT: &str = "  ";
+
+/// REAPER usually writes base64-encoded binary data in lines of 128 characters.
+pub const RPP_BASE64_BYTES_PER_LINE: usize = 96;
+
+/// A block in RPP format, e.g. `<TRACK ... >`.
+///
+/// Everything between the opening and the closing line is available in [`items`], in the original
+/// order. [`lines()`] and [`children()`] give convenient access to the simple lines and the nested
+/// blocks.
+///
+/// Equality only takes the content into account, not the formatting (indentation, line endings,
+/// quote characters).
+///
+/// [`items`]: #structfield.items
+/// [`lines()`]: #method.lines
+/// [`children()`]: #method.children
+#[derive(Clone, Debug)]
+pub struct RppNode {
+    /// The tag name without the leading `<`, e.g. `TRACK`.
+    pub tag: String,
+    /// The unquoted parameters following the tag name in the opening line.
+    pub params: Vec<String>,
+    /// Simple lines and nested blocks in their original order.
+    pub items: Vec<RppItem>,
+    header_format: LineFormat,
+    footer_format: LineFormat,
+}
+
+/// Content of an RPP block.
+#[derive(Clone, PartialEq, Debug)]
+pub enum RppItem {
+    Line(RppLine),
+    Node(RppNode),
+}
+
+/// A simple line within an RPP block.
+///
+/// Most lines consist of an attribute name followed by values (e.g. `VOLPAN 1 0 -1 -1 1`) but there
+/// are also data lines, e.g. base64-encoded binary data or `|`-prefixed notes.
+#[derive(Clone, Debug, Default)]
+pub struct RppLine {
+    /// The unquoted tokens of this line.
+    pub tokens: Vec<String>,
+    format: LineFormat,
+}
+
+// Remembers how a line looked like when it was parsed. As long as the parsed content of the line
+// is not changed, we write the line exactly as it was.
+#[derive(Clone, Debug, Default)]
+struct LineFormat {
+    raw: Option<String>,
+    ending: LineEnding,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum LineEnding {
+    None,
+    Lf,
+    CrLf,
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        LineEnding::Lf
+    }
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        use LineEnding::*;
+        match self {
+            None => "",
+            Lf => "\n",
+            CrLf => "\r\n",
+        }
+    }
+}
+
+impl RppNode {
+    /// Creates an empty block.
+    pub fn new(tag: impl Into<String>, params: Vec<String>) -> RppNode {
+        RppNode {
+            tag: tag.into(),
+            params,
+            items: vec![],
+            header_format: Default::default(),
+            footer_format: Default::default(),
+        }
+    }
+
+    /// Parses the given text, which must contain exactly one block.
+    ///
+    /// A leading byte order mark and blank lines after the block are ignored (and therefore not
+    /// written again).
+    pub fn parse(text: &str) -> Result<RppNode, &'static str> {
+        let mut lines = split_lines(strip_bom(text));
+        let (raw, ending) = lines.next().ok_or("empty text")?;
+        let root = parse_node(raw, ending, &mut lines)?;
+        if lines.any(|(raw, _)| !raw.trim().is_empty()) {
+            return Err("unexpected content after end of block");
+        }
+        Ok(root)
+    }
+
+    /// Returns the simple lines of this block (not the ones of nested blocks).
+    pub fn lines(&self) -> impl Iterator<Item = &RppLine> {
+        self.items.iter().filter_map(|item| match item {
+            RppItem::Line(l) => Some(l),
+            RppItem::Node(_) => None,
+        })
+    }
+
+    /// Returns the simple lines of this block for modification.
+    pub fn lines_mut(&mut self) -> impl Iterator<Item = &mut RppLine> {
+        self.items.iter_mut().filter_map(|item| match item {
+            RppItem::Line(l) => Some(l),
+            RppItem::Node(_) => None,
+        })
+    }
+
+    /// Returns the directly nested blocks.
+    pub fn children(&self) -> impl Iterator<Item = &RppNode> {
+        self.items.iter().filter_map(|item| match item {
+            RppItem::Node(n) => Some(n),
+            RppItem::Line(_) => None,
+        })
+    }
+
+    /// Returns the directly nested blocks for modification.
+    pub fn children_mut(&mut self) -> impl Iterator<Item = &mut RppNode> {
+        self.items.iter_mut().filter_map(|item| match item {
+            RppItem::Node(n) => Some(n),
+            RppItem::Line(_) => None,
+        })
+    }
+
+    /// Returns all directly nested blocks with the given tag name.
+    pub fn children_named<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a RppNode> {
+        self.children().filter(move |c| c.tag == tag)
+    }
+
+    /// Returns the first directly nested block with the given tag name.
+    pub fn find_child(&self, tag: &str) -> Option<&RppNode> {
+        self.children().find(|c| c.tag == tag)
+    }
+
+    /// Returns the first directly nested block with the given tag name for modification.
+    pub fn find_child_mut(&mut self, tag: &str) -> Option<&mut RppNode> {
+        self.children_mut().find(|c| c.tag == tag)
+    }
+
+    /// Returns the first simple line starting with the given attribute name.
+    pub fn find_line(&self, key: &str) -> Option<&RppLine> {
+        self.lines().find(|l| l.key() == Some(key))
+    }
+
+    /// Returns the first simple line starting with the given attribute name for modification.
+    pub fn find_line_mut(&mut self, key: &str) -> Option<&mut RppLine> {
+        self.lines_mut().find(|l| l.key() == Some(key))
+    }
+
+    /// Returns the values of the first simple line starting with the given attribute name.
+    pub fn attribute(&self, key: &str) -> Option<&[String]> {
+        self.find_line(key).map(|l| l.values())
+    }
+
+    /// Replaces the values of the first simple line starting with the given attribute name.
+    ///
+    /// Appends a new line if there's no such line yet.
+    pub fn set_attribute(&mut self, key: &str, values: Vec<String>) {
+        if let Some(line) = self.find_line_mut(key) {
+            line.tokens.truncate(1);
+            line.tokens.extend(values);
+            return;
+        }
+        let mut tokens = vec![key.to_string()];
+        tokens.extend(values);
+        self.push_line(RppLine::new(tokens));
+    }
+
+    /// Removes all simple lines starting with the given attribute name.
+    pub fn remove_attribute(&mut self, key: &str) {
+        self.items.retain(|item| match item {
+            RppItem::Line(l) => l.key() != Some(key),
+            RppItem::Node(_) => true,
+        });
+    }
+
+    /// Appends a simple line.
+    pub fn push_line(&mut self, line: RppLine) {
+        self.items.push(RppItem::Line(line));
+    }
+
+    /// Appends a nested block.
+    pub fn push_child(&mut self, child: RppNode) {
+        self.items.push(RppItem::Node(child));
+    }
+
+    /// Decodes the base64-encoded binary data contained in the simple lines of this block.
+    ///
+    /// Each line is decoded separately and the results are concatenated. That's possible because
+    /// REAPER always writes base64 lines whose length is a multiple of 4.
+    pub fn decode_base64_lines(&self) -> Result<Vec<u8>, &'static str> {
+        decode_base64_lines(self.lines())
+    }
+
+    /// Replaces the simple lines of this block with the given binary data, base64-encoded.
+    pub fn set_base64_lines(&mut self, data: &[u8]) {
+        self.items.retain(|item| matches!(item, RppItem::Node(_)));
+        for line in encode_base64_lines(data, RPP_BASE64_BYTES_PER_LINE) {
+            self.push_line(line);
+        }
+    }
+
+    /// Writes this block in RPP format, including the line ending after the closing `>`.
+    pub fn write_to(&self, out: &mut impl Write) -> fmt::Result {
+        self.write_at_depth(out, 0)
+    }
+
+    fn write_at_depth(&self, out: &mut impl Write, depth: usize) -> fmt::Result {
+        let header_tokens = self.header_tokens();
+        write_line(out, &header_tokens, &self.header_format, depth)?;
+        for item in &self.items {
+            match item {
+                RppItem::Line(l) => write_line(out, &l.tokens, &l.format, depth + 1)?,
+                RppItem::Node(n) => n.write_at_depth(out, depth + 1)?,
+            }
+        }
+        write_line(out, &[">".to_string()], &self.footer_format, depth)
+    }
+
+    fn header_tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::with_capacity(self.params.len() + 1);
+        tokens.push(format!("<{}", self.tag));
+        tokens.extend(self.params.iter().cloned());
+        tokens
+    }
+}
+
+impl PartialEq for RppNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag && self.params == other.params && self.items == other.items
+    }
+}
+
+impl Display for RppNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_to(f)
+    }
+}
+
+impl FromStr for RppNode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RppNode::parse(s)
+    }
+}
+
+impl RppLine {
+    /// Creates a line consisting of the given unquoted tokens.
+    pub fn new(tokens: Vec<String>) -> RppLine {
+        RppLine {
+            tokens,
+            format: Default::default(),
+        }
+    }
+
+    /// Parses a single line (without line ending).
+    pub fn parse(line: &str) -> RppLine {
+        RppLine {
+            tokens: tokenize(line),
+            format: LineFormat {
+                raw: Some(line.to_string()),
+                ending: LineEnding::None,
+            },
+        }
+    }
+
+    /// Returns the first token, which is usually the attribute name.
+    pub fn key(&self) -> Option<&str> {
+        self.tokens.first().map(|t| t.as_str())
+    }
+
+    /// Returns all tokens except the first one.
+    pub fn values(&self) -> &[String] {
+        self.tokens.get(1..).unwrap_or(&[])
+    }
+
+    /// Returns the value at the given index (not counting the attribute name).
+    pub fn value(&self, index: usize) -> Option<&str> {
+        self.values().get(index).map(|v| v.as_str())
+    }
+
+    /// Parses the value at the given index (not counting the attribute name).
+    pub fn parse_value<T: FromStr>(&self, index: usize) -> Option<T> {
+        self.value(index)?.parse().ok()
+    }
+}
+
+impl PartialEq for RppLine {
+    fn eq(&self, other: &Self) -> bool {
+        self.tokens == other.tokens
+    }
+}
+
+impl Display for RppLine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.format.raw {
+            Some(raw) if tokenize(raw) == self.tokens => f.write_str(raw.trim_start()),
+            _ => f.write_str(&join_tokens(&self.tokens)),
+        }
+    }
+}
+
+/// Splits the given line into unquoted tokens the same way as REAPER does.
+///
+/// Tokens are separated by whitespace. A token starting with `"`, `'` or `` ` `` extends until the
+/// next occurrence of the same quote character.
+pub fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = line.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if QUOTE_CHARS.contains(&c) {
+            chars.next();
+            let content_start = start + c.len_utf8();
+            match line[content_start..].find(c) {
+                Some(rel_end) => {
+                    let end = content_start + rel_end;
+                    tokens.push(line[content_start..end].to_string());
+                    while let Some(&(i, _)) = chars.peek() {
+                        if i > end {
+                            break;
+                        }
+                        chars.next();
+                    }
+                }
+                None => {
+                    // Unterminated quote. Take the rest of the line.
+                    tokens.push(line[content_start..].to_string());
+                    break;
+                }
+            }
+        } else {
+            let mut end = line.len();
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    end = i;
+                    break;
+                }
+                chars.next();
+            }
+            tokens.push(line[start..end].to_string());
+        }
+    }
+    tokens
+}
+
+/// Quotes the given token if necessary, the same way as REAPER does.
+///
+/// If the token contains all possible quote characters, backticks are replaced with single quotes
+/// (that's what REAPER does as well, so the value can't be preserved exactly in this case).
+pub fn quote_token(token: &str) -> String {
+    let needs_quotes = token.is_empty()
+        || token.chars().any(char::is_whitespace)
+        || token.starts_with(&QUOTE_CHARS[..]);
+    if !needs_quotes {
+        return token.to_string();
+    }
+    match QUOTE_CHARS.iter().find(|q| !token.contains(**q)) {
+        Some(q) => format!("{}{}{}", q, token, q),
+        None => format!("`{}`", token.replace('`', "'")),
+    }
+}
+
+/// Joins the given tokens to a line, quoting them where necessary.
+pub fn join_tokens(tokens: &[String]) -> String {
+    let quoted: Vec<_> = tokens.iter().map(|t| quote_token(t)).collect();
+    quoted.join(" ")
+}
+
+/// Decodes the given base64-encoded lines and concatenates the results.
+pub fn decode_base64_lines<'a>(
+    lines: impl IntoIterator<Item = &'a RppLine>,
+) -> Result<Vec<u8>, &'static str> {
+    let mut data = vec![];
+    for line in lines {
+        for token in &line.tokens {
+            let decoded = base64::decode(token).map_err(|_| "invalid base64 data")?;
+            data.extend(decoded);
+        }
+    }
+    Ok(data)
+}
+
+/// Encodes the given binary data as base64 lines, each one representing at most the given number
+/// of bytes.
+pub fn encode_base64_lines(data: &[u8], bytes_per_line: usize) -> Vec<RppLine> {
+    assert!(
+        bytes_per_line > 0 && bytes_per_line % 3 == 0,
+        "bytes per line must be a positive multiple of 3"
+    );
+    data.chunks(bytes_per_line)
+        .map(|chunk| RppLine::new(vec![base64::encode(chunk)]))
+        .collect()
+}
+
+/// Parses a sequence of simple lines and blocks which is not enclosed in a block.
+///
+/// That's the format of FX chain (`.RfxChain`) and track template (`.RTrackTemplate`) files.
+///
+/// A leading byte order mark is ignored.
+pub fn parse_rpp_items(text: &str) -> Result<Vec<RppItem>, &'static str> {
+    let mut lines = split_lines(strip_bom(text));
+    let mut items = vec![];
+    while let Some((raw, ending)) = lines.next() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with('<') {
+            let node = parse_node(raw, ending, &mut lines)?;
+            items.push(RppItem::Node(node));
+        } else if trimmed.trim_end() == ">" {
+            return Err("end of block without beginning");
+        } else {
+            let line = RppLine {
+                tokens: tokenize(raw),
+                format: LineFormat {
+                    raw: Some(raw.to_string()),
+                    ending,
+                },
+            };
+            items.push(RppItem::Line(line));
+        }
+    }
+    Ok(items)
+}
+
+/// Writes a sequence of simple lines and blocks which is not enclosed in a block.
+pub fn write_rpp_items(out: &mut impl Write, items: &[RppItem]) -> fmt::Result {
+    for item in items {
+        match item {
+            RppItem::Line(l) => write_line(out, &l.tokens, &l.format, 0)?,
+            RppItem::Node(n) => n.write_to(out)?,
+        }
+    }
+    Ok(())
+}
+
+fn strip_bom(text: &str) -> &str {
+    text.strip_prefix('\u{feff}').unwrap_or(text)
+}
+
+fn split_lines(text: &str) -> impl Iterator<Item = (&str, LineEnding)> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let (line, ending, consumed) = match rest.find('\n') {
+            None => (rest, LineEnding::None, rest.len()),
+            Some(i) if i > 0 && rest.as_bytes()[i - 1] == b'\r' => {
+                (&rest[..i - 1], LineEnding::CrLf, i + 1)
+            }
+            Some(i) => (&rest[..i], LineEnding::Lf, i + 1),
+        };
+        rest = &rest[consumed..];
+        Some((line, ending))
+    })
+}
+
+fn parse_node<'a>(
+    header: &'a str,
+    header_ending: LineEnding,
+    lines: &mut impl Iterator<Item = (&'a str, LineEnding)>,
+) -> Result<RppNode, &'static str> {
+    let mut tokens = tokenize(header);
+    if tokens.is_empty() || !tokens[0].starts_with('<') {
+        return Err("block doesn't start with tag opener");
+    }
+    let tag = tokens.remove(0)[1..].to_string();
+    let mut node = RppNode {
+        tag,
+        params: tokens,
+        items: vec![],
+        header_format: LineFormat {
+            raw: Some(header.to_string()),
+            ending: header_ending,
+        },
+        footer_format: Default::default(),
+    };
+    while let Some((raw, ending)) = lines.next() {
+        let trimmed = raw.trim_start();
+        if trimmed.starts_with('<') {
+            let child = parse_node(raw, ending, lines)?;
+            node.items.push(RppItem::Node(child));
+        } else if trimmed.trim_end() == ">" {
+            node.footer_format = LineFormat {
+                raw: Some(raw.to_string()),
+                ending,
+            };
+            return Ok(node);
+        } else {
+            let line = RppLine {
+                tokens: tokenize(raw),
+                format: LineFormat {
+                    raw: Some(raw.to_string()),
+                    ending,
+                },
+            };
+            node.items.push(RppItem::Line(line));
+        }
+    }
+    Err("block not closed")
+}
+
+fn write_line(
+    out: &mut impl Write,
+    tokens: &[String],
+    format: &LineFormat,
+    depth: usize,
+) -> fmt::Result {
+    match &format.raw {
+        Some(raw) if tokenize(raw) == tokens => out.write_str(raw)?,
+        _ => {
+            for _ in 0..depth {
+                out.write_str(INDENT)?;
+            }
+            out.write_str(&join_tokens(tokens))?;
+        }
+    }
+    out.write_str(format.ending.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACK_CHUNK: &str = r#"<TRACK {8F9D2B6C-57A5-4B6B-A3C7-0B3C9B1A4C5E}
+  NAME "My track"
+  PEAKCOL 16576
+  VOLPAN 1 0 -1 -1 1
+  <FXCHAIN
+    SHOW 0
+    <VST "VST: ReaEQ (Cockos)" reaeq.dll 0 "" 1919247729<56535472656571726561657100000000> ""
+      cWVyde9e7f4CAAAAAQAAAAAAAAACAAAAAAAAAAIAAAABAAAAAAAAAAIAAAAAAAAAXAAAAAEAAAAAABAA
+      AAAQAAAAAAAAAEAAAAA=
+    >
+    FLOATPOS 0 0 0 0
+  >
+>
+"#;
+
+    #[test]
+    fn items() {
+        let text = "BYPASS 0 0 0\n<JS utility/volume \"\"\n  0 -\n>\nFXID {A}\nWAK 0 0\n";
+        let items = parse_rpp_items(text).unwrap();
+        assert_eq!(items.len(), 4);
+        assert!(matches!(&items[1], RppItem::Node(n) if n.tag == "JS"));
+        let mut out = String::new();
+        write_rpp_items(&mut out, &items).unwrap();
+        assert_eq!(out, text);
+        assert!(parse_rpp_items("A\n>\n").is_err());
+    }
+
+    #[test]
+    fn tokenize_quoting() {
+        assert_eq!(
+            tokenize(r#"NAME "My track" 'a "b"' `c 'd' "e"` x"#),
+            vec!["NAME", "My track", r#"a "b""#, r#"c 'd' "e""#, "x"]
+        );
+        assert_eq!(tokenize(r#"A "" B"#), vec!["A", "", "B"]);
+        assert_eq!(tokenize("  A   B  "), vec!["A", "B"]);
+        assert_eq!(tokenize(r#"A "unterminated"#), vec!["A", "unterminated"]);
+    }
+
+    #[test]
+    fn quote_tokens() {
+        assert_eq!(quote_token("abc"), "abc");
+        assert_eq!(quote_token(""), r#""""#);
+        assert_eq!(quote_token("a b"), r#""a b""#);
+        assert_eq!(quote_token(r#"a "b""#), r#"'a "b"'"#);
+        assert_eq!(quote_token(r#"a "b" 'c'"#), r#"`a "b" 'c'`"#);
+        assert_eq!(quote_token(r#"a "b" 'c' `d`"#), r#"`a "b" 'c' 'd'`"#);
+        assert_eq!(quote_token("'a"), r#""'a""#);
+    }
+
+    #[test]
+    fn round_trip() {
+        let node = RppNode::parse(TRACK_CHUNK).unwrap();
+        assert_eq!(node.to_string(), TRACK_CHUNK);
+        let crlf = TRACK_CHUNK.replace('\n', "\r\n");
+        assert_eq!(RppNode::parse(&crlf).unwrap().to_string(), crlf);
+        let without_final_newline = TRACK_CHUNK.trim_end();
+        assert_eq!(
+            RppNode::parse(without_final_newline).unwrap().to_string(),
+            without_final_newline
+        );
+    }
+
+    #[test]
+    fn structure() {
+        let node = RppNode::parse(TRACK_CHUNK).unwrap();
+        assert_eq!(node.tag, "TRACK");
+        assert_eq!(node.params, vec!["{8F9D2B6C-57A5-4B6B-A3C7-0B3C9B1A4C5E}"]);
+        assert_eq!(node.attribute("NAME").unwrap(), &["My track".to_string()]);
+        assert_eq!(
+            node.find_line("VOLPAN").unwrap().parse_value::<f64>(0),
+            Some(1.0)
+        );
+        let fx_chain = node.find_child("FXCHAIN").unwrap();
+        assert_eq!(fx_chain.lines().count(), 2);
+        let vst = fx_chain.find_child("VST").unwrap();
+        assert_eq!(vst.params[0], "VST: ReaEQ (Cockos)");
+        assert_eq!(vst.params[3], "");
+        assert!(vst.decode_base64_lines().is_ok());
+    }
+
+    #[test]
+    fn modification() {
+        let mut node = RppNode::parse(TRACK_CHUNK).unwrap();
+        node.set_attribute("NAME", vec!["Renamed 'track'".to_string()]);
+        node.set_attribute("NEW", vec!["1".to_string()]);
+        node.find_child_mut("FXCHAIN")
+            .unwrap()
+            .remove_attribute("SHOW");
+        let expected = TRACK_CHUNK
+            .replace(r#"NAME "My track""#, r#"NAME "Renamed 'track'""#)
+            .replace("    SHOW 0\n", "")
+            .replace(
+                "    FLOATPOS 0 0 0 0\n  >\n",
+                "    FLOATPOS 0 0 0 0\n  >\n  NEW 1\n",
+            );
+        assert_eq!(node.to_string(), expected);
+    }
+
+    #[test]
+    fn base64() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut node = RppNode::new("VST", vec![]);
+        node.set_base64_lines(&data);
+        assert_eq!(node.lines().count(), 3);
+        assert_eq!(node.decode_base64_lines().unwrap(), data);
+        let reparsed = RppNode::parse(&node.to_string()).unwrap();
+        assert_eq!(reparsed, node);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(RppNode::parse("").is_err());
+        assert!(RppNode::parse("NAME x\n").is_err());
+        assert!(RppNode::parse("<TRACK\n  NAME x\n").is_err());
+        assert!(RppNode::parse("<TRACK\n>\n<TRACK\n>\n").is_err());
+        assert!(RppNode::parse("<TRACK\n>\n\nNAME x\n").is_err());
+    }
+
+    #[test]
+    fn trailing_blank_lines_and_bom() {
+        // Given
+        let text = "\u{feff}<X\n  A 1\n>\n\n  \r\n";
+        // When
+        let node = RppNode::parse(text).unwrap();
+        // Then
+        assert_eq!(node.tag, "X");
+        assert_eq!(node.to_string(), "<X\n  A 1\n>\n");
+        assert_eq!(RppNode::parse("<X\n>\n\n").unwrap().to_string(), "<X\n>\n");
+        let items = parse_rpp_items("\u{feff}BYPASS 0 0 0\n").unwrap();
+        assert!(matches!(&items[0], RppItem::Line(l) if l.tokens[0] == "BYPASS"));
+    }
+}
@@ -0,0 +1,1108 @@
+//! Typed offline access to REAPER project files (`.RPP`).
+//!
+//! Everything in here works without REAPER running, e.g. on build servers. The typed structures
+//! only expose the most important properties. All other lines are preserved verbatim when writing
+//! the project back, and so are lines whose values didn't change.
+use crate::{RppItem, RppLine, RppNode};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::Path;
+
+const PROJECT_TAG: &str = "REAPER_PROJECT";
+const TRACK_TAG: &str = "TRACK";
+const ITEM_TAG: &str = "ITEM";
+const SOURCE_TAG: &str = "SOURCE";
+const TEMPO_ENVELOPE_TAG: &str = "TEMPOENVEX";
+const FX_TAGS: [&str; 7] = ["VST", "JS", "AU", "DX", "LV2", "CLAP", "VIDEO_EFFECT"];
+
+/// A REAPER project file.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppProject {
+    pub tempo: RppTempo,
+    pub markers: Vec<RppMarker>,
+    pub tempo_envelope: Option<RppEnvelope>,
+    pub tracks: Vec<RppTrack>,
+    node: RppNode,
+}
+
+/// The project tempo and time signature.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RppTempo {
+    pub bpm: f64,
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+/// A marker or region.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppMarker {
+    /// The displayed marker or region number.
+    pub id: u32,
+    pub position: f64,
+    /// `Some` if this is a region.
+    pub region_end: Option<f64>,
+    pub name: String,
+    /// Native color, 0 if the default color is used.
+    pub color: i32,
+    lines: Vec<RppLine>,
+}
+
+/// A track.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppTrack {
+    pub guid: Option<String>,
+    pub name: String,
+    pub peak_color: Option<i32>,
+    pub volume: f64,
+    pub pan: f64,
+    pub mute: bool,
+    pub solo: bool,
+    pub fx_chain: Option<RppFxChain>,
+    pub envelopes: Vec<RppEnvelope>,
+    pub items: Vec<RppMediaItem>,
+    node: RppNode,
+}
+
+/// A media item.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppMediaItem {
+    pub guid: Option<String>,
+    pub position: f64,
+    pub length: f64,
+    pub mute: bool,
+    pub selected: bool,
+    pub takes: Vec<RppTake>,
+    // Only contains the item-level lines, takes follow after them.
+    node: RppNode,
+}
+
+/// A take of a media item.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppTake {
+    pub guid: Option<String>,
+    pub name: String,
+    pub volume: f64,
+    pub start_offset: f64,
+    pub play_rate: f64,
+    /// The `SOURCE` block.
+    pub source: Option<RppNode>,
+    pub fx_chain: Option<RppFxChain>,
+    items: Vec<RppItem>,
+}
+
+/// An FX chain (track FX chain, take FX chain, ...).
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppFxChain {
+    pub fxs: Vec<RppFx>,
+    // Only contains the chain-level lines, FX follow after them.
+    node: RppNode,
+}
+
+/// An FX within an FX chain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppFx {
+    pub guid: Option<String>,
+    pub bypassed: bool,
+    pub offline: bool,
+    /// The plug-in block (e.g. `VST` or `JS`), which contains the plug-in state.
+    pub state: RppNode,
+    items: Vec<RppItem>,
+}
+
+/// An envelope (track envelope, take envelope, tempo envelope, ...).
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppEnvelope {
+    pub guid: Option<String>,
+    pub active: bool,
+    pub visible: bool,
+    pub armed: bool,
+    pub points: Vec<RppEnvelopePoint>,
+    node: RppNode,
+}
+
+/// A point of an envelope.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RppEnvelopePoint {
+    pub position: f64,
+    pub value: f64,
+    pub shape: i32,
+    line: RppLine,
+}
+
+impl RppProject {
+    /// Creates an empty project.
+    ///
+    /// REAPER uses defaults for everything that's not contained in the file.
+    pub fn new() -> RppProject {
+        let node = RppNode::new(
+            PROJECT_TAG,
+            vec!["0.1".to_string(), "6.0".to_string(), "0".to_string()],
+        );
+        RppProject::from_node(node).expect("couldn't create project")
+    }
+
+    /// Parses the given project file content.
+    pub fn parse(text: &str) -> Result<RppProject, &'static str> {
+        RppProject::from_node(RppNode::parse(text)?)
+    }
+
+    /// Reads the given project file.
+    pub fn load(path: &Path) -> io::Result<RppProject> {
+        let text = std::fs::read_to_string(path)?;
+        RppProject::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes this project to the given file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    /// Interprets the given `REAPER_PROJECT` block.
+    pub fn from_node(node: RppNode) -> Result<RppProject, &'static str> {
+        if node.tag != PROJECT_TAG {
+            return Err("not a REAPER project");
+        }
+        let default_tempo = RppTempo::default();
+        let tempo = RppTempo {
+            bpm: get_value(&node.items, "TEMPO", 0).unwrap_or(default_tempo.bpm),
+            numerator: get_value(&node.items, "TEMPO", 1).unwrap_or(default_tempo.numerator),
+            denominator: get_value(&node.items, "TEMPO", 2).unwrap_or(default_tempo.denominator),
+        };
+        let project = RppProject {
+            tempo,
+            markers: RppMarker::parse_lines(node.lines())?,
+            tempo_envelope: node
+                .find_child(TEMPO_ENVELOPE_TAG)
+                .cloned()
+                .map(RppEnvelope::from_node),
+            tracks: node
+                .children_named(TRACK_TAG)
+                .cloned()
+                .map(RppTrack::from_node)
+                .collect::<Result<_, _>>()?,
+            node,
+        };
+        Ok(project)
+    }
+
+    /// Returns the REAPER version which saved the project, e.g. `6.10/win64`.
+    pub fn reaper_version(&self) -> Option<&str> {
+        self.node.params.get(1).map(|p| p.as_str())
+    }
+
+    /// Returns the complete project block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        let items = &mut node.items;
+        if needs_line(items, "TEMPO", self.tempo != RppTempo::default()) {
+            set_value(items, "TEMPO", 0, &self.tempo.bpm);
+            set_value(items, "TEMPO", 1, &self.tempo.numerator);
+            set_value(items, "TEMPO", 2, &self.tempo.denominator);
+        }
+        let marker_lines = self
+            .markers
+            .iter()
+            .flat_map(|m| m.to_lines())
+            .map(RppItem::Line)
+            .collect();
+        replace_items(items, |i| is_line(i, "MARKER"), marker_lines);
+        let tempo_envelope = self
+            .tempo_envelope
+            .iter()
+            .map(|e| RppItem::Node(e.to_node()))
+            .collect();
+        replace_items(items, |i| is_node(i, TEMPO_ENVELOPE_TAG), tempo_envelope);
+        let tracks = self
+            .tracks
+            .iter()
+            .map(|t| RppItem::Node(t.to_node()))
+            .collect();
+        replace_items(items, |i| is_node(i, TRACK_TAG), tracks);
+        node
+    }
+}
+
+impl Default for RppProject {
+    fn default() -> Self {
+        RppProject::new()
+    }
+}
+
+impl Display for RppProject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_node().write_to(f)
+    }
+}
+
+impl Default for RppTempo {
+    /// Returns the tempo which REAPER assumes if the project doesn't contain any.
+    fn default() -> Self {
+        RppTempo {
+            bpm: 120.0,
+            numerator: 4,
+            denominator: 4,
+        }
+    }
+}
+
+impl RppMarker {
+    /// Creates a marker.
+    pub fn new_marker(id: u32, position: f64, name: impl Into<String>) -> RppMarker {
+        RppMarker {
+            id,
+            position,
+            region_end: None,
+            name: name.into(),
+            color: 0,
+            lines: vec![],
+        }
+    }
+
+    /// Creates a region.
+    pub fn new_region(id: u32, start: f64, end: f64, name: impl Into<String>) -> RppMarker {
+        RppMarker {
+            region_end: Some(end),
+            ..RppMarker::new_marker(id, start, name)
+        }
+    }
+
+    /// Returns whether this is a region.
+    pub fn is_region(&self) -> bool {
+        self.region_end.is_some()
+    }
+
+    // A region consists of two lines with the same ID, the first one for the start, the second one
+    // for the end.
+    fn parse_lines<'a>(
+        lines: impl Iterator<Item = &'a RppLine>,
+    ) -> Result<Vec<RppMarker>, &'static str> {
+        let mut markers: Vec<RppMarker> = vec![];
+        for line in lines.filter(|l| l.key() == Some("MARKER")) {
+            let id: u32 = line.parse_value(0).ok_or("invalid marker ID")?;
+            let position: f64 = line.parse_value(1).ok_or("invalid marker position")?;
+            let flags: i32 = line.parse_value(3).unwrap_or(0);
+            let is_region = flags & 1 != 0;
+            if let Some(last) = markers.last_mut() {
+                let waits_for_end = last.region_end.is_none() && last.lines.len() == 1;
+                if is_region && last.id == id && waits_for_end && last_is_region(last) {
+                    last.region_end = Some(position);
+                    last.lines.push(line.clone());
+                    continue;
+                }
+            }
+            markers.push(RppMarker {
+                id,
+                position,
+                region_end: None,
+                name: line.value(2).unwrap_or_default().to_string(),
+                color: line.parse_value(4).unwrap_or(0),
+                lines: vec![line.clone()],
+            });
+        }
+        Ok(markers)
+    }
+
+    fn to_lines(&self) -> Vec<RppLine> {
+        let mut start = self
+            .lines
+            .first()
+            .cloned()
+            .unwrap_or_else(|| RppLine::new(vec!["MARKER".to_string()]));
+        let old_flags: i32 = start.parse_value(3).unwrap_or(0);
+        let flags = (old_flags & !1) | self.is_region() as i32;
+        set_token(&mut start.tokens, 1, &self.id);
+        set_token(&mut start.tokens, 2, &self.position);
+        set_token(&mut start.tokens, 3, &self.name);
+        set_token(&mut start.tokens, 4, &flags);
+        set_token(&mut start.tokens, 5, &self.color);
+        let end_pos = match self.region_end {
+            None => return vec![start],
+            Some(p) => p,
+        };
+        let mut end = self.lines.get(1).cloned().unwrap_or_else(|| {
+            RppLine::new(vec![
+                "MARKER".to_string(),
+                "0".to_string(),
+                "0".to_string(),
+                "".to_string(),
+                "1".to_string(),
+            ])
+        });
+        set_token(&mut end.tokens, 1, &self.id);
+        set_token(&mut end.tokens, 2, &end_pos);
+        vec![start, end]
+    }
+}
+
+fn last_is_region(marker: &RppMarker) -> bool {
+    let flags: i32 = marker.lines[0].parse_value(3).unwrap_or(0);
+    flags & 1 != 0
+}
+
+impl RppTrack {
+    /// Creates an empty track with the given GUID (including braces).
+    pub fn new(guid: impl Into<String>) -> RppTrack {
+        let node = RppNode::new(TRACK_TAG, vec![guid.into()]);
+        RppTrack::from_node(node).expect("couldn't create track")
+    }
+
+    /// Interprets the given `TRACK` block.
+    pub fn from_node(node: RppNode) -> Result<RppTrack, &'static str> {
+        if node.tag != TRACK_TAG {
+            return Err("not a track");
+        }
+        let track = RppTrack {
+            guid: node.params.first().cloned(),
+            name: get_value(&node.items, "NAME", 0).unwrap_or_default(),
+            peak_color: get_value(&node.items, "PEAKCOL", 0),
+            volume: get_value(&node.items, "VOLPAN", 0).unwrap_or(1.0),
+            pan: get_value(&node.items, "VOLPAN", 1).unwrap_or(0.0),
+            mute: get_value(&node.items, "MUTESOLO", 0).unwrap_or(false),
+            solo: get_value(&node.items, "MUTESOLO", 1).unwrap_or(false),
+            fx_chain: node
+                .find_child("FXCHAIN")
+                .cloned()
+                .map(RppFxChain::from_node),
+            envelopes: node
+                .children()
+                .filter(|c| is_envelope_tag(&c.tag))
+                .cloned()
+                .map(RppEnvelope::from_node)
+                .collect(),
+            items: node
+                .children_named(ITEM_TAG)
+                .cloned()
+                .map(RppMediaItem::from_node)
+                .collect(),
+            node,
+        };
+        Ok(track)
+    }
+
+    /// Returns the complete track block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        if let Some(guid) = &self.guid {
+            set_token(&mut node.params, 0, guid);
+        }
+        let items = &mut node.items;
+        if needs_line(items, "NAME", !self.name.is_empty()) {
+            set_value(items, "NAME", 0, &self.name);
+        }
+        match self.peak_color {
+            None => remove_lines(items, "PEAKCOL"),
+            Some(c) => set_value(items, "PEAKCOL", 0, &c),
+        }
+        if needs_line(items, "VOLPAN", self.volume != 1.0 || self.pan != 0.0) {
+            set_value(items, "VOLPAN", 0, &self.volume);
+            set_value(items, "VOLPAN", 1, &self.pan);
+        }
+        if needs_line(items, "MUTESOLO", self.mute || self.solo) {
+            set_value(items, "MUTESOLO", 0, &self.mute);
+            set_value(items, "MUTESOLO", 1, &self.solo);
+        }
+        let fx_chain = self
+            .fx_chain
+            .iter()
+            .map(|c| RppItem::Node(c.to_node()))
+            .collect();
+        replace_items(items, |i| is_node(i, "FXCHAIN"), fx_chain);
+        let envelopes = self
+            .envelopes
+            .iter()
+            .map(|e| RppItem::Node(e.to_node()))
+            .collect();
+        replace_items(
+            items,
+            |i| matches!(i, RppItem::Node(n) if is_envelope_tag(&n.tag)),
+            envelopes,
+        );
+        let media_items = self
+            .items
+            .iter()
+            .map(|i| RppItem::Node(i.to_node()))
+            .collect();
+        replace_items(items, |i| is_node(i, ITEM_TAG), media_items);
+        node
+    }
+}
+
+impl RppMediaItem {
+    /// Creates an item without takes.
+    pub fn new(position: f64, length: f64) -> RppMediaItem {
+        let mut item = RppMediaItem::from_node(RppNode::new(ITEM_TAG, vec![]));
+        item.position = position;
+        item.length = length;
+        item
+    }
+
+    /// Interprets the given `ITEM` block.
+    pub fn from_node(mut node: RppNode) -> RppMediaItem {
+        // The properties of the first take follow directly after the item properties. All further
+        // takes are introduced by a `TAKE` line.
+        let first_take_index = node
+            .items
+            .iter()
+            .position(|i| is_line(i, "NAME") || is_line(i, "TAKE") || is_node(i, SOURCE_TAG));
+        let mut takes = vec![];
+        if let Some(index) = first_take_index {
+            let mut take_items: Vec<RppItem> = vec![];
+            for item in node.items.split_off(index) {
+                if is_line(&item, "TAKE") && !take_items.is_empty() {
+                    takes.push(RppTake::from_items(std::mem::take(&mut take_items)));
+                }
+                take_items.push(item);
+            }
+            takes.push(RppTake::from_items(take_items));
+        }
+        RppMediaItem {
+            guid: get_value(&node.items, "IGUID", 0),
+            position: get_value(&node.items, "POSITION", 0).unwrap_or(0.0),
+            length: get_value(&node.items, "LENGTH", 0).unwrap_or(0.0),
+            mute: get_value(&node.items, "MUTE", 0).unwrap_or(false),
+            selected: get_value(&node.items, "SEL", 0).unwrap_or(false),
+            takes,
+            node,
+        }
+    }
+
+    /// Returns the complete item block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        let items = &mut node.items;
+        if needs_line(items, "POSITION", self.position != 0.0) {
+            set_value(items, "POSITION", 0, &self.position);
+        }
+        if needs_line(items, "LENGTH", self.length != 0.0) {
+            set_value(items, "LENGTH", 0, &self.length);
+        }
+        if needs_line(items, "MUTE", self.mute) {
+            set_value(items, "MUTE", 0, &self.mute);
+        }
+        if needs_line(items, "SEL", self.selected) {
+            set_value(items, "SEL", 0, &self.selected);
+        }
+        if let Some(guid) = &self.guid {
+            set_value(items, "IGUID", 0, guid);
+        }
+        for (i, take) in self.takes.iter().enumerate() {
+            items.extend(take.to_items(i == 0));
+        }
+        node
+    }
+}
+
+impl RppTake {
+    /// Creates a take with the given name and source block.
+    pub fn new(name: impl Into<String>, source: Option<RppNode>) -> RppTake {
+        let mut take = RppTake::from_items(vec![]);
+        take.name = name.into();
+        take.source = source;
+        take
+    }
+
+    fn from_items(items: Vec<RppItem>) -> RppTake {
+        RppTake {
+            guid: get_value(&items, "GUID", 0),
+            name: get_value(&items, "NAME", 0).unwrap_or_default(),
+            volume: get_value(&items, "VOLPAN", 0).unwrap_or(1.0),
+            start_offset: get_value(&items, "SOFFS", 0).unwrap_or(0.0),
+            play_rate: get_value(&items, "PLAYRATE", 0).unwrap_or(1.0),
+            source: find_node(&items, SOURCE_TAG).cloned(),
+            fx_chain: find_node(&items, "TAKEFX")
+                .cloned()
+                .map(RppFxChain::from_node),
+            items,
+        }
+    }
+
+    /// Returns the type of the source, e.g. `WAVE` or `MIDI`.
+    pub fn source_type(&self) -> Option<&str> {
+        self.source.as_ref()?.params.first().map(|p| p.as_str())
+    }
+
+    /// Returns the media file of the source if it's file-based.
+    pub fn source_file(&self) -> Option<&str> {
+        self.source
+            .as_ref()?
+            .attribute("FILE")?
+            .first()
+            .map(|f| f.as_str())
+    }
+
+    fn to_items(&self, is_first: bool) -> Vec<RppItem> {
+        let mut items = self.items.clone();
+        // Only further takes are introduced by a `TAKE` line. The first take might have been a
+        // further one before the takes in front of it were removed.
+        let starts_with_take_line = items.first().map(|i| is_line(i, "TAKE")).unwrap_or(false);
+        if is_first && starts_with_take_line {
+            items.remove(0);
+        } else if !is_first && !starts_with_take_line {
+            items.insert(0, RppItem::Line(RppLine::new(vec!["TAKE".to_string()])));
+        }
+        if needs_line(&items, "NAME", !self.name.is_empty()) {
+            set_value(&mut items, "NAME", 0, &self.name);
+        }
+        if needs_line(&items, "VOLPAN", self.volume != 1.0) {
+            set_value(&mut items, "VOLPAN", 0, &self.volume);
+        }
+        if needs_line(&items, "SOFFS", self.start_offset != 0.0) {
+            set_value(&mut items, "SOFFS", 0, &self.start_offset);
+        }
+        if needs_line(&items, "PLAYRATE", self.play_rate != 1.0) {
+            set_value(&mut items, "PLAYRATE", 0, &self.play_rate);
+        }
+        if let Some(guid) = &self.guid {
+            set_value(&mut items, "GUID", 0, guid);
+        }
+        let source = self.source.iter().cloned().map(RppItem::Node).collect();
+        replace_items(&mut items, |i| is_node(i, SOURCE_TAG), source);
+        let fx_chain = self
+            .fx_chain
+            .iter()
+            .map(|c| RppItem::Node(c.to_node()))
+            .collect();
+        replace_items(&mut items, |i| is_node(i, "TAKEFX"), fx_chain);
+        items
+    }
+}
+
+impl RppFxChain {
+    /// Creates an empty FX chain with the given tag (e.g. `FXCHAIN` or `TAKEFX`).
+    pub fn new(tag: impl Into<String>) -> RppFxChain {
+        RppFxChain::from_node(RppNode::new(tag, vec![]))
+    }
+
+    /// Interprets the given FX chain block (e.g. `FXCHAIN` or `TAKEFX`).
+    pub fn from_node(mut node: RppNode) -> RppFxChain {
+        // Each FX consists of a `BYPASS` line, the plug-in block and further lines such as `FXID`.
+        let mut fxs = vec![];
+        let mut fx_items: Option<Vec<RppItem>> = None;
+        let mut chain_items = vec![];
+        for item in std::mem::take(&mut node.items) {
+            let starts_new_fx = match &item {
+                RppItem::Line(l) => l.key() == Some("BYPASS"),
+                RppItem::Node(n) => {
+                    is_fx_tag(&n.tag)
+                        && fx_items
+                            .as_ref()
+                            .map(|items| find_fx_state(items).is_some())
+                            .unwrap_or(true)
+                }
+            };
+            if starts_new_fx {
+                if let Some(items) = fx_items.take() {
+                    fxs.extend(RppFx::from_items(items));
+                }
+                fx_items = Some(vec![]);
+            }
+            match &mut fx_items {
+                Some(items) => items.push(item),
+                None => chain_items.push(item),
+            }
+        }
+        if let Some(items) = fx_items {
+            fxs.extend(RppFx::from_items(items));
+        }
+        node.items = chain_items;
+        RppFxChain { fxs, node }
+    }
+
+    /// Returns the complete FX chain block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        for fx in &self.fxs {
+            node.items.extend(fx.to_items());
+        }
+        node
+    }
+}
+
+impl RppFx {
+    /// Creates an FX from the given plug-in block (e.g. `VST` or `JS`).
+    pub fn new(state: RppNode) -> RppFx {
+        RppFx {
+            guid: None,
+            bypassed: false,
+            offline: false,
+            state: state.clone(),
+            items: vec![
+                RppItem::Line(RppLine::new(vec![
+                    "BYPASS".to_string(),
+                    "0".to_string(),
+                    "0".to_string(),
+                    "0".to_string(),
+                ])),
+                RppItem::Node(state),
+            ],
+        }
+    }
+
+    // Returns `None` if the items don't contain a plug-in block.
+    fn from_items(items: Vec<RppItem>) -> Option<RppFx> {
+        let fx = RppFx {
+            guid: get_value(&items, "FXID", 0),
+            bypassed: get_value(&items, "BYPASS", 0).unwrap_or(false),
+            offline: get_value(&items, "BYPASS", 1).unwrap_or(false),
+            state: find_fx_state(&items)?.clone(),
+            items,
+        };
+        Some(fx)
+    }
+
+    /// Returns the plug-in type, e.g. `VST` or `JS`.
+    pub fn kind(&self) -> &str {
+        &self.state.tag
+    }
+
+    /// Returns the plug-in name (VST, AU, ...) or path (JS).
+    pub fn name(&self) -> Option<&str> {
+        self.state.params.first().map(|p| p.as_str())
+    }
+
+    fn to_items(&self) -> Vec<RppItem> {
+        let mut items = self.items.clone();
+        if needs_line(&items, "BYPASS", self.bypassed || self.offline) {
+            set_value(&mut items, "BYPASS", 0, &self.bypassed);
+            set_value(&mut items, "BYPASS", 1, &self.offline);
+        }
+        if let Some(guid) = &self.guid {
+            set_value(&mut items, "FXID", 0, guid);
+        }
+        if let Some(index) = items
+            .iter()
+            .position(|i| matches!(i, RppItem::Node(n) if is_fx_tag(&n.tag)))
+        {
+            items[index] = RppItem::Node(self.state.clone());
+        }
+        items
+    }
+}
+
+impl RppEnvelope {
+    /// Creates an empty envelope block with the given tag (e.g. `VOLENV2`).
+    pub fn new(tag: impl Into<String>) -> RppEnvelope {
+        let mut envelope = RppEnvelope::from_node(RppNode::new(tag, vec![]));
+        envelope.active = true;
+        envelope.visible = true;
+        envelope
+    }
+
+    /// Interprets the given envelope block.
+    pub fn from_node(node: RppNode) -> RppEnvelope {
+        RppEnvelope {
+            guid: get_value(&node.items, "EGUID", 0),
+            active: get_value(&node.items, "ACT", 0).unwrap_or(false),
+            visible: get_value(&node.items, "VIS", 0).unwrap_or(false),
+            armed: get_value(&node.items, "ARM", 0).unwrap_or(false),
+            points: node
+                .lines()
+                .filter(|l| l.key() == Some("PT"))
+                .map(RppEnvelopePoint::from_line)
+                .collect(),
+            node,
+        }
+    }
+
+    /// Returns the tag of the envelope, e.g. `VOLENV2` or `PARMENV`.
+    pub fn tag(&self) -> &str {
+        &self.node.tag
+    }
+
+    /// Returns the complete envelope block including all changes.
+    pub fn to_node(&self) -> RppNode {
+        let mut node = self.node.clone();
+        let items = &mut node.items;
+        if let Some(guid) = &self.guid {
+            set_value(items, "EGUID", 0, guid);
+        }
+        if needs_line(items, "ACT", self.active) {
+            set_value(items, "ACT", 0, &self.active);
+        }
+        if needs_line(items, "VIS", self.visible) {
+            set_value(items, "VIS", 0, &self.visible);
+        }
+        if needs_line(items, "ARM", self.armed) {
+            set_value(items, "ARM", 0, &self.armed);
+        }
+        let points = self
+            .points
+            .iter()
+            .map(|p| RppItem::Line(p.to_line()))
+            .collect();
+        replace_items(items, |i| is_line(i, "PT"), points);
+        node
+    }
+}
+
+impl RppEnvelopePoint {
+    /// Creates an envelope point.
+    pub fn new(position: f64, value: f64, shape: i32) -> RppEnvelopePoint {
+        RppEnvelopePoint {
+            position,
+            value,
+            shape,
+            line: RppLine::new(vec!["PT".to_string()]),
+        }
+    }
+
+    fn from_line(line: &RppLine) -> RppEnvelopePoint {
+        RppEnvelopePoint {
+            position: line.parse_value(0).unwrap_or(0.0),
+            value: line.parse_value(1).unwrap_or(0.0),
+            shape: line.parse_value(2).unwrap_or(0),
+            line: line.clone(),
+        }
+    }
+
+    fn to_line(&self) -> RppLine {
+        let mut line = self.line.clone();
+        set_token(&mut line.tokens, 1, &self.position);
+        set_token(&mut line.tokens, 2, &self.value);
+        set_token(&mut line.tokens, 3, &self.shape);
+        line
+    }
+}
+
+/// A value which can be read from and written to an RPP token.
+trait RppValue: Sized + PartialEq {
+    fn from_token(token: &str) -> Option<Self>;
+
+    fn to_token(&self) -> String;
+}
+
+impl RppValue for f64 {
+    fn from_token(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+
+    fn to_token(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl RppValue for i32 {
+    fn from_token(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+
+    fn to_token(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl RppValue for u32 {
+    fn from_token(token: &str) -> Option<Self> {
+        token.parse().ok()
+    }
+
+    fn to_token(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl RppValue for bool {
+    fn from_token(token: &str) -> Option<Self> {
+        token.parse::<i32>().ok().map(|v| v != 0)
+    }
+
+    fn to_token(&self) -> String {
+        (*self as i32).to_string()
+    }
+}
+
+impl RppValue for String {
+    fn from_token(token: &str) -> Option<Self> {
+        Some(token.to_string())
+    }
+
+    fn to_token(&self) -> String {
+        self.clone()
+    }
+}
+
+fn get_value<T: RppValue>(items: &[RppItem], key: &str, index: usize) -> Option<T> {
+    let line = find_line(items, key)?;
+    T::from_token(line.value(index)?)
+}
+
+// Returns whether the given line must be written. A missing line is only added if the value
+// differs from the default which REAPER (and the parser) assumes for it. Otherwise writing an
+// unchanged project would add lines.
+fn needs_line(items: &[RppItem], key: &str, differs_from_default: bool) -> bool {
+    differs_from_default || find_line(items, key).is_some()
+}
+
+// Leaves the line untouched if it already contains an equal value, so we don't change formatting
+// unnecessarily (e.g. `1.000` vs. `1`).
+fn set_value<T: RppValue>(items: &mut Vec<RppItem>, key: &str, index: usize, value: &T) {
+    let line = items.iter_mut().find_map(|i| match i {
+        RppItem::Line(l) if l.key() == Some(key) => Some(l),
+        _ => None,
+    });
+    match line {
+        Some(l) => set_token(&mut l.tokens, index + 1, value),
+        None => {
+            let mut tokens = vec![key.to_string()];
+            set_token(&mut tokens, index + 1, value);
+            items.push(RppItem::Line(RppLine::new(tokens)));
+        }
+    }
+}
+
+fn set_token<T: RppValue>(tokens: &mut Vec<String>, index: usize, value: &T) {
+    if tokens.get(index).and_then(|t| T::from_token(t)).as_ref() == Some(value) {
+        return;
+    }
+    while tokens.len() <= index {
+        tokens.push("0".to_string());
+    }
+    tokens[index] = value.to_token();
+}
+
+fn remove_lines(items: &mut Vec<RppItem>, key: &str) {
+    items.retain(|i| !is_line(i, key));
+}
+
+// Replaces all matching items with the given ones, inserted at the position of the first matching
+// item (or at the end if there's none).
+fn replace_items(
+    items: &mut Vec<RppItem>,
+    is_match: impl Fn(&RppItem) -> bool,
+    new_items: Vec<RppItem>,
+) {
+    let index = items.iter().position(&is_match);
+    items.retain(|i| !is_match(i));
+    let index = index.unwrap_or(items.len());
+    items.splice(index..index, new_items);
+}
+
+fn find_line<'a>(items: &'a [RppItem], key: &str) -> Option<&'a RppLine> {
+    items.iter().find_map(|i| match i {
+        RppItem::Line(l) if l.key() == Some(key) => Some(l),
+        _ => None,
+    })
+}
+
+fn find_node<'a>(items: &'a [RppItem], tag: &str) -> Option<&'a RppNode> {
+    items.iter().find_map(|i| match i {
+        RppItem::Node(n) if n.tag == tag => Some(n),
+        _ => None,
+    })
+}
+
+fn find_fx_state(items: &[RppItem]) -> Option<&RppNode> {
+    items.iter().find_map(|i| match i {
+        RppItem::Node(n) if is_fx_tag(&n.tag) => Some(n),
+        _ => None,
+    })
+}
+
+fn is_line(item: &RppItem, key: &str) -> bool {
+    matches!(item, RppItem::Line(l) if l.key() == Some(key))
+}
+
+fn is_node(item: &RppItem, tag: &str) -> bool {
+    matches!(item, RppItem::Node(n) if n.tag == tag)
+}
+
+fn is_fx_tag(tag: &str) -> bool {
+    FX_TAGS.contains(&tag)
+}
+
+fn is_envelope_tag(tag: &str) -> bool {
+    tag.contains("ENV")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r#"<REAPER_PROJECT 0.1 "6.10/win64" 1591264325
+  RIPPLE 0
+  TEMPO 120 4 4
+  PLAYRATE 1 0 0.25 4
+  MARKER 1 0 Intro 0 0 1 B {A1B2C3D4-0000-0000-0000-000000000001} 0
+  MARKER 2 4.5 "Verse 1" 1 16576 1 R {A1B2C3D4-0000-0000-0000-000000000002} 0
+  MARKER 2 12 "" 1
+  <TEMPOENVEX
+    EGUID {A1B2C3D4-0000-0000-0000-000000000003}
+    ACT 0 -1
+    VIS 1 0 1
+    ARM 0
+    PT 0 120 0
+    PT 8 140 1 0 1
+  >
+  <TRACK {A1B2C3D4-0000-0000-0000-000000000004}
+    NAME Drums
+    PEAKCOL 16576
+    VOLPAN 0.5 -0.25 -1 -1 1
+    MUTESOLO 0 2 0
+    <FXCHAIN
+      SHOW 0
+      LASTSEL 0
+      BYPASS 0 0 0
+      <JS utility/volume ""
+        0.000000 0.000000 - - - - - - - - - - - - - - -
+      >
+      FLOATPOS 0 0 0 0
+      FXID {A1B2C3D4-0000-0000-0000-000000000005}
+      WAK 0 0
+      BYPASS 1 0 0
+      <VST "VST: ReaEQ (Cockos)" reaeq.dll 0 "" 1919247729<56535472656571726561657100000000> ""
+        cWVyde9e7f4CAAAAAQAAAAAAAAACAAAAAAAAAAIAAAABAAAAAAAAAAIAAAAAAAAAXAAAAAEAAAAAABAA
+      >
+      FXID {A1B2C3D4-0000-0000-0000-000000000006}
+      WAK 0 0
+    >
+    <VOLENV2
+      EGUID {A1B2C3D4-0000-0000-0000-000000000007}
+      ACT 1 -1
+      VIS 1 1 1
+      ARM 0
+      PT 0 1 0
+    >
+    <ITEM
+      POSITION 2
+      LENGTH 4.000000
+      MUTE 0 0
+      SEL 1
+      IGUID {A1B2C3D4-0000-0000-0000-000000000008}
+      NAME kick.wav
+      VOLPAN 1 0 1 -1
+      SOFFS 0
+      PLAYRATE 1 1 0 -1 0 0.0025
+      GUID {A1B2C3D4-0000-0000-0000-000000000009}
+      <SOURCE WAVE
+        FILE "samples/kick.wav"
+      >
+      TAKE SEL
+      NAME "kick 2.wav"
+      VOLPAN 0.8 0 1 -1
+      SOFFS 0.5
+      PLAYRATE 2 1 0 -1 0 0.0025
+      GUID {A1B2C3D4-0000-0000-0000-000000000010}
+      <SOURCE WAVE
+        FILE "samples/kick 2.wav"
+      >
+    >
+  >
+>
+"#;
+
+    #[test]
+    fn round_trip() {
+        let project = RppProject::parse(PROJECT).unwrap();
+        assert_eq!(project.to_string(), PROJECT);
+    }
+
+    #[test]
+    fn round_trip_minimal() {
+        // Given
+        let text = "<REAPER_PROJECT 0.1 \"6.10/win64\" 0\n  <TRACK\n    <ITEM\n      \
+                    <SOURCE EMPTY\n      >\n    >\n  >\n>\n";
+        // When
+        let project = RppProject::parse(text).unwrap();
+        // Then
+        assert_eq!(project.tempo, RppTempo::default());
+        assert_eq!(project.tracks[0].items[0].takes.len(), 1);
+        assert_eq!(project.to_string(), text);
+    }
+
+    #[test]
+    fn remove_first_take() {
+        // Given
+        let mut project = RppProject::parse(PROJECT).unwrap();
+        // When
+        project.tracks[0].items[0].takes.remove(0);
+        // Then
+        let reparsed = RppProject::parse(&project.to_string()).unwrap();
+        let item = &reparsed.tracks[0].items[0];
+        assert_eq!(item.takes.len(), 1);
+        assert_eq!(item.takes[0].name, "kick 2.wav");
+        assert!(!project.to_string().contains("TAKE"));
+    }
+
+    #[test]
+    fn read() {
+        let project = RppProject::parse(PROJECT).unwrap();
+        assert_eq!(project.reaper_version(), Some("6.10/win64"));
+        assert_eq!(project.tempo.bpm, 120.0);
+        assert_eq!(project.markers.len(), 2);
+        assert_eq!(project.markers[0].name, "Intro");
+        assert!(!project.markers[0].is_region());
+        assert_eq!(project.markers[1].name, "Verse 1");
+        assert_eq!(project.markers[1].region_end, Some(12.0));
+        assert_eq!(project.markers[1].color, 16576);
+        let tempo_envelope = project.tempo_envelope.as_ref().unwrap();
+        assert_eq!(tempo_envelope.points.len(), 2);
+        assert_eq!(tempo_envelope.points[1].value, 140.0);
+        let track = &project.tracks[0];
+        assert_eq!(track.name, "Drums");
+        assert_eq!(track.volume, 0.5);
+        assert_eq!(track.pan, -0.25);
+        assert!(track.solo);
+        assert!(!track.mute);
+        let fxs = &track.fx_chain.as_ref().unwrap().fxs;
+        assert_eq!(fxs.len(), 2);
+        assert_eq!(fxs[0].kind(), "JS");
+        assert_eq!(fxs[0].name(), Some("utility/volume"));
+        assert!(fxs[1].bypassed);
+        assert_eq!(fxs[1].name(), Some("VST: ReaEQ (Cockos)"));
+        assert_eq!(track.envelopes.len(), 1);
+        assert_eq!(track.envelopes[0].tag(), "VOLENV2");
+        let item = &track.items[0];
+        assert_eq!(item.position, 2.0);
+        assert_eq!(item.length, 4.0);
+        assert!(item.selected);
+        assert_eq!(item.takes.len(), 2);
+        assert_eq!(item.takes[0].name, "kick.wav");
+        assert_eq!(item.takes[1].name, "kick 2.wav");
+        assert_eq!(item.takes[1].play_rate, 2.0);
+        assert_eq!(item.takes[1].source_type(), Some("WAVE"));
+        assert_eq!(item.takes[1].source_file(), Some("samples/kick 2.wav"));
+    }
+
+    #[test]
+    fn modify() {
+        let mut project = RppProject::parse(PROJECT).unwrap();
+        project.tempo.bpm = 90.0;
+        project.markers[1].region_end = Some(16.0);
+        let track = &mut project.tracks[0];
+        track.name = "Drum Bus".to_string();
+        track.fx_chain.as_mut().unwrap().fxs.remove(0);
+        track.items[0].takes[1].name = "snare.wav".to_string();
+        let expected = PROJECT
+            .replace("TEMPO 120 4 4", "TEMPO 90 4 4")
+            .replace("MARKER 2 12 \"\" 1", "MARKER 2 16 \"\" 1")
+            .replace("NAME Drums", "NAME \"Drum Bus\"")
+            .replace("NAME \"kick 2.wav\"", "NAME snare.wav");
+        let js_start = expected.find("      BYPASS 0 0 0").unwrap();
+        let js_end = expected.find("      BYPASS 1 0 0").unwrap();
+        let expected = format!("{}{}", &expected[..js_start], &expected[js_end..]);
+        assert_eq!(project.to_string(), expected);
+    }
+
+    #[test]
+    fn create() {
+        let mut project = RppProject::new();
+        let mut track = RppTrack::new("{A1B2C3D4-0000-0000-0000-000000000011}");
+        track.name = "Generated".to_string();
+        let mut item = RppMediaItem::new(1.0, 2.0);
+        let mut source = RppNode::new("SOURCE", vec!["WAVE".to_string()]);
+        source.set_attribute("FILE", vec!["a.wav".to_string()]);
+        item.takes.push(RppTake::new("a", Some(source)));
+        track.items.push(item);
+        project.tracks.push(track);
+        project.markers.push(RppMarker::new_marker(1, 0.0, "Start"));
+        let reparsed = RppProject::parse(&project.to_string()).unwrap();
+        assert_eq!(reparsed.tracks[0].name, "Generated");
+        assert_eq!(reparsed.tracks[0].items[0].length, 2.0);
+        assert_eq!(
+            reparsed.tracks[0].items[0].takes[0].source_file(),
+            Some("a.wav")
+        );
+        assert_eq!(reparsed.markers[0].name, "Start");
+    }
+}
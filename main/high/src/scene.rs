@@ -0,0 +1,403 @@
+//! Mixer scenes which can be recalled instantly or crossfaded.
+use crate::{FxParameter, Pan, TaskSupport, Track, TrackRoute, Volume, Width};
+use reaper_medium::ReaperNormalizedFxParamValue;
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Something whose value can be stored in a scene.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SceneTarget {
+    TrackVolume(Track),
+    TrackPan(Track),
+    TrackWidth(Track),
+    RouteVolume(TrackRoute),
+    RoutePan(TrackRoute),
+    FxParameter(FxParameter),
+}
+
+/// A target together with its stored value.
+///
+/// The value is normalized as far as possible. For volumes it's the soft-normalized value, which
+/// can exceed 1.0.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SceneValue {
+    pub target: SceneTarget,
+    pub value: f64,
+}
+
+/// A named subset of mixer values.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MixerScene {
+    pub name: String,
+    pub values: Vec<SceneValue>,
+}
+
+/// A collection of scenes with unique names.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SceneBank {
+    scenes: Vec<MixerScene>,
+}
+
+/// Interpolates between two scenes.
+///
+/// Can be controlled like an FX parameter: A normalized value of 0.0 corresponds to the first
+/// scene, 1.0 to the second one. Only targets contained in both scenes are affected.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SceneMorph {
+    pairs: Vec<(SceneTarget, f64, f64)>,
+    position: Cell<f64>,
+}
+
+/// Handle of a running crossfade.
+#[derive(Clone, Debug)]
+pub struct SceneCrossfade {
+    state: Rc<CrossfadeState>,
+}
+
+#[derive(Debug)]
+struct CrossfadeState {
+    morph: SceneMorph,
+    start_position: f64,
+    cycle_count: u32,
+    current_cycle: Cell<u32>,
+    cancelled: Cell<bool>,
+}
+
+impl SceneTarget {
+    pub fn is_available(&self) -> bool {
+        use SceneTarget::*;
+        match self {
+            TrackVolume(t) | TrackPan(t) | TrackWidth(t) => t.is_available(),
+            RouteVolume(r) | RoutePan(r) => r.is_available(),
+            FxParameter(p) => p.is_available(),
+        }
+    }
+
+    pub fn current_value(&self) -> f64 {
+        use SceneTarget::*;
+        match self {
+            TrackVolume(t) => t.volume().soft_normalized_value(),
+            TrackPan(t) => t.pan().normalized_value(),
+            TrackWidth(t) => t.width().normalized_value(),
+            RouteVolume(r) => r.volume().soft_normalized_value(),
+            RoutePan(r) => r.pan().normalized_value(),
+            FxParameter(p) => p.reaper_normalized_value().get(),
+        }
+    }
+
+    pub fn set_value(&self, value: f64) -> Result<(), &'static str> {
+        if !self.is_available() {
+            return Err("scene target not available");
+        }
+        use SceneTarget::*;
+        match self {
+            TrackVolume(t) => t.set_volume(Volume::try_from_soft_normalized_value(value)?),
+            TrackPan(t) => t.set_pan(Pan::from_normalized_value(clamp_normalized(value))),
+            TrackWidth(t) => t.set_width(Width::from_normalized_value(clamp_normalized(value))),
+            RouteVolume(r) => r
+                .set_volume(Volume::try_from_soft_normalized_value(value)?)
+                .map_err(|_| "couldn't set route volume")?,
+            RoutePan(r) => r
+                .set_pan(Pan::from_normalized_value(clamp_normalized(value)))
+                .map_err(|_| "couldn't set route pan")?,
+            FxParameter(p) => p
+                .set_reaper_normalized_value(ReaperNormalizedFxParamValue::new(value))
+                .map_err(|_| "couldn't set FX parameter value")?,
+        };
+        Ok(())
+    }
+}
+
+impl MixerScene {
+    /// Creates a scene from the current values of the given targets.
+    pub fn capture(
+        name: impl Into<String>,
+        targets: impl IntoIterator<Item = SceneTarget>,
+    ) -> MixerScene {
+        MixerScene {
+            name: name.into(),
+            values: targets
+                .into_iter()
+                .map(|target| SceneValue {
+                    value: target.current_value(),
+                    target,
+                })
+                .collect(),
+        }
+    }
+
+    /// Applies all values of this scene immediately.
+    ///
+    /// Values whose targets are not available anymore are skipped, which results in an error.
+    pub fn recall(&self) -> Result<(), &'static str> {
+        let mut all_applied = true;
+        for v in &self.values {
+            if v.target.set_value(v.value).is_err() {
+                all_applied = false;
+            }
+        }
+        if all_applied {
+            Ok(())
+        } else {
+            Err("some scene values couldn't be applied")
+        }
+    }
+
+    fn value_of(&self, target: &SceneTarget) -> Option<f64> {
+        self.values
+            .iter()
+            .find(|v| &v.target == target)
+            .map(|v| v.value)
+    }
+}
+
+impl SceneBank {
+    /// Adds the given scene, replacing an existing one with the same name.
+    pub fn insert(&mut self, scene: MixerScene) {
+        match self.scenes.iter_mut().find(|s| s.name == scene.name) {
+            Some(existing) => *existing = scene,
+            None => self.scenes.push(scene),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<MixerScene> {
+        let index = self.scenes.iter().position(|s| s.name == name)?;
+        Some(self.scenes.remove(index))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MixerScene> {
+        self.scenes.iter().find(|s| s.name == name)
+    }
+
+    pub fn scenes(&self) -> impl Iterator<Item = &MixerScene> + ExactSizeIterator {
+        self.scenes.iter()
+    }
+
+    /// Recalls the scene with the given name immediately.
+    pub fn recall(&self, name: &str) -> Result<(), &'static str> {
+        self.get(name).ok_or("scene not found")?.recall()
+    }
+
+    /// Crossfades from the scene named `from` to the one named `to`.
+    ///
+    /// See [`SceneMorph::crossfade`](struct.SceneMorph.html#method.crossfade).
+    pub fn crossfade(
+        &self,
+        from: &str,
+        to: &str,
+        cycle_count: u32,
+        task_support: &'static TaskSupport,
+    ) -> Result<SceneCrossfade, &'static str> {
+        let from = self.get(from).ok_or("source scene not found")?;
+        let to = self.get(to).ok_or("destination scene not found")?;
+        SceneMorph::new(from, to).crossfade(cycle_count, task_support)
+    }
+}
+
+impl SceneMorph {
+    pub fn new(from: &MixerScene, to: &MixerScene) -> SceneMorph {
+        SceneMorph {
+            pairs: from
+                .values
+                .iter()
+                .filter_map(|v| {
+                    let to_value = to.value_of(&v.target)?;
+                    Some((v.target.clone(), v.value, to_value))
+                })
+                .collect(),
+            position: Cell::new(0.0),
+        }
+    }
+
+    pub fn reaper_normalized_value(&self) -> ReaperNormalizedFxParamValue {
+        ReaperNormalizedFxParamValue::new(self.position.get())
+    }
+
+    /// Applies the interpolated values of all targets.
+    ///
+    /// Values whose targets are not available anymore are skipped, which results in an error.
+    pub fn set_reaper_normalized_value(
+        &self,
+        reaper_value: impl Into<ReaperNormalizedFxParamValue>,
+    ) -> Result<(), &'static str> {
+        let position = clamp_normalized(reaper_value.into().get());
+        self.position.set(position);
+        let mut all_applied = true;
+        for (target, from_value, to_value) in &self.pairs {
+            let value = interpolate(*from_value, *to_value, position);
+            if target.set_value(value).is_err() {
+                all_applied = false;
+            }
+        }
+        if all_applied {
+            Ok(())
+        } else {
+            Err("some scene values couldn't be applied")
+        }
+    }
+
+    /// Moves from the current position to the second scene, one step per main loop cycle.
+    ///
+    /// The steps are executed by the `MainTaskMiddleware` that belongs to the given task support.
+    /// With a cycle count of 0, the second scene is applied immediately. Must be called in the
+    /// main thread.
+    pub fn crossfade(
+        self,
+        cycle_count: u32,
+        task_support: &'static TaskSupport,
+    ) -> Result<SceneCrossfade, &'static str> {
+        let state = Rc::new(CrossfadeState {
+            start_position: self.position.get(),
+            morph: self,
+            cycle_count,
+            current_cycle: Cell::new(0),
+            cancelled: Cell::new(false),
+        });
+        if cycle_count == 0 {
+            // Errors are of no use because the crossfade is finished anyway
+            let _ = state.morph.set_reaper_normalized_value(1.0);
+        } else {
+            schedule_crossfade_step(state.clone(), task_support)?;
+        }
+        Ok(SceneCrossfade { state })
+    }
+}
+
+impl SceneCrossfade {
+    /// Stops the crossfade at the current position.
+    pub fn cancel(&self) {
+        self.state.cancelled.set(true);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.state.cancelled.get() || self.state.current_cycle.get() >= self.state.cycle_count
+    }
+
+    pub fn position(&self) -> ReaperNormalizedFxParamValue {
+        self.state.morph.reaper_normalized_value()
+    }
+}
+
+fn schedule_crossfade_step(
+    state: Rc<CrossfadeState>,
+    task_support: &'static TaskSupport,
+) -> Result<(), &'static str> {
+    task_support.do_later_in_main_thread_from_main_thread_asap(move || {
+        if state.cancelled.get() {
+            return;
+        }
+        let cycle = state.current_cycle.get() + 1;
+        state.current_cycle.set(cycle);
+        let position = crossfade_position(state.start_position, cycle, state.cycle_count);
+        let _ = state.morph.set_reaper_normalized_value(position);
+        if cycle < state.cycle_count {
+            let _ = schedule_crossfade_step(state, task_support);
+        }
+    })
+}
+
+// Morph position after the given number of cycles of a crossfade towards the second scene.
+fn crossfade_position(start_position: f64, cycle: u32, cycle_count: u32) -> f64 {
+    let progress = cycle as f64 / cycle_count as f64;
+    interpolate(start_position, 1.0, progress)
+}
+
+fn interpolate(from_value: f64, to_value: f64, position: f64) -> f64 {
+    from_value + (to_value - from_value) * position
+}
+
+fn clamp_normalized(value: f64) -> f64 {
+    value.max(0.0).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_scenes() {
+        // Given
+        let mut bank = SceneBank::default();
+        // When
+        bank.insert(scene("A"));
+        bank.insert(scene("B"));
+        bank.insert(MixerScene {
+            name: "A".to_string(),
+            values: vec![],
+        });
+        // Then
+        assert_eq!(bank.scenes().len(), 2);
+        assert_eq!(
+            bank.scenes().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+            vec!["A", "B"]
+        );
+        assert_eq!(bank.get("B"), Some(&scene("B")));
+        assert_eq!(bank.get("C"), None);
+    }
+
+    #[test]
+    fn recall_scenes() {
+        // Given
+        let mut bank = SceneBank::default();
+        bank.insert(scene("A"));
+        // When
+        // Then
+        assert!(bank.recall("A").is_ok());
+        assert_eq!(bank.recall("B"), Err("scene not found"));
+    }
+
+    #[test]
+    fn remove_scenes() {
+        // Given
+        let mut bank = SceneBank::default();
+        bank.insert(scene("A"));
+        bank.insert(scene("B"));
+        // When
+        let removed = bank.remove("A");
+        // Then
+        assert_eq!(removed, Some(scene("A")));
+        assert_eq!(bank.remove("A"), None);
+        assert_eq!(bank.get("A"), None);
+        assert_eq!(bank.scenes().len(), 1);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn morph_position_is_clamped() {
+        // Given
+        let morph = SceneMorph::new(&scene("A"), &scene("B"));
+        // When
+        morph.set_reaper_normalized_value(1.5).unwrap();
+        // Then
+        assert_eq!(morph.reaper_normalized_value().get(), 1.0);
+        morph.set_reaper_normalized_value(-0.5).unwrap();
+        assert_eq!(morph.reaper_normalized_value().get(), 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn interpolation() {
+        assert_eq!(interpolate(0.25, 0.75, 0.0), 0.25);
+        assert_eq!(interpolate(0.25, 0.75, 0.5), 0.5);
+        assert_eq!(interpolate(0.25, 0.75, 1.0), 0.75);
+        assert_eq!(interpolate(0.75, 0.25, 0.25), 0.625);
+        // Soft-normalized volumes can exceed 1.0
+        assert_eq!(interpolate(1.0, 2.0, 0.5), 1.5);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn crossfade_positions() {
+        assert_eq!(crossfade_position(0.0, 1, 4), 0.25);
+        assert_eq!(crossfade_position(0.0, 4, 4), 1.0);
+        assert_eq!(crossfade_position(0.5, 1, 2), 0.75);
+        assert_eq!(crossfade_position(0.5, 2, 2), 1.0);
+    }
+
+    fn scene(name: &str) -> MixerScene {
+        MixerScene {
+            name: name.to_string(),
+            values: vec![],
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{FxChain, Reaper, ReaperSource, Track};
+use crate::{FxChain, Guid, Item, Project, Reaper, ReaperSource, Track};
 use reaper_medium::MediaItemTake;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -11,12 +11,51 @@ impl Take {
         Take { raw }
     }
 
+    pub fn raw(&self) -> MediaItemTake {
+        self.raw
+    }
+
+    /// Returns the project which contains this take, which is not necessarily the current one.
+    ///
+    /// Returns `None` if the take doesn't exist anymore.
+    pub fn project(&self) -> Option<Project> {
+        let reaper = Reaper::get();
+        reaper
+            .projects()
+            .find(|p| reaper.medium_reaper().validate_ptr_2(p.context(), self.raw))
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.project().is_some()
+    }
+
+    pub fn guid(&self) -> Guid {
+        let internal = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_set_media_item_take_info_get_guid(self.raw)
+        };
+        Guid::new(internal)
+    }
+
+    pub fn item(&self) -> Option<Item> {
+        let raw_item = unsafe {
+            Reaper::get()
+                .medium_reaper
+                .get_media_item_take_item(self.raw)?
+        };
+        Some(Item::new(raw_item))
+    }
+
     pub fn fx_chain(&self) -> FxChain {
         FxChain::from_take(*self)
     }
 
-    pub fn track(&self) -> &Track {
-        todo!()
+    /// Returns the track on which the item of this take is located.
+    ///
+    /// Returns `None` if the take doesn't exist anymore.
+    pub fn track(&self) -> Option<Track> {
+        self.item()?.track()
     }
 
     pub fn name(&self) -> String {
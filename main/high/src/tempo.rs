@@ -25,6 +25,24 @@ impl Tempo {
     }
 }
 
+// Serialized as BPM value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Tempo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.0.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Tempo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use std::convert::TryFrom;
+        let bpm = f64::deserialize(deserializer)?;
+        let bpm = Bpm::try_from(bpm).map_err(serde::de::Error::custom)?;
+        Ok(Tempo(bpm))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +64,17 @@ mod tests {
         // Then
         assert_eq!(tempo.bpm(), Bpm::new(480.5));
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        // Given
+        let tempo = Tempo::from_bpm(Bpm::new(120.0));
+        // When
+        let json = serde_json::to_string(&tempo).unwrap();
+        // Then
+        assert_eq!(json, "120.0");
+        assert_eq!(serde_json::from_str::<Tempo>(&json).unwrap(), tempo);
+        assert!(serde_json::from_str::<Tempo>("0.0").is_err());
+    }
 }
@@ -6,7 +6,8 @@ use crate::guid::Guid;
 use crate::track_route::TrackRoute;
 
 use crate::{
-    Chunk, ChunkRegion, Pan, Project, Reaper, SendPartnerType, TrackRoutePartner, Volume, Width,
+    Chunk, ChunkRegion, Envelope, Pan, Project, Reaper, SendPartnerType, TrackRoutePartner, Volume,
+    Width,
 };
 
 use reaper_medium::NotificationBehavior::NotifyAll;
@@ -15,13 +16,15 @@ use reaper_medium::SendTarget::OtherTrack;
 use reaper_medium::TrackAttributeKey::{RecArm, RecInput, RecMon, Selected, Solo};
 use reaper_medium::ValueChange::Absolute;
 use reaper_medium::{
-    AutomationMode, ChunkCacheHint, GangBehavior, GlobalAutomationModeOverride,
+    AutomationMode, ChunkCacheHint, EnvChunkName, GangBehavior, GlobalAutomationModeOverride,
     InputMonitoringMode, MediaTrack, ReaProject, ReaperString, ReaperStringArg, RecordArmMode,
     RecordingInput, SoloMode, TrackArea, TrackAttributeKey, TrackLocation, TrackSendCategory,
     TrackSendDirection,
 };
 use std::convert::TryInto;
+use std::fs;
 use std::hash::{Hash, Hasher};
+use std::path::Path;
 
 pub const MAX_TRACK_CHUNK_SIZE: u32 = 20_000_000;
 
@@ -678,6 +681,15 @@ impl Track {
         Ok(())
     }
 
+    /// Saves this track to the given file in REAPER's track template format (`.RTrackTemplate`).
+    ///
+    /// Relative paths are resolved against REAPER's resource path.
+    pub fn save_as_template(&self, path: impl AsRef<Path>) -> Result<(), &'static str> {
+        let chunk = self.chunk(MAX_TRACK_CHUNK_SIZE, ChunkCacheHint::NormalMode)?;
+        fs::write(Reaper::get().resolve_resource_path(path), chunk.to_string())
+            .map_err(|_| "couldn't write track template file")
+    }
+
     #[allow(clippy::float_cmp)]
     pub fn is_selected(&self) -> bool {
         self.load_and_check_if_necessary_or_complain();
@@ -996,6 +1008,16 @@ impl Track {
         FxChain::from_track(self.clone(), true)
     }
 
+    pub fn envelope_by_chunk_name(&self, chunk_name: EnvChunkName) -> Option<Envelope> {
+        self.load_and_check_if_necessary_or_complain();
+        let raw = unsafe {
+            Reaper::get()
+                .medium_reaper()
+                .get_track_envelope_by_chunk_name(self.raw(), chunk_name)?
+        };
+        Some(Envelope::new(raw))
+    }
+
     pub fn is_master_track(&self) -> bool {
         self.load_and_check_if_necessary_or_complain();
         let t = unsafe {
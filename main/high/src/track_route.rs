@@ -7,7 +7,7 @@ use reaper_medium::{
 use std::fmt;
 use TrackSendDirection::*;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TrackRoute {
     direction: TrackSendDirection,
     track: Track,
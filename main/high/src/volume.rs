@@ -4,7 +4,7 @@ use std::convert::TryFrom;
 use std::fmt;
 
 /// TODO-medium This struct needs an overhaul, not ready for prime time at all.
-#[derive(Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Volume {
     soft_normalized_value: f64,
 }
@@ -82,3 +82,37 @@ impl fmt::Display for Volume {
         write!(f, "{}", vol_string)
     }
 }
+
+// Serialized as soft-normalized value because converting from and to dB requires REAPER.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Volume {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.soft_normalized_value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Volume {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let soft_normalized_value = f64::deserialize(deserializer)?;
+        Volume::try_from_soft_normalized_value(soft_normalized_value)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        // Given
+        let volume = Volume::try_from_soft_normalized_value(0.5).unwrap();
+        // When
+        let json = serde_json::to_string(&volume).unwrap();
+        // Then
+        assert_eq!(json, "0.5");
+        assert_eq!(serde_json::from_str::<Volume>(&json).unwrap(), volume);
+        assert!(serde_json::from_str::<Volume>("-0.5").is_err());
+    }
+}
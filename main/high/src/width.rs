@@ -1,5 +1,6 @@
 use reaper_medium::ReaperWidthValue;
 
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Width {
     normalized_value: f64,
 }
@@ -22,3 +23,41 @@ impl Width {
         ReaperWidthValue::new(self.normalized_value * 2.0 - 1.0)
     }
 }
+
+// Serialized as REAPER width value (from -1.0 to 1.0).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Width {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.reaper_value().get())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Width {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = f64::deserialize(deserializer)?;
+        if !(-1.0..=1.0).contains(&value) {
+            return Err(serde::de::Error::custom(
+                "width value must be between -1.0 and 1.0",
+            ));
+        }
+        Ok(Width::from_normalized_value((value + 1.0) / 2.0))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        // Given
+        let width = Width::from_normalized_value(1.0);
+        // When
+        let json = serde_json::to_string(&width).unwrap();
+        // Then
+        assert_eq!(json, "1.0");
+        assert_eq!(serde_json::from_str::<Width>(&json).unwrap(), width);
+        assert!(serde_json::from_str::<Width>("-1.5").is_err());
+    }
+}
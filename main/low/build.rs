@@ -46,7 +46,10 @@ fn compile_glue_code() {
         .warnings(false)
         .file("src/control_surface.cpp")
         .file("src/pcm_source.cpp")
-        .file("src/midi.cpp");
+        .file("src/midi.cpp")
+        .file("src/peaks.cpp")
+        .file("src/media_decoder.cpp")
+        .file("src/resample.cpp");
     if cfg!(target_os = "macos") {
         build.cpp_set_stdlib("c++");
     }
@@ -123,6 +126,8 @@ mod codegen {
                 .whitelist_var("PCM_SOURCE_EXT_.*")
                 .whitelist_var("REAPER_PLUGIN_VERSION")
                 .whitelist_var("UNDO_STATE_.*")
+                .whitelist_var("RESAMPLE_EXT_.*")
+                .whitelist_var("REAPER_PITCHSHIFT_API_VER")
                 .whitelist_var("VK_.*")
                 .whitelist_var("BM_.*")
                 .whitelist_var("BST_.*")
@@ -154,6 +159,7 @@ mod codegen {
                 .whitelist_type("HINSTANCE")
                 .whitelist_type("reaper_plugin_info_t")
                 .whitelist_type("gaccel_register_t")
+                .whitelist_type("pcmsrc_register_t")
                 .whitelist_type("audio_hook_register_t")
                 .whitelist_type("KbdSectionInfo")
                 .whitelist_type("GUID")
@@ -162,6 +168,9 @@ mod codegen {
                 .whitelist_function("reaper_control_surface::.*")
                 .whitelist_function("reaper_midi::.*")
                 .whitelist_function("reaper_pcm_source::.*")
+                .whitelist_function("reaper_peaks::.*")
+                .whitelist_function("reaper_media_decoder::.*")
+                .whitelist_function("reaper_resample::.*")
                 .blacklist_type("preview_register_t");
             #[cfg(target_os = "macos")]
             let builder = builder.clang_arg("-stdlib=libc++");
@@ -410,8 +410,13 @@ pub mod root {
     pub const UNDO_STATE_ITEMS: u32 = 4;
     pub const UNDO_STATE_MISCCFG: u32 = 8;
     pub const UNDO_STATE_FREEZE: u32 = 16;
+    pub const RESAMPLE_EXT_SETRSMODE: u32 = 4096;
+    pub const RESAMPLE_EXT_SETFEEDMODE: u32 = 4097;
+    pub const RESAMPLE_EXT_RESETWITHFRACPOS: u32 = 24576;
+    pub const REAPER_PITCHSHIFT_API_VER: u32 = 20;
     pub type ReaSample = f64;
     pub type __intptr_t = ::std::os::raw::c_long;
+    pub type INT64 = ::std::os::raw::c_longlong;
     #[repr(C)]
     #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
     pub struct __pthread_internal_list {
@@ -999,6 +1004,28 @@ pub mod root {
         }
     }
     #[repr(C)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+    pub struct pcmsrc_register_t {
+        pub CreateFromType: ::std::option::Option<
+            unsafe extern "C" fn(
+                type_: *const ::std::os::raw::c_char,
+                priority: ::std::os::raw::c_int,
+            ) -> *mut root::PCM_source,
+        >,
+        pub CreateFromFile: ::std::option::Option<
+            unsafe extern "C" fn(
+                filename: *const ::std::os::raw::c_char,
+                priority: ::std::os::raw::c_int,
+            ) -> *mut root::PCM_source,
+        >,
+        pub EnumFileExtensions: ::std::option::Option<
+            unsafe extern "C" fn(
+                i: ::std::os::raw::c_int,
+                descptr: *mut *const ::std::os::raw::c_char,
+            ) -> *const ::std::os::raw::c_char,
+        >,
+    }
+    #[repr(C)]
     pub struct ISimpleMediaDecoder__bindgen_vtable(::std::os::raw::c_void);
     #[repr(C)]
     #[derive(Debug, Hash, PartialEq, Eq)]
@@ -11074,6 +11101,20 @@ pub mod root {
                 evt: *mut root::MIDI_event_t,
             );
         }
+        extern "C" {
+            pub fn MIDI_eventlist_DeleteItem(
+                self_: *mut root::MIDI_eventlist,
+                bpos: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn MIDI_eventlist_GetSize(
+                self_: *mut root::MIDI_eventlist,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn MIDI_eventlist_Empty(self_: *mut root::MIDI_eventlist);
+        }
         extern "C" {
             pub fn midi_Input_GetReadBuf(self_: *mut root::midi_Input)
             -> *mut root::MIDI_eventlist;
@@ -11095,6 +11136,296 @@ pub mod root {
             );
         }
     }
+    pub mod reaper_resample {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        extern "C" {
+            pub fn REAPER_Resample_Interface_SetRates(
+                self_: *mut root::REAPER_Resample_Interface,
+                rate_in: f64,
+                rate_out: f64,
+            );
+        }
+        extern "C" {
+            pub fn REAPER_Resample_Interface_Reset(self_: *mut root::REAPER_Resample_Interface);
+        }
+        extern "C" {
+            pub fn REAPER_Resample_Interface_GetCurrentLatency(
+                self_: *mut root::REAPER_Resample_Interface,
+            ) -> f64;
+        }
+        extern "C" {
+            pub fn REAPER_Resample_Interface_ResamplePrepare(
+                self_: *mut root::REAPER_Resample_Interface,
+                out_samples: ::std::os::raw::c_int,
+                nch: ::std::os::raw::c_int,
+                inbuffer: *mut *mut root::ReaSample,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn REAPER_Resample_Interface_ResampleOut(
+                self_: *mut root::REAPER_Resample_Interface,
+                out: *mut root::ReaSample,
+                nsamples_in: ::std::os::raw::c_int,
+                nsamples_out: ::std::os::raw::c_int,
+                nch: ::std::os::raw::c_int,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn REAPER_Resample_Interface_Extended(
+                self_: *mut root::REAPER_Resample_Interface,
+                call: ::std::os::raw::c_int,
+                parm1: *mut ::std::os::raw::c_void,
+                parm2: *mut ::std::os::raw::c_void,
+                parm3: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn delete_resample_interface(self_: *mut root::REAPER_Resample_Interface);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_set_srate(self_: *mut root::IReaperPitchShift, srate: f64);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_set_nch(
+                self_: *mut root::IReaperPitchShift,
+                nch: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_set_shift(self_: *mut root::IReaperPitchShift, shift: f64);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_set_formant_shift(
+                self_: *mut root::IReaperPitchShift,
+                shift: f64,
+            );
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_set_tempo(self_: *mut root::IReaperPitchShift, tempo: f64);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_Reset(self_: *mut root::IReaperPitchShift);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_GetBuffer(
+                self_: *mut root::IReaperPitchShift,
+                size: ::std::os::raw::c_int,
+            ) -> *mut root::ReaSample;
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_BufferDone(
+                self_: *mut root::IReaperPitchShift,
+                input_filled: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_FlushSamples(self_: *mut root::IReaperPitchShift);
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_IsReset(self_: *mut root::IReaperPitchShift) -> bool;
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_GetSamples(
+                self_: *mut root::IReaperPitchShift,
+                requested_output: ::std::os::raw::c_int,
+                buffer: *mut root::ReaSample,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_SetQualityParameter(
+                self_: *mut root::IReaperPitchShift,
+                parm: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn IReaperPitchShift_Extended(
+                self_: *mut root::IReaperPitchShift,
+                call: ::std::os::raw::c_int,
+                parm1: *mut ::std::os::raw::c_void,
+                parm2: *mut ::std::os::raw::c_void,
+                parm3: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn delete_pitch_shift(self_: *mut root::IReaperPitchShift);
+        }
+    }
+    pub mod reaper_peaks {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        extern "C" {
+            pub fn REAPER_PeakGet_Interface_GetMaxPeakRes(
+                self_: *mut root::REAPER_PeakGet_Interface,
+            ) -> f64;
+        }
+        extern "C" {
+            pub fn REAPER_PeakGet_Interface_GetPeakInfo(
+                self_: *mut root::REAPER_PeakGet_Interface,
+                block: *mut root::PCM_source_peaktransfer_t,
+            );
+        }
+        extern "C" {
+            pub fn REAPER_PeakGet_Interface_Extended(
+                self_: *mut root::REAPER_PeakGet_Interface,
+                call: ::std::os::raw::c_int,
+                parm1: *mut ::std::os::raw::c_void,
+                parm2: *mut ::std::os::raw::c_void,
+                parm3: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn delete_peak_get_interface(self_: *mut root::REAPER_PeakGet_Interface);
+        }
+        extern "C" {
+            pub fn REAPER_PeakBuild_Interface_ProcessSamples(
+                self_: *mut root::REAPER_PeakBuild_Interface,
+                samples: *mut *mut root::ReaSample,
+                len: ::std::os::raw::c_int,
+                nch: ::std::os::raw::c_int,
+                offs: ::std::os::raw::c_int,
+                spread: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn REAPER_PeakBuild_Interface_Run(
+                self_: *mut root::REAPER_PeakBuild_Interface,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn REAPER_PeakBuild_Interface_GetLastSecondPeaks(
+                self_: *mut root::REAPER_PeakBuild_Interface,
+                sz: ::std::os::raw::c_int,
+                buf: *mut root::ReaSample,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn REAPER_PeakBuild_Interface_GetPeakInfo(
+                self_: *mut root::REAPER_PeakBuild_Interface,
+                block: *mut root::PCM_source_peaktransfer_t,
+            );
+        }
+        extern "C" {
+            pub fn REAPER_PeakBuild_Interface_Extended(
+                self_: *mut root::REAPER_PeakBuild_Interface,
+                call: ::std::os::raw::c_int,
+                parm1: *mut ::std::os::raw::c_void,
+                parm2: *mut ::std::os::raw::c_void,
+                parm3: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn delete_peak_build_interface(self_: *mut root::REAPER_PeakBuild_Interface);
+        }
+    }
+    pub mod reaper_media_decoder {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        extern "C" {
+            pub fn create_cpp_to_rust_media_decoder(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> *mut root::ISimpleMediaDecoder;
+        }
+        extern "C" {
+            pub fn delete_media_decoder(decoder: *mut root::ISimpleMediaDecoder);
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_Destroy(
+                callback_target: *mut ::std::os::raw::c_void,
+            );
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_Duplicate(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> *mut root::ISimpleMediaDecoder;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_Open(
+                callback_target: *mut ::std::os::raw::c_void,
+                filename: *const ::std::os::raw::c_char,
+                diskreadmode: ::std::os::raw::c_int,
+                diskreadbs: ::std::os::raw::c_int,
+                diskreadnb: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_Close(
+                callback_target: *mut ::std::os::raw::c_void,
+                fullClose: bool,
+            );
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetFileName(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> *const ::std::os::raw::c_char;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetType(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> *const ::std::os::raw::c_char;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetInfoString(
+                callback_target: *mut ::std::os::raw::c_void,
+                buf: *mut ::std::os::raw::c_char,
+                buflen: ::std::os::raw::c_int,
+                title: *mut ::std::os::raw::c_char,
+                titlelen: ::std::os::raw::c_int,
+            );
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_IsOpen(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> bool;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetNumChannels(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetBitsPerSample(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetSampleRate(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> f64;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetLength(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> root::INT64;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_GetPosition(
+                callback_target: *mut ::std::os::raw::c_void,
+            ) -> root::INT64;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_SetPosition(
+                callback_target: *mut ::std::os::raw::c_void,
+                pos: root::INT64,
+            );
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_ReadSamples(
+                callback_target: *mut ::std::os::raw::c_void,
+                buf: *mut root::ReaSample,
+                length: ::std::os::raw::c_int,
+            ) -> ::std::os::raw::c_int;
+        }
+        extern "C" {
+            pub fn cpp_to_rust_ISimpleMediaDecoder_Extended(
+                callback_target: *mut ::std::os::raw::c_void,
+                call: ::std::os::raw::c_int,
+                parm1: *mut ::std::os::raw::c_void,
+                parm2: *mut ::std::os::raw::c_void,
+                parm3: *mut ::std::os::raw::c_void,
+            ) -> ::std::os::raw::c_int;
+        }
+    }
     pub mod reaper_pcm_source {
         #[allow(unused_imports)]
         use self::super::super::root;
@@ -96,3 +96,12 @@ pub use midi::*;
 
 mod pcm_source;
 pub use pcm_source::*;
+
+mod peaks;
+pub use peaks::*;
+
+mod media_decoder;
+pub use media_decoder::*;
+
+mod resample;
+pub use resample::*;
@@ -0,0 +1,73 @@
+#include "media_decoder.hpp"
+
+namespace reaper_media_decoder {
+  // C++ -> Rust
+
+  // This decoder just delegates to the free functions implemented in Rust. See header file for an explanation.
+  class CppToRustMediaDecoder : public ISimpleMediaDecoder {
+  private:
+    // This pointer points to a Box in Rust which holds an ISimpleMediaDecoder trait implementation.
+    void* callback_target_;
+  public:
+    CppToRustMediaDecoder(void* callback_target) : callback_target_(callback_target) {
+    }
+
+    virtual ~CppToRustMediaDecoder() {
+      ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_Destroy(this->callback_target_);
+    }
+
+    virtual ISimpleMediaDecoder* Duplicate() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_Duplicate(this->callback_target_);
+    }
+    virtual void Open(const char* filename, int diskreadmode, int diskreadbs, int diskreadnb) {
+      ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_Open(this->callback_target_, filename, diskreadmode, diskreadbs, diskreadnb);
+    }
+    virtual void Close(bool fullClose) {
+      ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_Close(this->callback_target_, fullClose);
+    }
+    virtual const char* GetFileName() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetFileName(this->callback_target_);
+    }
+    virtual const char* GetType() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetType(this->callback_target_);
+    }
+    virtual void GetInfoString(char* buf, int buflen, char* title, int titlelen) {
+      ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetInfoString(this->callback_target_, buf, buflen, title, titlelen);
+    }
+    virtual bool IsOpen() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_IsOpen(this->callback_target_);
+    }
+    virtual int GetNumChannels() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetNumChannels(this->callback_target_);
+    }
+    virtual int GetBitsPerSample() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetBitsPerSample(this->callback_target_);
+    }
+    virtual double GetSampleRate() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetSampleRate(this->callback_target_);
+    }
+    virtual INT64 GetLength() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetLength(this->callback_target_);
+    }
+    virtual INT64 GetPosition() {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_GetPosition(this->callback_target_);
+    }
+    virtual void SetPosition(INT64 pos) {
+      ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_SetPosition(this->callback_target_, pos);
+    }
+    virtual int ReadSamples(ReaSample* buf, int length) {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_ReadSamples(this->callback_target_, buf, length);
+    }
+    virtual int Extended(int call, void* parm1, void* parm2, void* parm3) {
+      return ::reaper_media_decoder::cpp_to_rust_ISimpleMediaDecoder_Extended(this->callback_target_, call, parm1, parm2, parm3);
+    }
+  };
+
+  ISimpleMediaDecoder* create_cpp_to_rust_media_decoder(void* callback_target) {
+    return new CppToRustMediaDecoder(callback_target);
+  }
+
+  void delete_media_decoder(ISimpleMediaDecoder* decoder) {
+    delete decoder;
+  }
+}
\ No newline at end of file
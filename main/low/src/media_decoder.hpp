@@ -0,0 +1,31 @@
+#pragma once
+
+#include "../lib/reaper/reaper_plugin.h"
+
+namespace reaper_media_decoder {
+  // This function is called from Rust and implemented in C++. It instantiates a C++ ISimpleMediaDecoder and returns
+  // its address to Rust. The returned decoder takes ownership of the given callback target and notifies Rust when it
+  // gets destroyed (REAPER destroys decoders itself when they are not needed anymore).
+  extern "C" ISimpleMediaDecoder* create_cpp_to_rust_media_decoder(void* callback_target);
+
+  // This function is called from Rust and implemented in C++. It destroys the given C++ ISimpleMediaDecoder object.
+  extern "C" void delete_media_decoder(ISimpleMediaDecoder* decoder);
+
+  // All of the following functions are called from C++ and implemented in Rust.
+  extern "C" void                 cpp_to_rust_ISimpleMediaDecoder_Destroy(void* callback_target);
+  extern "C" ISimpleMediaDecoder* cpp_to_rust_ISimpleMediaDecoder_Duplicate(void* callback_target);
+  extern "C" void                 cpp_to_rust_ISimpleMediaDecoder_Open(void* callback_target, const char* filename, int diskreadmode, int diskreadbs, int diskreadnb);
+  extern "C" void                 cpp_to_rust_ISimpleMediaDecoder_Close(void* callback_target, bool fullClose);
+  extern "C" const char*          cpp_to_rust_ISimpleMediaDecoder_GetFileName(void* callback_target);
+  extern "C" const char*          cpp_to_rust_ISimpleMediaDecoder_GetType(void* callback_target);
+  extern "C" void                 cpp_to_rust_ISimpleMediaDecoder_GetInfoString(void* callback_target, char* buf, int buflen, char* title, int titlelen);
+  extern "C" bool                 cpp_to_rust_ISimpleMediaDecoder_IsOpen(void* callback_target);
+  extern "C" int                  cpp_to_rust_ISimpleMediaDecoder_GetNumChannels(void* callback_target);
+  extern "C" int                  cpp_to_rust_ISimpleMediaDecoder_GetBitsPerSample(void* callback_target);
+  extern "C" double               cpp_to_rust_ISimpleMediaDecoder_GetSampleRate(void* callback_target);
+  extern "C" INT64                cpp_to_rust_ISimpleMediaDecoder_GetLength(void* callback_target);
+  extern "C" INT64                cpp_to_rust_ISimpleMediaDecoder_GetPosition(void* callback_target);
+  extern "C" void                 cpp_to_rust_ISimpleMediaDecoder_SetPosition(void* callback_target, INT64 pos);
+  extern "C" int                  cpp_to_rust_ISimpleMediaDecoder_ReadSamples(void* callback_target, ReaSample* buf, int length);
+  extern "C" int                  cpp_to_rust_ISimpleMediaDecoder_Extended(void* callback_target, int call, void* parm1, void* parm2, void* parm3);
+}
\ No newline at end of file
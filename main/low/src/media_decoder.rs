@@ -0,0 +1,228 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+use crate::{firewall, raw};
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr::{null, null_mut, NonNull};
+
+/// This is the Rust analog to the C++ virtual base class `ISimpleMediaDecoder`.
+///
+/// An implementation of this trait can be passed to [`create_cpp_to_rust_media_decoder()`].
+///
+/// [`create_cpp_to_rust_media_decoder()`]: fn.create_cpp_to_rust_media_decoder.html
+pub trait ISimpleMediaDecoder {
+    fn Duplicate(&mut self) -> *mut raw::ISimpleMediaDecoder;
+
+    fn Open(
+        &mut self,
+        filename: *const c_char,
+        diskreadmode: c_int,
+        diskreadbs: c_int,
+        diskreadnb: c_int,
+    );
+
+    fn Close(&mut self, fullClose: bool);
+
+    fn GetFileName(&mut self) -> *const c_char;
+    fn GetType(&mut self) -> *const c_char;
+
+    fn GetInfoString(
+        &mut self,
+        buf: *mut c_char,
+        buflen: c_int,
+        title: *mut c_char,
+        titlelen: c_int,
+    );
+
+    fn IsOpen(&mut self) -> bool;
+    fn GetNumChannels(&mut self) -> c_int;
+
+    fn GetBitsPerSample(&mut self) -> c_int;
+    fn GetSampleRate(&mut self) -> f64;
+
+    fn GetLength(&mut self) -> raw::INT64;
+    fn GetPosition(&mut self) -> raw::INT64;
+    fn SetPosition(&mut self, pos: raw::INT64);
+
+    fn ReadSamples(&mut self, buf: *mut raw::ReaSample, length: c_int) -> c_int;
+
+    fn Extended(
+        &mut self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        let _ = call;
+        let _ = parm1;
+        let _ = parm2;
+        let _ = parm3;
+        0
+    }
+}
+
+/// Creates an `ISimpleMediaDecoder` object on C++ side and returns a pointer to it.
+///
+/// This function is provided because Rust structs can't implement C++ virtual base classes.
+///
+/// # Ownership
+///
+/// Unlike [`create_cpp_to_rust_pcm_source()`], this function takes ownership of the given Rust
+/// decoder. The C++ decoder drops it as soon as it gets destroyed itself. That's necessary because
+/// REAPER destroys decoders by itself, e.g. when the PCM source created with
+/// `PCM_Source_CreateFromSimple()` is destroyed.
+///
+/// # Cleaning up
+///
+/// If you don't hand the decoder over to REAPER, you must take care of destroying the C++
+/// counterpart by calling [`delete_cpp_media_decoder()`] in order to avoid memory leaks.
+///
+/// # Safety
+///
+/// This function is highly unsafe. Better use the medium-level API instead.
+///
+/// [`create_cpp_to_rust_pcm_source()`]: fn.create_cpp_to_rust_pcm_source.html
+/// [`delete_cpp_media_decoder()`]: fn.delete_cpp_media_decoder.html
+pub unsafe fn create_cpp_to_rust_media_decoder(
+    callback_target: Box<Box<dyn ISimpleMediaDecoder>>,
+) -> NonNull<raw::ISimpleMediaDecoder> {
+    let thin_ptr = Box::into_raw(callback_target);
+    let instance = crate::bindings::root::reaper_media_decoder::create_cpp_to_rust_media_decoder(
+        thin_ptr as *mut c_void,
+    );
+    NonNull::new_unchecked(instance)
+}
+
+/// Destroys a C++ `ISimpleMediaDecoder` object.
+///
+/// Intended to be used on pointers returned from [`create_cpp_to_rust_media_decoder()`] which have
+/// not been handed over to REAPER.
+///
+/// # Safety
+///
+/// REAPER can crash if you pass an invalid pointer because C++ will attempt to free the wrong
+/// location in memory.
+///
+/// [`create_cpp_to_rust_media_decoder()`]: fn.create_cpp_to_rust_media_decoder.html
+pub unsafe fn delete_cpp_media_decoder(decoder: NonNull<raw::ISimpleMediaDecoder>) {
+    crate::bindings::root::reaper_media_decoder::delete_media_decoder(decoder.as_ptr());
+}
+
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_Destroy(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) {
+    firewall(|| {
+        let _ = unsafe { Box::from_raw(callback_target) };
+    });
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_Duplicate(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> *mut raw::ISimpleMediaDecoder {
+    firewall(|| unsafe { &mut *callback_target }.Duplicate()).unwrap_or(null_mut())
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_Open(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    filename: *const c_char,
+    diskreadmode: c_int,
+    diskreadbs: c_int,
+    diskreadnb: c_int,
+) {
+    firewall(|| {
+        unsafe { &mut *callback_target }.Open(filename, diskreadmode, diskreadbs, diskreadnb)
+    });
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_Close(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    fullClose: bool,
+) {
+    firewall(|| unsafe { &mut *callback_target }.Close(fullClose));
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetFileName(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> *const c_char {
+    firewall(|| unsafe { &mut *callback_target }.GetFileName()).unwrap_or(null())
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetType(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> *const c_char {
+    firewall(|| unsafe { &mut *callback_target }.GetType()).unwrap_or(null())
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetInfoString(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    buf: *mut c_char,
+    buflen: c_int,
+    title: *mut c_char,
+    titlelen: c_int,
+) {
+    firewall(|| unsafe { &mut *callback_target }.GetInfoString(buf, buflen, title, titlelen));
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_IsOpen(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> bool {
+    firewall(|| unsafe { &mut *callback_target }.IsOpen()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetNumChannels(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> c_int {
+    firewall(|| unsafe { &mut *callback_target }.GetNumChannels()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetBitsPerSample(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> c_int {
+    firewall(|| unsafe { &mut *callback_target }.GetBitsPerSample()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetSampleRate(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> f64 {
+    firewall(|| unsafe { &mut *callback_target }.GetSampleRate()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetLength(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> raw::INT64 {
+    firewall(|| unsafe { &mut *callback_target }.GetLength()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_GetPosition(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+) -> raw::INT64 {
+    firewall(|| unsafe { &mut *callback_target }.GetPosition()).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_SetPosition(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    pos: raw::INT64,
+) {
+    firewall(|| unsafe { &mut *callback_target }.SetPosition(pos));
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_ReadSamples(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    buf: *mut raw::ReaSample,
+    length: c_int,
+) -> c_int {
+    firewall(|| unsafe { &mut *callback_target }.ReadSamples(buf, length)).unwrap_or_default()
+}
+#[no_mangle]
+extern "C" fn cpp_to_rust_ISimpleMediaDecoder_Extended(
+    callback_target: *mut Box<dyn ISimpleMediaDecoder>,
+    call: c_int,
+    parm1: *mut c_void,
+    parm2: *mut c_void,
+    parm3: *mut c_void,
+) -> c_int {
+    firewall(|| unsafe { &mut *callback_target }.Extended(call, parm1, parm2, parm3))
+        .unwrap_or_default()
+}
@@ -11,6 +11,18 @@ namespace reaper_midi {
     self->AddItem(evt);
   }
 
+  void MIDI_eventlist_DeleteItem(MIDI_eventlist* self, int bpos) {
+    self->DeleteItem(bpos);
+  }
+
+  int MIDI_eventlist_GetSize(MIDI_eventlist* self) {
+    return self->GetSize();
+  }
+
+  void MIDI_eventlist_Empty(MIDI_eventlist* self) {
+    self->Empty();
+  }
+
   // midi_Input methods
 
   MIDI_eventlist* midi_Input_GetReadBuf(midi_Input* self) {
@@ -27,6 +27,21 @@ impl MIDI_eventlist {
     pub unsafe fn AddItem(&self, evt: *mut MIDI_event_t) {
         MIDI_eventlist_AddItem(self as *const _ as _, evt);
     }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass a position which doesn't point to the start of an event.
+    pub unsafe fn DeleteItem(&self, bpos: c_int) {
+        MIDI_eventlist_DeleteItem(self as *const _ as _, bpos);
+    }
+
+    pub fn GetSize(&self) -> c_int {
+        unsafe { MIDI_eventlist_GetSize(self as *const _ as _) }
+    }
+
+    pub fn Empty(&self) {
+        unsafe { MIDI_eventlist_Empty(self as *const _ as _) };
+    }
 }
 
 impl midi_Output {
@@ -0,0 +1,47 @@
+#include "peaks.hpp"
+
+namespace reaper_peaks {
+  // REAPER_PeakGet_Interface methods
+
+  double REAPER_PeakGet_Interface_GetMaxPeakRes(REAPER_PeakGet_Interface* self) {
+    return self->GetMaxPeakRes();
+  }
+
+  void REAPER_PeakGet_Interface_GetPeakInfo(REAPER_PeakGet_Interface* self, PCM_source_peaktransfer_t* block) {
+    self->GetPeakInfo(block);
+  }
+
+  int REAPER_PeakGet_Interface_Extended(REAPER_PeakGet_Interface* self, int call, void* parm1, void* parm2, void* parm3) {
+    return self->Extended(call, parm1, parm2, parm3);
+  }
+
+  void delete_peak_get_interface(REAPER_PeakGet_Interface* self) {
+    delete self;
+  }
+
+  // REAPER_PeakBuild_Interface methods
+
+  void REAPER_PeakBuild_Interface_ProcessSamples(REAPER_PeakBuild_Interface* self, ReaSample** samples, int len, int nch, int offs, int spread) {
+    self->ProcessSamples(samples, len, nch, offs, spread);
+  }
+
+  int REAPER_PeakBuild_Interface_Run(REAPER_PeakBuild_Interface* self) {
+    return self->Run();
+  }
+
+  int REAPER_PeakBuild_Interface_GetLastSecondPeaks(REAPER_PeakBuild_Interface* self, int sz, ReaSample* buf) {
+    return self->GetLastSecondPeaks(sz, buf);
+  }
+
+  void REAPER_PeakBuild_Interface_GetPeakInfo(REAPER_PeakBuild_Interface* self, PCM_source_peaktransfer_t* block) {
+    self->GetPeakInfo(block);
+  }
+
+  int REAPER_PeakBuild_Interface_Extended(REAPER_PeakBuild_Interface* self, int call, void* parm1, void* parm2, void* parm3) {
+    return self->Extended(call, parm1, parm2, parm3);
+  }
+
+  void delete_peak_build_interface(REAPER_PeakBuild_Interface* self) {
+    delete self;
+  }
+}
\ No newline at end of file
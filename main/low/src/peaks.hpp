@@ -0,0 +1,26 @@
+#pragma once
+
+#include "../lib/reaper/reaper_plugin.h"
+
+// All the functions in this namespace are called from Rust and implemented in C++. The implementation simply delegates
+// to the respective method of the `self` object. This glue code is necessary because Rust can't call  C++ pure virtual
+// functions directly.
+namespace reaper_peaks {
+  // REAPER_PeakGet_Interface methods
+  extern "C" double REAPER_PeakGet_Interface_GetMaxPeakRes(REAPER_PeakGet_Interface* self);
+  extern "C" void REAPER_PeakGet_Interface_GetPeakInfo(REAPER_PeakGet_Interface* self, PCM_source_peaktransfer_t* block);
+  extern "C" int REAPER_PeakGet_Interface_Extended(REAPER_PeakGet_Interface* self, int call, void* parm1, void* parm2, void* parm3);
+
+  // Destroys the given peak getter (which must have been created by REAPER via `PeakGet_Create()`).
+  extern "C" void delete_peak_get_interface(REAPER_PeakGet_Interface* self);
+
+  // REAPER_PeakBuild_Interface methods
+  extern "C" void REAPER_PeakBuild_Interface_ProcessSamples(REAPER_PeakBuild_Interface* self, ReaSample** samples, int len, int nch, int offs, int spread);
+  extern "C" int REAPER_PeakBuild_Interface_Run(REAPER_PeakBuild_Interface* self);
+  extern "C" int REAPER_PeakBuild_Interface_GetLastSecondPeaks(REAPER_PeakBuild_Interface* self, int sz, ReaSample* buf);
+  extern "C" void REAPER_PeakBuild_Interface_GetPeakInfo(REAPER_PeakBuild_Interface* self, PCM_source_peaktransfer_t* block);
+  extern "C" int REAPER_PeakBuild_Interface_Extended(REAPER_PeakBuild_Interface* self, int call, void* parm1, void* parm2, void* parm3);
+
+  // Destroys the given peak builder (which must have been created by REAPER via `PeakBuild_Create()`).
+  extern "C" void delete_peak_build_interface(REAPER_PeakBuild_Interface* self);
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+use super::bindings::root::reaper_peaks::*;
+use crate::raw;
+use std::os::raw::{c_int, c_void};
+use std::ptr::NonNull;
+
+impl raw::REAPER_PeakGet_Interface {
+    pub fn GetMaxPeakRes(&self) -> f64 {
+        unsafe { REAPER_PeakGet_Interface_GetMaxPeakRes(self as *const _ as _) }
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn GetPeakInfo(&self, block: *mut raw::PCM_source_peaktransfer_t) {
+        REAPER_PeakGet_Interface_GetPeakInfo(self as *const _ as _, block);
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn Extended(
+        &self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        REAPER_PeakGet_Interface_Extended(self as *const _ as _, call, parm1, parm2, parm3)
+    }
+}
+
+impl raw::REAPER_PeakBuild_Interface {
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn ProcessSamples(
+        &self,
+        samples: *mut *mut raw::ReaSample,
+        len: c_int,
+        nch: c_int,
+        offs: c_int,
+        spread: c_int,
+    ) {
+        REAPER_PeakBuild_Interface_ProcessSamples(
+            self as *const _ as _,
+            samples,
+            len,
+            nch,
+            offs,
+            spread,
+        );
+    }
+
+    pub fn Run(&self) -> c_int {
+        unsafe { REAPER_PeakBuild_Interface_Run(self as *const _ as _) }
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn GetLastSecondPeaks(&self, sz: c_int, buf: *mut raw::ReaSample) -> c_int {
+        REAPER_PeakBuild_Interface_GetLastSecondPeaks(self as *const _ as _, sz, buf)
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn GetPeakInfo(&self, block: *mut raw::PCM_source_peaktransfer_t) {
+        REAPER_PeakBuild_Interface_GetPeakInfo(self as *const _ as _, block);
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn Extended(
+        &self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        REAPER_PeakBuild_Interface_Extended(self as *const _ as _, call, parm1, parm2, parm3)
+    }
+}
+
+/// Destroys a C++ `REAPER_PeakGet_Interface` object.
+///
+/// Intended to be used on pointers returned from `PeakGet_Create()`.
+///
+/// # Safety
+///
+/// REAPER can crash if you pass an invalid pointer because C++ will attempt to free the wrong
+/// location in memory.
+pub unsafe fn delete_cpp_peak_get_interface(peak_get: NonNull<raw::REAPER_PeakGet_Interface>) {
+    delete_peak_get_interface(peak_get.as_ptr());
+}
+
+/// Destroys a C++ `REAPER_PeakBuild_Interface` object.
+///
+/// Intended to be used on pointers returned from `PeakBuild_Create()`.
+///
+/// # Safety
+///
+/// REAPER can crash if you pass an invalid pointer because C++ will attempt to free the wrong
+/// location in memory.
+pub unsafe fn delete_cpp_peak_build_interface(
+    peak_build: NonNull<raw::REAPER_PeakBuild_Interface>,
+) {
+    delete_peak_build_interface(peak_build.as_ptr());
+}
@@ -5,18 +5,26 @@ use std::os::raw::{c_int, c_void};
 
 /// Structs, types and constants defined by REAPER.
 pub use super::bindings::root::{
-    audio_hook_register_t, gaccel_register_t, midi_Input, midi_Output, preview_register_t,
-    reaper_plugin_info_t, IReaperControlSurface, KbdCmd, KbdSectionInfo, MIDI_event_t,
-    MIDI_eventlist, MediaItem, MediaItem_Take, MediaTrack, PCM_source, PCM_source_peaktransfer_t,
-    PCM_source_transfer_t, ProjectStateContext, ReaProject, ReaSample, TrackEnvelope,
-    CSURF_EXT_RESET, CSURF_EXT_SETBPMANDPLAYRATE, CSURF_EXT_SETFOCUSEDFX, CSURF_EXT_SETFXCHANGE,
+    audio_hook_register_t, gaccel_register_t, midi_Input, midi_Output, pcmsrc_register_t,
+    preview_register_t, reaper_plugin_info_t, IReaperControlSurface, IReaperPitchShift,
+    ISimpleMediaDecoder, INT64, KbdCmd, KbdSectionInfo, MIDI_event_t, MIDI_eventlist, MediaItem,
+    MediaItem_Take, MediaTrack, PCM_source, PCM_source_peaktransfer_t, PCM_source_transfer_t,
+    ProjectStateContext, REAPER_PeakBuild_Interface, REAPER_PeakGet_Interface,
+    REAPER_Resample_Interface, ReaProject, ReaSample, TrackEnvelope, CSURF_EXT_RESET,
+    CSURF_EXT_SETBPMANDPLAYRATE, CSURF_EXT_SETFOCUSEDFX, CSURF_EXT_SETFXCHANGE,
     CSURF_EXT_SETFXENABLED, CSURF_EXT_SETFXOPEN, CSURF_EXT_SETFXPARAM, CSURF_EXT_SETFXPARAM_RECFX,
     CSURF_EXT_SETINPUTMONITOR, CSURF_EXT_SETLASTTOUCHEDFX, CSURF_EXT_SETPAN_EX,
     CSURF_EXT_SETPROJECTMARKERCHANGE, CSURF_EXT_SETRECVPAN, CSURF_EXT_SETRECVVOLUME,
     CSURF_EXT_SETSENDPAN, CSURF_EXT_SETSENDVOLUME, CSURF_EXT_SUPPORTS_EXTENDED_TOUCH,
     CSURF_EXT_TRACKFX_PRESET_CHANGED, PCM_SOURCE_EXT_EXPORTTOFILE, PCM_SOURCE_EXT_GETPOOLEDMIDIID,
-    PCM_SOURCE_EXT_OPENEDITOR, REAPER_PLUGIN_VERSION, UNDO_STATE_ALL, UNDO_STATE_FREEZE,
-    UNDO_STATE_FX, UNDO_STATE_ITEMS, UNDO_STATE_MISCCFG, UNDO_STATE_TRACKCFG,
+    PCM_SOURCE_EXT_OPENEDITOR, PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_MODE,
+    PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_TRIANGLE_MODE,
+    PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_NOTE_MODE,
+    PCM_source_peaktransfer_t_PEAKTRANSFER_PEAKS_MODE,
+    PCM_source_peaktransfer_t_PEAKTRANSFER_WAVEFORM_MODE, REAPER_PITCHSHIFT_API_VER,
+    REAPER_PLUGIN_VERSION, RESAMPLE_EXT_RESETWITHFRACPOS, RESAMPLE_EXT_SETFEEDMODE,
+    RESAMPLE_EXT_SETRSMODE, UNDO_STATE_ALL, UNDO_STATE_FREEZE, UNDO_STATE_FX, UNDO_STATE_ITEMS,
+    UNDO_STATE_MISCCFG, UNDO_STATE_TRACKCFG,
 };
 
 /// Structs, types and constants defined by `swell.h` (on Linux and Mac OS X) and
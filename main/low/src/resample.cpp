@@ -0,0 +1,91 @@
+#include "resample.hpp"
+
+namespace reaper_resample {
+  // REAPER_Resample_Interface methods
+
+  void REAPER_Resample_Interface_SetRates(REAPER_Resample_Interface* self, double rate_in, double rate_out) {
+    self->SetRates(rate_in, rate_out);
+  }
+
+  void REAPER_Resample_Interface_Reset(REAPER_Resample_Interface* self) {
+    self->Reset();
+  }
+
+  double REAPER_Resample_Interface_GetCurrentLatency(REAPER_Resample_Interface* self) {
+    return self->GetCurrentLatency();
+  }
+
+  int REAPER_Resample_Interface_ResamplePrepare(REAPER_Resample_Interface* self, int out_samples, int nch, ReaSample** inbuffer) {
+    return self->ResamplePrepare(out_samples, nch, inbuffer);
+  }
+
+  int REAPER_Resample_Interface_ResampleOut(REAPER_Resample_Interface* self, ReaSample* out, int nsamples_in, int nsamples_out, int nch) {
+    return self->ResampleOut(out, nsamples_in, nsamples_out, nch);
+  }
+
+  int REAPER_Resample_Interface_Extended(REAPER_Resample_Interface* self, int call, void* parm1, void* parm2, void* parm3) {
+    return self->Extended(call, parm1, parm2, parm3);
+  }
+
+  void delete_resample_interface(REAPER_Resample_Interface* self) {
+    delete self;
+  }
+
+  // IReaperPitchShift methods
+
+  void IReaperPitchShift_set_srate(IReaperPitchShift* self, double srate) {
+    self->set_srate(srate);
+  }
+
+  void IReaperPitchShift_set_nch(IReaperPitchShift* self, int nch) {
+    self->set_nch(nch);
+  }
+
+  void IReaperPitchShift_set_shift(IReaperPitchShift* self, double shift) {
+    self->set_shift(shift);
+  }
+
+  void IReaperPitchShift_set_formant_shift(IReaperPitchShift* self, double shift) {
+    self->set_formant_shift(shift);
+  }
+
+  void IReaperPitchShift_set_tempo(IReaperPitchShift* self, double tempo) {
+    self->set_tempo(tempo);
+  }
+
+  void IReaperPitchShift_Reset(IReaperPitchShift* self) {
+    self->Reset();
+  }
+
+  ReaSample* IReaperPitchShift_GetBuffer(IReaperPitchShift* self, int size) {
+    return self->GetBuffer(size);
+  }
+
+  void IReaperPitchShift_BufferDone(IReaperPitchShift* self, int input_filled) {
+    self->BufferDone(input_filled);
+  }
+
+  void IReaperPitchShift_FlushSamples(IReaperPitchShift* self) {
+    self->FlushSamples();
+  }
+
+  bool IReaperPitchShift_IsReset(IReaperPitchShift* self) {
+    return self->IsReset();
+  }
+
+  int IReaperPitchShift_GetSamples(IReaperPitchShift* self, int requested_output, ReaSample* buffer) {
+    return self->GetSamples(requested_output, buffer);
+  }
+
+  void IReaperPitchShift_SetQualityParameter(IReaperPitchShift* self, int parm) {
+    self->SetQualityParameter(parm);
+  }
+
+  int IReaperPitchShift_Extended(IReaperPitchShift* self, int call, void* parm1, void* parm2, void* parm3) {
+    return self->Extended(call, parm1, parm2, parm3);
+  }
+
+  void delete_pitch_shift(IReaperPitchShift* self) {
+    delete self;
+  }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+#pragma once
+
+#include "../lib/reaper/reaper_plugin.h"
+
+// All the functions in this namespace are called from Rust and implemented in C++. The implementation simply delegates
+// to the respective method of the `self` object. This glue code is necessary because Rust can't call  C++ pure virtual
+// functions directly.
+namespace reaper_resample {
+  // REAPER_Resample_Interface methods
+  extern "C" void REAPER_Resample_Interface_SetRates(REAPER_Resample_Interface* self, double rate_in, double rate_out);
+  extern "C" void REAPER_Resample_Interface_Reset(REAPER_Resample_Interface* self);
+  extern "C" double REAPER_Resample_Interface_GetCurrentLatency(REAPER_Resample_Interface* self);
+  extern "C" int REAPER_Resample_Interface_ResamplePrepare(REAPER_Resample_Interface* self, int out_samples, int nch, ReaSample** inbuffer);
+  extern "C" int REAPER_Resample_Interface_ResampleOut(REAPER_Resample_Interface* self, ReaSample* out, int nsamples_in, int nsamples_out, int nch);
+  extern "C" int REAPER_Resample_Interface_Extended(REAPER_Resample_Interface* self, int call, void* parm1, void* parm2, void* parm3);
+
+  // Destroys the given object (which must have been created by REAPER via `Resampler_Create()`).
+  extern "C" void delete_resample_interface(REAPER_Resample_Interface* self);
+
+  // IReaperPitchShift methods
+  extern "C" void IReaperPitchShift_set_srate(IReaperPitchShift* self, double srate);
+  extern "C" void IReaperPitchShift_set_nch(IReaperPitchShift* self, int nch);
+  extern "C" void IReaperPitchShift_set_shift(IReaperPitchShift* self, double shift);
+  extern "C" void IReaperPitchShift_set_formant_shift(IReaperPitchShift* self, double shift);
+  extern "C" void IReaperPitchShift_set_tempo(IReaperPitchShift* self, double tempo);
+  extern "C" void IReaperPitchShift_Reset(IReaperPitchShift* self);
+  extern "C" ReaSample* IReaperPitchShift_GetBuffer(IReaperPitchShift* self, int size);
+  extern "C" void IReaperPitchShift_BufferDone(IReaperPitchShift* self, int input_filled);
+  extern "C" void IReaperPitchShift_FlushSamples(IReaperPitchShift* self);
+  extern "C" bool IReaperPitchShift_IsReset(IReaperPitchShift* self);
+  extern "C" int IReaperPitchShift_GetSamples(IReaperPitchShift* self, int requested_output, ReaSample* buffer);
+  extern "C" void IReaperPitchShift_SetQualityParameter(IReaperPitchShift* self, int parm);
+  extern "C" int IReaperPitchShift_Extended(IReaperPitchShift* self, int call, void* parm1, void* parm2, void* parm3);
+
+  // Destroys the given object (which must have been created by REAPER via `ReaperGetPitchShiftAPI()`).
+  extern "C" void delete_pitch_shift(IReaperPitchShift* self);
+}
\ No newline at end of file
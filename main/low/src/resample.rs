@@ -0,0 +1,156 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+use super::bindings::root::reaper_resample::*;
+use crate::raw;
+use std::os::raw::{c_int, c_void};
+use std::ptr::NonNull;
+
+impl raw::REAPER_Resample_Interface {
+    pub fn SetRates(&self, rate_in: f64, rate_out: f64) {
+        unsafe { REAPER_Resample_Interface_SetRates(self as *const _ as _, rate_in, rate_out) };
+    }
+
+    pub fn Reset(&self) {
+        unsafe { REAPER_Resample_Interface_Reset(self as *const _ as _) };
+    }
+
+    pub fn GetCurrentLatency(&self) -> f64 {
+        unsafe { REAPER_Resample_Interface_GetCurrentLatency(self as *const _ as _) }
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn ResamplePrepare(
+        &self,
+        out_samples: c_int,
+        nch: c_int,
+        inbuffer: *mut *mut raw::ReaSample,
+    ) -> c_int {
+        REAPER_Resample_Interface_ResamplePrepare(self as *const _ as _, out_samples, nch, inbuffer)
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn ResampleOut(
+        &self,
+        out: *mut raw::ReaSample,
+        nsamples_in: c_int,
+        nsamples_out: c_int,
+        nch: c_int,
+    ) -> c_int {
+        REAPER_Resample_Interface_ResampleOut(
+            self as *const _ as _,
+            out,
+            nsamples_in,
+            nsamples_out,
+            nch,
+        )
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn Extended(
+        &self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        REAPER_Resample_Interface_Extended(self as *const _ as _, call, parm1, parm2, parm3)
+    }
+}
+
+impl raw::IReaperPitchShift {
+    pub fn set_srate(&self, srate: f64) {
+        unsafe { IReaperPitchShift_set_srate(self as *const _ as _, srate) };
+    }
+
+    pub fn set_nch(&self, nch: c_int) {
+        unsafe { IReaperPitchShift_set_nch(self as *const _ as _, nch) };
+    }
+
+    pub fn set_shift(&self, shift: f64) {
+        unsafe { IReaperPitchShift_set_shift(self as *const _ as _, shift) };
+    }
+
+    pub fn set_formant_shift(&self, shift: f64) {
+        unsafe { IReaperPitchShift_set_formant_shift(self as *const _ as _, shift) };
+    }
+
+    pub fn set_tempo(&self, tempo: f64) {
+        unsafe { IReaperPitchShift_set_tempo(self as *const _ as _, tempo) };
+    }
+
+    pub fn Reset(&self) {
+        unsafe { IReaperPitchShift_Reset(self as *const _ as _) };
+    }
+
+    pub fn GetBuffer(&self, size: c_int) -> *mut raw::ReaSample {
+        unsafe { IReaperPitchShift_GetBuffer(self as *const _ as _, size) }
+    }
+
+    pub fn BufferDone(&self, input_filled: c_int) {
+        unsafe { IReaperPitchShift_BufferDone(self as *const _ as _, input_filled) };
+    }
+
+    pub fn FlushSamples(&self) {
+        unsafe { IReaperPitchShift_FlushSamples(self as *const _ as _) };
+    }
+
+    pub fn IsReset(&self) -> bool {
+        unsafe { IReaperPitchShift_IsReset(self as *const _ as _) }
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn GetSamples(&self, requested_output: c_int, buffer: *mut raw::ReaSample) -> c_int {
+        IReaperPitchShift_GetSamples(self as *const _ as _, requested_output, buffer)
+    }
+
+    pub fn SetQualityParameter(&self, parm: c_int) {
+        unsafe { IReaperPitchShift_SetQualityParameter(self as *const _ as _, parm) };
+    }
+
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid pointer.
+    pub unsafe fn Extended(
+        &self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        IReaperPitchShift_Extended(self as *const _ as _, call, parm1, parm2, parm3)
+    }
+}
+
+/// Destroys a C++ `REAPER_Resample_Interface` object.
+///
+/// Intended to be used on pointers returned from `Resampler_Create()`.
+///
+/// # Safety
+///
+/// REAPER can crash if you pass an invalid pointer because C++ will attempt to free the wrong
+/// location in memory.
+pub unsafe fn delete_cpp_resample_interface(resampler: NonNull<raw::REAPER_Resample_Interface>) {
+    delete_resample_interface(resampler.as_ptr());
+}
+
+/// Destroys a C++ `IReaperPitchShift` object.
+///
+/// Intended to be used on pointers returned from `ReaperGetPitchShiftAPI()`.
+///
+/// # Safety
+///
+/// REAPER can crash if you pass an invalid pointer because C++ will attempt to free the wrong
+/// location in memory.
+pub unsafe fn delete_cpp_pitch_shift(pitch_shift: NonNull<raw::IReaperPitchShift>) {
+    delete_pitch_shift(pitch_shift.as_ptr());
+}
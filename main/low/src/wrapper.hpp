@@ -30,4 +30,7 @@ namespace swell_functions {
 // Make C++ glue code functions visible to bindgen. They will be used in the real application.
 #include "control_surface.hpp"
 #include "midi.hpp"
-#include "pcm_source.hpp"
\ No newline at end of file
+#include "pcm_source.hpp"
+#include "media_decoder.hpp"
+#include "peaks.hpp"
+#include "resample.hpp"
\ No newline at end of file
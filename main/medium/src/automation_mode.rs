@@ -1,7 +1,10 @@
 use crate::Hidden;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Global override of track automation modes.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GlobalAutomationModeOverride {
     /// All automation is bypassed.
     Bypass,
@@ -11,6 +14,7 @@ pub enum GlobalAutomationModeOverride {
 
 /// Automation mode of a track.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AutomationMode {
     TrimRead,
     Read,
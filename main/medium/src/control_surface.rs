@@ -8,6 +8,8 @@ use crate::{
 
 use reaper_low::raw;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
@@ -533,6 +535,7 @@ pub struct QualifiedFxLocation {
 
 /// Location of a track or take FX.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VersionDependentFxLocation {
     /// It's a take FX.
     ///
@@ -549,6 +552,7 @@ pub enum VersionDependentFxLocation {
 
 /// Location of a track FX.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VersionDependentTrackFxLocation {
     /// This is REAPER < 5.95.
     ///
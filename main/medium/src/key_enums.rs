@@ -598,6 +598,119 @@ impl<'a> TrackSendAttributeKey<'a> {
     }
 }
 
+/// Item attribute key which you can pass to [`get_media_item_info_value()`].
+///
+/// [`get_media_item_info_value()`]: struct.Reaper.html#method.get_media_item_info_value
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ItemAttributeKey<'a> {
+    /// Muted.
+    ///
+    /// `*mut bool`
+    Mute,
+    /// Loop source.
+    ///
+    /// `*mut bool`
+    LoopSrc,
+    /// Play all takes.
+    ///
+    /// `*mut bool`
+    AllTakesPlay,
+    /// Selected in arrange view.
+    ///
+    /// `*mut bool`
+    UiSel,
+    /// Locked.
+    ///
+    /// `*mut char`
+    ///
+    /// - &1 → locked
+    Lock,
+    /// Item volume.
+    ///
+    /// `*mut f64`
+    ///
+    /// 0 → -inf, 0.5 → -6dB, 1 → +0dB, 2 → +6dB etc.
+    Vol,
+    /// Position in seconds.
+    ///
+    /// `*mut f64`
+    Position,
+    /// Length in seconds.
+    ///
+    /// `*mut f64`
+    Length,
+    /// Snap offset in seconds.
+    ///
+    /// `*mut f64`
+    SnapOffset,
+    /// Fade-in length in seconds.
+    ///
+    /// `*mut f64`
+    FadeInLen,
+    /// Fade-out length in seconds.
+    ///
+    /// `*mut f64`
+    FadeOutLen,
+    /// Active take number.
+    ///
+    /// `*mut i32`
+    CurTake,
+    /// Group ID.
+    ///
+    /// `*mut i32`
+    ///
+    /// 0 → no group
+    GroupId,
+    /// Custom color.
+    ///
+    /// `*mut i32`
+    ///
+    /// OS dependent color|0x100000 (i.e. ColorToNative(r,g,b)|0x100000). If you don't do
+    /// |0x100000, then it will not be used, but will store the color anyway.
+    CustomColor,
+    /// Item GUID.
+    ///
+    /// `*mut GUID`
+    Guid,
+    /// If a variant is missing in this enum, you can use this custom one as a resort.
+    ///
+    /// Use [`custom()`] to create this variant.
+    ///
+    /// [`custom()`]: #method.custom
+    Custom(Cow<'a, ReaperStr>),
+}
+
+impl<'a> ItemAttributeKey<'a> {
+    /// Convenience function for creating a [`Custom`] key.
+    ///
+    /// [`Custom`]: #variant.Custom
+    pub fn custom(key: impl Into<ReaperStringArg<'a>>) -> ItemAttributeKey<'a> {
+        ItemAttributeKey::Custom(key.into().into_inner())
+    }
+
+    pub(crate) fn into_raw(self) -> Cow<'a, ReaperStr> {
+        use ItemAttributeKey::*;
+        match self {
+            Mute => reaper_str!("B_MUTE").into(),
+            LoopSrc => reaper_str!("B_LOOPSRC").into(),
+            AllTakesPlay => reaper_str!("B_ALLTAKESPLAY").into(),
+            UiSel => reaper_str!("B_UISEL").into(),
+            Lock => reaper_str!("C_LOCK").into(),
+            Vol => reaper_str!("D_VOL").into(),
+            Position => reaper_str!("D_POSITION").into(),
+            Length => reaper_str!("D_LENGTH").into(),
+            SnapOffset => reaper_str!("D_SNAPOFFSET").into(),
+            FadeInLen => reaper_str!("D_FADEINLEN").into(),
+            FadeOutLen => reaper_str!("D_FADEOUTLEN").into(),
+            CurTake => reaper_str!("I_CURTAKE").into(),
+            GroupId => reaper_str!("I_GROUPID").into(),
+            CustomColor => reaper_str!("I_CUSTOMCOLOR").into(),
+            Guid => reaper_str!("GUID").into(),
+            Custom(key) => key,
+        }
+    }
+}
+
 /// Envelope chunk name which you can pass e.g. to [`TrackAttributeKey::Env()`].
 ///
 /// [`TrackAttributeKey::Env()`]: enum.TrackAttributeKey.html#variant.Env
@@ -357,6 +357,15 @@ pub use midi::*;
 mod pcm_source;
 pub use pcm_source::*;
 
+mod media_decoder;
+pub use media_decoder::*;
+
+mod resample;
+pub use resample::*;
+
+mod peaks;
+pub use peaks::*;
+
 mod reaper_session;
 pub use reaper_session::*;
 
@@ -0,0 +1,424 @@
+#![allow(non_snake_case)]
+use crate::util::create_passing_c_str;
+use crate::{ExtendedArgs, Hz, ReaperStr};
+use reaper_low::raw::{ISimpleMediaDecoder, PCM_source, ReaSample, INT64};
+use reaper_low::{create_cpp_to_rust_media_decoder, delete_cpp_media_decoder, firewall, raw};
+use std::os::raw::{c_char, c_int, c_void};
+use std::path::Path;
+use std::ptr::{null, null_mut, NonNull};
+
+/// Consumers need to implement this trait in order to teach REAPER how to decode a custom media
+/// format.
+///
+/// This is a much simpler alternative to implementing a complete [`CustomPcmSource`]. REAPER wraps
+/// the decoder in a PCM source of its own which takes care of peaks, state saving etc.
+///
+/// Decoders are created by a [`MediaDecoderFactory`] which you register via
+/// [`ReaperSession::plugin_register_add_media_decoder()`].
+///
+/// [`CustomPcmSource`]: trait.CustomPcmSource.html
+/// [`MediaDecoderFactory`]: trait.MediaDecoderFactory.html
+/// [`ReaperSession::plugin_register_add_media_decoder()`]:
+/// struct.ReaperSession.html#method.plugin_register_add_media_decoder
+pub trait CustomMediaDecoder {
+    /// Returns a fresh decoder for the same file (not opened).
+    fn duplicate(&mut self) -> Option<Box<dyn CustomMediaDecoder>>;
+
+    /// Opens the given file or - if `None` - the last file.
+    fn open(&mut self, args: OpenMediaArgs);
+
+    /// If `full_close` is `false`, close disk resources, but decoders etc. can stay initialized
+    /// (and subsequently check the file date on re-open).
+    fn close(&mut self, args: CloseMediaArgs);
+
+    /// Returns the name of the currently opened file.
+    //
+    // Must return a reference to something that we own for the same reason as
+    // `CustomPcmSource::get_file_name()`.
+    fn get_file_name(&mut self) -> Option<&ReaperStr>;
+
+    /// Returns the type, e.g. "MYFORMAT".
+    fn get_type(&mut self) -> &ReaperStr;
+
+    /// Returns an info text suitable for a dialog (and a title for that dialog).
+    ///
+    /// Optional.
+    fn get_info_string(&mut self) -> Option<MediaInfoString> {
+        None
+    }
+
+    /// Returns whether a file is currently opened.
+    fn is_open(&mut self) -> bool;
+
+    /// Returns the number of channels.
+    fn get_num_channels(&mut self) -> u32;
+
+    /// Returns bits/sample. Only used for metadata purposes.
+    fn get_bits_per_sample(&mut self) -> u32 {
+        0
+    }
+
+    /// Returns the sample rate.
+    fn get_sample_rate(&mut self) -> Hz;
+
+    /// Returns the length in sample frames.
+    fn get_length(&mut self) -> u64;
+
+    /// Returns the current read position in sample frames.
+    fn get_position(&mut self) -> u64;
+
+    /// Sets the current read position in sample frames.
+    fn set_position(&mut self, position: u64);
+
+    /// Reads samples starting at the current position into the given buffer and advances the
+    /// position.
+    ///
+    /// The buffer is interleaved and at least `frame_count * channel count` samples long. Returns
+    /// the number of sample frames read.
+    fn read_samples(&mut self, args: ReadSamplesArgs) -> u32;
+
+    /// Generic method which may also be called with `PCM_source` messages.
+    ///
+    /// The meaning of the return value depends on the particular event type ([`args.call`]). In
+    /// any case, returning 0 means that the event has not been handled.
+    ///
+    /// # Safety
+    ///
+    /// Implementing this is unsafe because you need to deal with raw pointers.
+    ///
+    /// [`args.call`]: struct.ExtendedArgs.html#structfield.call
+    unsafe fn extended(&mut self, args: ExtendedArgs) -> i32 {
+        let _ = args;
+        0
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OpenMediaArgs<'a> {
+    /// `None` means "last file name".
+    pub file_name: Option<&'a Path>,
+    /// Suggested value to pass to `WDL_FileRead` if you use it, can be ignored otherwise.
+    pub disk_read_mode: i32,
+    /// Suggested value to pass to `WDL_FileRead` if you use it, can be ignored otherwise.
+    pub disk_read_block_size: i32,
+    /// Suggested value to pass to `WDL_FileRead` if you use it, can be ignored otherwise.
+    pub disk_read_block_count: i32,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CloseMediaArgs {
+    pub full_close: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ReadSamplesArgs<'a> {
+    pub buffer: &'a mut [f64],
+    pub frame_count: u32,
+}
+
+/// Info text of a media decoder.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct MediaInfoString {
+    /// Dialog title.
+    pub title: String,
+    /// Info text.
+    pub text: String,
+}
+
+/// Consumers need to implement this trait in order to let REAPER create [`CustomMediaDecoder`]s.
+///
+/// See [`ReaperSession::plugin_register_add_media_decoder()`].
+///
+/// [`CustomMediaDecoder`]: trait.CustomMediaDecoder.html
+/// [`ReaperSession::plugin_register_add_media_decoder()`]:
+/// struct.ReaperSession.html#method.plugin_register_add_media_decoder
+pub trait MediaDecoderFactory {
+    /// Creates a decoder for the given file if it's supported.
+    ///
+    /// Priority is 0 - 7, 0 is highest. If priority is 5 - 7 and the file isn't found, you should
+    /// return a decoder anyway (which opens the file in an offline state).
+    fn create_from_file(file_name: &Path, priority: u32) -> Option<Box<dyn CustomMediaDecoder>>;
+
+    /// Creates an empty decoder for the given type (e.g. when REAPER loads a project) if supported.
+    ///
+    /// Priority is 0 - 7, 0 is highest.
+    fn create_from_type(
+        media_type: &ReaperStr,
+        priority: u32,
+    ) -> Option<Box<dyn CustomMediaDecoder>> {
+        let _ = media_type;
+        let _ = priority;
+        None
+    }
+
+    /// Returns the file extension at the given index or `None` if the index is out of range.
+    ///
+    /// This is used for UI purposes only (e.g. in file dialogs).
+    fn enum_file_extensions(index: u32) -> Option<MediaFileExtension> {
+        let _ = index;
+        None
+    }
+}
+
+/// A file extension supported by a media decoder.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MediaFileExtension {
+    /// The extension without dot, e.g. "myf".
+    pub extension: &'static ReaperStr,
+    /// Description, e.g. "My format files". If `None`, REAPER uses the last description.
+    pub description: Option<&'static ReaperStr>,
+}
+
+/// A registration struct which delegates to a [`MediaDecoderFactory`].
+///
+/// [`MediaDecoderFactory`]: trait.MediaDecoderFactory.html
+//
+// Case 2: Internals exposed: yes | vtable: no
+// ===========================================
+#[derive(Debug)]
+pub(crate) struct OwnedPcmSourceRegister(raw::pcmsrc_register_t);
+
+impl OwnedPcmSourceRegister {
+    pub fn new<T: MediaDecoderFactory>() -> OwnedPcmSourceRegister {
+        OwnedPcmSourceRegister(raw::pcmsrc_register_t {
+            CreateFromType: Some(delegating_create_from_type::<T>),
+            CreateFromFile: Some(delegating_create_from_file::<T>),
+            EnumFileExtensions: Some(delegating_enum_file_extensions::<T>),
+        })
+    }
+}
+
+impl AsRef<raw::pcmsrc_register_t> for OwnedPcmSourceRegister {
+    fn as_ref(&self) -> &raw::pcmsrc_register_t {
+        &self.0
+    }
+}
+
+// REAPER doesn't pass any context to the `pcmsrc_register_t` callbacks but we need the REAPER
+// functions in there for creating the PCM source. So we keep them globally.
+static mut LOW_REAPER: Option<reaper_low::Reaper> = None;
+
+/// Makes the given REAPER functions available to the media decoder callbacks.
+///
+/// Called when registering a media decoder. Subsequent calls don't have any effect.
+pub(crate) fn make_low_reaper_available_to_media_decoders(low: reaper_low::Reaper) {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    unsafe {
+        INIT.call_once(|| {
+            LOW_REAPER = Some(low);
+        });
+    }
+}
+
+/// Wraps the given decoder in a REAPER PCM source and hands its ownership over to REAPER.
+fn create_pcm_source_from_decoder(
+    decoder: Box<dyn CustomMediaDecoder>,
+    file_name: *const c_char,
+) -> *mut PCM_source {
+    let low = match unsafe { LOW_REAPER.as_ref() } {
+        None => return null_mut(),
+        Some(l) => l,
+    };
+    let cpp_decoder = create_cpp_decoder(decoder);
+    let source = unsafe { low.PCM_Source_CreateFromSimple(cpp_decoder.as_ptr(), file_name) };
+    if source.is_null() {
+        unsafe { delete_cpp_media_decoder(cpp_decoder) };
+    }
+    source
+}
+
+fn create_cpp_decoder(decoder: Box<dyn CustomMediaDecoder>) -> NonNull<ISimpleMediaDecoder> {
+    let adapter = MediaDecoderAdapter::new(decoder);
+    // The C++ decoder takes ownership of the (double-boxed) Rust decoder and drops it as soon as
+    // REAPER destroys it.
+    let rust_decoder: Box<Box<dyn reaper_low::ISimpleMediaDecoder>> = Box::new(Box::new(adapter));
+    unsafe { create_cpp_to_rust_media_decoder(rust_decoder) }
+}
+
+extern "C" fn delegating_create_from_type<T: MediaDecoderFactory>(
+    type_: *const c_char,
+    priority: c_int,
+) -> *mut PCM_source {
+    firewall(|| {
+        let media_type = unsafe { create_passing_c_str(type_) }?;
+        let decoder = T::create_from_type(media_type, priority as u32)?;
+        Some(create_pcm_source_from_decoder(decoder, null()))
+    })
+    .flatten()
+    .unwrap_or(null_mut())
+}
+
+extern "C" fn delegating_create_from_file<T: MediaDecoderFactory>(
+    filename: *const c_char,
+    priority: c_int,
+) -> *mut PCM_source {
+    firewall(|| {
+        let file_name = unsafe { create_passing_c_str(filename) }?;
+        let decoder = T::create_from_file(Path::new(file_name.to_str()), priority as u32)?;
+        Some(create_pcm_source_from_decoder(decoder, filename))
+    })
+    .flatten()
+    .unwrap_or(null_mut())
+}
+
+extern "C" fn delegating_enum_file_extensions<T: MediaDecoderFactory>(
+    i: c_int,
+    descptr: *mut *const c_char,
+) -> *const c_char {
+    firewall(|| {
+        if i < 0 {
+            return null();
+        }
+        let ext = match T::enum_file_extensions(i as u32) {
+            None => return null(),
+            Some(e) => e,
+        };
+        if !descptr.is_null() {
+            let desc = ext.description.map(|d| d.as_ptr()).unwrap_or(null());
+            unsafe { *descptr = desc };
+        }
+        ext.extension.as_ptr()
+    })
+    .unwrap_or(null())
+}
+
+struct MediaDecoderAdapter {
+    // We need dynamic dispatch here anyway because `duplicate()` must be able to return decoders
+    // of any type.
+    delegate: Box<dyn CustomMediaDecoder>,
+}
+
+impl MediaDecoderAdapter {
+    pub fn new(delegate: Box<dyn CustomMediaDecoder>) -> Self {
+        Self { delegate }
+    }
+}
+
+impl reaper_low::ISimpleMediaDecoder for MediaDecoderAdapter {
+    fn Duplicate(&mut self) -> *mut ISimpleMediaDecoder {
+        self.delegate
+            .duplicate()
+            .map(|d| create_cpp_decoder(d).as_ptr())
+            .unwrap_or(null_mut())
+    }
+
+    fn Open(
+        &mut self,
+        filename: *const c_char,
+        diskreadmode: c_int,
+        diskreadbs: c_int,
+        diskreadnb: c_int,
+    ) {
+        let file_name = unsafe { create_passing_c_str(filename) };
+        let args = OpenMediaArgs {
+            file_name: file_name.map(|f| Path::new(f.to_str())),
+            disk_read_mode: diskreadmode,
+            disk_read_block_size: diskreadbs,
+            disk_read_block_count: diskreadnb,
+        };
+        self.delegate.open(args);
+    }
+
+    fn Close(&mut self, fullClose: bool) {
+        self.delegate.close(CloseMediaArgs {
+            full_close: fullClose,
+        });
+    }
+
+    fn GetFileName(&mut self) -> *const c_char {
+        self.delegate
+            .get_file_name()
+            .map(|s| s.as_ptr())
+            .unwrap_or(null())
+    }
+
+    fn GetType(&mut self) -> *const c_char {
+        self.delegate.get_type().as_ptr()
+    }
+
+    fn GetInfoString(
+        &mut self,
+        buf: *mut c_char,
+        buflen: c_int,
+        title: *mut c_char,
+        titlelen: c_int,
+    ) {
+        let info = match self.delegate.get_info_string() {
+            None => return,
+            Some(i) => i,
+        };
+        unsafe {
+            copy_to_string_buffer(&info.text, buf, buflen);
+            copy_to_string_buffer(&info.title, title, titlelen);
+        }
+    }
+
+    fn IsOpen(&mut self) -> bool {
+        self.delegate.is_open()
+    }
+
+    fn GetNumChannels(&mut self) -> c_int {
+        self.delegate.get_num_channels() as _
+    }
+
+    fn GetBitsPerSample(&mut self) -> c_int {
+        self.delegate.get_bits_per_sample() as _
+    }
+
+    fn GetSampleRate(&mut self) -> f64 {
+        self.delegate.get_sample_rate().get()
+    }
+
+    fn GetLength(&mut self) -> INT64 {
+        self.delegate.get_length() as _
+    }
+
+    fn GetPosition(&mut self) -> INT64 {
+        self.delegate.get_position() as _
+    }
+
+    fn SetPosition(&mut self, pos: INT64) {
+        self.delegate.set_position(pos.max(0) as u64);
+    }
+
+    fn ReadSamples(&mut self, buf: *mut ReaSample, length: c_int) -> c_int {
+        if buf.is_null() || length <= 0 {
+            return 0;
+        }
+        let channel_count = self.delegate.get_num_channels() as usize;
+        let buffer =
+            unsafe { std::slice::from_raw_parts_mut(buf, length as usize * channel_count) };
+        let args = ReadSamplesArgs {
+            buffer,
+            frame_count: length as u32,
+        };
+        self.delegate.read_samples(args) as _
+    }
+
+    fn Extended(
+        &mut self,
+        call: c_int,
+        parm1: *mut c_void,
+        parm2: *mut c_void,
+        parm3: *mut c_void,
+    ) -> c_int {
+        unsafe {
+            self.delegate.extended(ExtendedArgs {
+                call,
+                parm_1: parm1,
+                parm_2: parm2,
+                parm_3: parm3,
+            })
+        }
+    }
+}
+
+/// Copies the given string into the given C string buffer, truncating it if necessary.
+unsafe fn copy_to_string_buffer(text: &str, buffer: *mut c_char, buffer_size: c_int) {
+    if buffer.is_null() || buffer_size <= 0 {
+        return;
+    }
+    let len = text.len().min(buffer_size as usize - 1);
+    std::ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, buffer, len);
+    *buffer.add(len) = 0;
+}
@@ -4,6 +4,7 @@ use reaper_low::raw;
 use crate::{MidiFrameOffset, SendMidiTime};
 use reaper_low::raw::MIDI_event_t;
 use ref_cast::RefCast;
+use std::hash::{Hash, Hasher};
 use std::os::raw::c_int;
 use std::ptr::NonNull;
 
@@ -46,6 +47,25 @@ impl MidiInput {
         }
         unsafe { &*(raw_evt_list as *const BorrowedMidiEventList) }
     }
+
+    /// Returns the list of MIDI events which are currently in the buffer for modification.
+    ///
+    /// This must only be called in the real-time audio thread! See [`get_midi_input_mut()`].
+    ///
+    /// If you remove, replace or add events in the pre-phase of the audio hook (when
+    /// [`OnAudioBufferArgs::is_post`] is `false`), the modified events are what REAPER
+    /// subsequently routes to the tracks. Please note that the frame offsets of events in this
+    /// buffer are in units of 1/1024000 of a second, not in sample frames.
+    ///
+    /// [`get_midi_input_mut()`]: struct.Reaper.html#method.get_midi_input_mut
+    /// [`OnAudioBufferArgs::is_post`]: struct.OnAudioBufferArgs.html#structfield.is_post
+    pub fn get_read_buf_mut(&mut self) -> &mut BorrowedMidiEventList {
+        let raw_evt_list = unsafe { self.0.as_ref().GetReadBuf() };
+        if raw_evt_list.is_null() {
+            panic!("GetReadBuf returned null");
+        }
+        unsafe { &mut *(raw_evt_list as *mut BorrowedMidiEventList) }
+    }
 }
 
 /// A list of MIDI events borrowed from REAPER.
@@ -66,20 +86,193 @@ impl BorrowedMidiEventList {
         }
     }
 
+    /// Returns an iterator exposing the contained MIDI events, distinguishing between short and
+    /// long (e.g. SysEx) events.
+    ///
+    /// In contrast to [`enum_items()`], this gives safe access to the complete data of long events,
+    /// no matter how large they are.
+    ///
+    /// `bpos` is the iterator start position.
+    ///
+    /// [`enum_items()`]: #method.enum_items
+    pub fn enum_typed_items(&self, bpos: u32) -> impl Iterator<Item = TypedMidiEvent> {
+        EnumItems {
+            raw_list: &self.0,
+            bpos: bpos as i32,
+        }
+        .map(|evt| {
+            if evt.is_long() {
+                TypedMidiEvent::Long(BorrowedLongMidiEvent::ref_cast(&evt.0))
+            } else {
+                TypedMidiEvent::Short(evt)
+            }
+        })
+    }
+
     /// Adds an item to this list of MIDI events.
-    pub fn add_item(&self, msg: &MidiEvent) {
+    ///
+    /// Accepts short events ([`MidiEvent`]) as well as long ones ([`LongMidiEvent`],
+    /// [`OwnedLongMidiEvent`]). REAPER copies the event data.
+    ///
+    /// [`MidiEvent`]: struct.MidiEvent.html
+    /// [`LongMidiEvent`]: struct.LongMidiEvent.html
+    /// [`OwnedLongMidiEvent`]: struct.OwnedLongMidiEvent.html
+    pub fn add_item(&self, msg: impl AsRef<raw::MIDI_event_t>) {
         unsafe {
-            self.0.AddItem(&msg.0 as *const _ as _);
+            self.0.AddItem(msg.as_ref() as *const _ as _);
+        }
+    }
+
+    /// Removes all events for which the given function returns `false`.
+    pub fn retain(&mut self, mut f: impl FnMut(TypedMidiEvent) -> bool) {
+        self.retain_raw(|raw_evt| {
+            let evt = MidiEvent::ref_cast(raw_evt);
+            let typed_evt = if evt.is_long() {
+                TypedMidiEvent::Long(BorrowedLongMidiEvent::ref_cast(raw_evt))
+            } else {
+                TypedMidiEvent::Short(evt)
+            };
+            f(typed_evt)
+        });
+    }
+
+    /// Removes all short events for which the given function returns `false` and lets the
+    /// function modify the remaining ones in place.
+    ///
+    /// This is the way to replace events, e.g. by calling [`MidiEvent::set_message()`]. Only short
+    /// events are passed to the function. Long events (e.g. SysEx) are always kept because
+    /// REAPER stores the events of a list without gaps, so changing their size in place would
+    /// corrupt the list. Use [`retain()`] in order to remove them. Replacing a message with a
+    /// long one is not possible in place either. In that case, remove the event and add a new one
+    /// via [`add_item()`] after this method has returned.
+    ///
+    /// [`MidiEvent::set_message()`]: struct.MidiEvent.html#method.set_message
+    /// [`retain()`]: #method.retain
+    /// [`add_item()`]: #method.add_item
+    pub fn retain_mut(&mut self, mut f: impl FnMut(&mut MidiEvent) -> bool) {
+        self.retain_raw(|raw_evt| {
+            let evt = MidiEvent::ref_cast_mut(raw_evt);
+            if evt.is_long() {
+                return true;
+            }
+            f(evt)
+        });
+    }
+
+    /// Removes all events.
+    pub fn clear(&mut self) {
+        self.0.Empty();
+    }
+
+    /// Returns the size of the complete event list in bytes.
+    pub fn get_size(&self) -> u32 {
+        self.0.GetSize().max(0) as u32
+    }
+
+    // REAPER stores each event with at least the size of `MIDI_event_t` (message sizes are padded
+    // to 4 bytes), so handing out a mutable `MIDI_event_t` reference is okay even for events whose
+    // message is shorter than 4 bytes.
+    fn retain_raw(&mut self, mut f: impl FnMut(&mut raw::MIDI_event_t) -> bool) {
+        let mut bpos: c_int = 0;
+        loop {
+            let current_bpos = bpos;
+            let raw_evt = unsafe { self.0.EnumItems(&mut bpos as *mut c_int) };
+            if raw_evt.is_null() {
+                return;
+            }
+            if !f(unsafe { &mut *raw_evt }) {
+                // After deleting, the next event is located at the position of the deleted one.
+                unsafe { self.0.DeleteItem(current_bpos) };
+                bpos = current_bpos;
+            }
         }
     }
 }
 
+/// A MIDI event borrowed from a REAPER MIDI event list, typed by message length.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TypedMidiEvent<'a> {
+    /// An event containing a short MIDI message (at most 3 bytes, not SysEx).
+    Short(&'a MidiEvent),
+    /// An event containing a long MIDI message, e.g. SysEx.
+    Long(&'a BorrowedLongMidiEvent),
+}
+
+impl<'a> TypedMidiEvent<'a> {
+    /// Returns the frame offset.
+    pub fn frame_offset(&self) -> MidiFrameOffset {
+        match self {
+            TypedMidiEvent::Short(evt) => evt.frame_offset(),
+            TypedMidiEvent::Long(evt) => evt.frame_offset(),
+        }
+    }
+
+    /// Returns the complete message as byte slice.
+    pub fn bytes(&self) -> &'a [u8] {
+        match *self {
+            TypedMidiEvent::Short(evt) => evt.bytes(),
+            TypedMidiEvent::Long(evt) => evt.bytes(),
+        }
+    }
+}
+
+/// A long MIDI event (e.g. SysEx) borrowed from a REAPER MIDI event list.
+///
+/// The message data is stored inline after the event header and can be of arbitrary size.
+//
+// Internals exposed: no | vtable: no
+//
+// It's important that this can only be obtained by reference from a REAPER event list, otherwise
+// the size field could point beyond the actually allocated memory.
+#[derive(Debug, RefCast)]
+#[repr(transparent)]
+pub struct BorrowedLongMidiEvent(raw::MIDI_event_t);
+
+impl BorrowedLongMidiEvent {
+    /// Returns the frame offset.
+    pub fn frame_offset(&self) -> MidiFrameOffset {
+        MidiFrameOffset::new(self.0.frame_offset as u32)
+    }
+
+    /// Returns the complete message as byte slice.
+    pub fn bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.0.midi_message.as_ptr(), self.0.size.max(0) as usize)
+        }
+    }
+}
+
+// The wrapped struct contains only the first 4 bytes of the message, so we compare the complete
+// message instead.
+impl PartialEq for BorrowedLongMidiEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.frame_offset() == other.frame_offset() && self.bytes() == other.bytes()
+    }
+}
+
+impl Eq for BorrowedLongMidiEvent {}
+
+impl Hash for BorrowedLongMidiEvent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.frame_offset().hash(state);
+        self.bytes().hash(state);
+    }
+}
+
+impl AsRef<raw::MIDI_event_t> for BorrowedLongMidiEvent {
+    fn as_ref(&self) -> &raw::MIDI_event_t {
+        &self.0
+    }
+}
+
 /// An owned or borrowed MIDI event for or from REAPER.
 ///
-/// Cannot own more than a short MIDI message (just like the low-level equivalent).
+/// Cannot own more than a short MIDI message (just like the low-level equivalent). Use
+/// [`BorrowedMidiEventList::enum_typed_items()`] in order to safely read long messages.
+///
+/// [`BorrowedMidiEventList::enum_typed_items()`]:
+/// struct.BorrowedMidiEventList.html#method.enum_typed_items
 //
-// TODO-medium Support at least reading larger sizes of MIDI messages (by checking the size and
-//  unsafely returning a slice).
 // # Internals exposed: yes | vtable: no
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, RefCast)]
 #[repr(transparent)]
@@ -114,7 +307,26 @@ impl MidiEvent {
         self.0.frame_offset = offset.to_raw();
     }
 
+    /// Returns whether the event contains a long message (e.g. SysEx) which doesn't fit into this
+    /// struct.
+    pub fn is_long(&self) -> bool {
+        self.0.size > 3 || self.0.midi_message[0] == 0xf0
+    }
+
+    /// Returns the short message as byte slice.
+    ///
+    /// In case of a long message, this returns just the bytes contained in this struct, not the
+    /// complete message.
+    pub fn bytes(&self) -> &[u8] {
+        let size = self.0.size.max(0) as usize;
+        &self.0.midi_message[..size.min(self.0.midi_message.len())]
+    }
+
     /// Returns the actual message.
+    ///
+    /// This is always interpreted as short message. Check [`is_long()`] first if you are not sure.
+    ///
+    /// [`is_long()`]: #method.is_long
     pub fn message(&self) -> &MidiMessage {
         MidiMessage::ref_cast(&self.0)
     }
@@ -136,7 +348,11 @@ impl AsRef<raw::MIDI_event_t> for MidiEvent {
 /// An owned MIDI event which can hold more than just the usual 3-byte short MIDI message.
 ///
 /// Has exactly the same layout as [`MidiEvent`](struct.MidiEvent.html) but reserves much more space
-/// for the message.
+/// for the message. Doesn't need heap allocation, so it can be created in real-time threads. If you
+/// need messages longer than [`MAX_LENGTH`], use [`OwnedLongMidiEvent`].
+///
+/// [`MAX_LENGTH`]: #associatedconstant.MAX_LENGTH
+/// [`OwnedLongMidiEvent`]: struct.OwnedLongMidiEvent.html
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 #[repr(C)]
 pub struct LongMidiEvent {
@@ -147,8 +363,7 @@ pub struct LongMidiEvent {
 
 impl LongMidiEvent {
     /// The maximum message length.
-    // TODO-medium What's a good maximum value? This seems too low. Attention: An array of that size
-    // will be created!
+    // Attention: An array of that size will be created!
     pub const MAX_LENGTH: usize = 256;
 
     /// Creates a long MIDI event directly from an owned byte array.
@@ -198,6 +413,114 @@ impl AsRef<raw::MIDI_event_t> for LongMidiEvent {
     }
 }
 
+/// An owned MIDI event which can hold a message of arbitrary length, e.g. a multi-kilobyte SysEx
+/// dump.
+///
+/// The event is stored on the heap with exactly the same layout as REAPER expects for
+/// [`MidiEvent`](struct.MidiEvent.html), so it can be passed to [`MidiOutput::send_msg()`] and
+/// [`BorrowedMidiEventList::add_item()`]. Because creating it and growing its message involve
+/// allocation, you should create it in advance (e.g. using [`with_capacity()`]) if you want to use
+/// it in real-time threads.
+///
+/// [`MidiOutput::send_msg()`]: struct.MidiOutput.html#method.send_msg
+/// [`BorrowedMidiEventList::add_item()`]: struct.BorrowedMidiEventList.html#method.add_item
+/// [`with_capacity()`]: #method.with_capacity
+#[derive(Clone, Debug)]
+pub struct OwnedLongMidiEvent {
+    // We use 4-byte words in order to satisfy the alignment of `MIDI_event_t`. The first word is
+    // the frame offset, the second one the message size, the rest is the message.
+    words: Vec<i32>,
+}
+
+impl OwnedLongMidiEvent {
+    const HEADER_WORD_COUNT: usize = 2;
+
+    /// Creates a long MIDI event by copying the given message.
+    pub fn new(frame_offset: MidiFrameOffset, midi_message: &[u8]) -> Self {
+        let mut evt = Self::with_capacity(midi_message.len());
+        evt.set_frame_offset(frame_offset);
+        evt.set_bytes(midi_message);
+        evt
+    }
+
+    /// Creates an empty long MIDI event with enough space for a message of the given length.
+    pub fn with_capacity(message_capacity: usize) -> Self {
+        let word_count = Self::HEADER_WORD_COUNT + Self::required_word_count(message_capacity);
+        Self {
+            words: vec![0; word_count],
+        }
+    }
+
+    /// Returns the frame offset.
+    pub fn frame_offset(&self) -> MidiFrameOffset {
+        MidiFrameOffset::new(self.words[0] as u32)
+    }
+
+    /// Sets the frame offset.
+    pub fn set_frame_offset(&mut self, offset: MidiFrameOffset) {
+        self.words[0] = offset.to_raw();
+    }
+
+    /// Returns the message length in bytes.
+    pub fn size(&self) -> u32 {
+        self.words[1] as u32
+    }
+
+    /// Returns the contained MIDI data as byte slice.
+    pub fn bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.message_ptr(), self.size() as usize) }
+    }
+
+    /// Replaces the message with the given one.
+    ///
+    /// Only allocates if the given message is longer than the current capacity.
+    pub fn set_bytes(&mut self, midi_message: &[u8]) {
+        let word_count = Self::HEADER_WORD_COUNT + Self::required_word_count(midi_message.len());
+        if self.words.len() < word_count {
+            self.words.resize(word_count, 0);
+        }
+        self.words[1] = midi_message.len() as i32;
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                midi_message.as_ptr(),
+                self.message_ptr() as *mut u8,
+                midi_message.len(),
+            );
+        }
+    }
+
+    fn message_ptr(&self) -> *const u8 {
+        unsafe { self.words.as_ptr().add(Self::HEADER_WORD_COUNT) as *const u8 }
+    }
+
+    fn required_word_count(message_length: usize) -> usize {
+        // `MIDI_event_t` always reserves space for at least 4 message bytes.
+        (message_length.max(4) + 3) / 4
+    }
+}
+
+// The buffer can contain stale bytes after the message, so only the relevant parts are compared.
+impl PartialEq for OwnedLongMidiEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.frame_offset() == other.frame_offset() && self.bytes() == other.bytes()
+    }
+}
+
+impl Eq for OwnedLongMidiEvent {}
+
+impl Hash for OwnedLongMidiEvent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.frame_offset().hash(state);
+        self.bytes().hash(state);
+    }
+}
+
+impl AsRef<raw::MIDI_event_t> for OwnedLongMidiEvent {
+    fn as_ref(&self) -> &raw::MIDI_event_t {
+        unsafe { &*(self.words.as_ptr() as *const raw::MIDI_event_t) }
+    }
+}
+
 struct EnumItems<'a> {
     raw_list: &'a raw::MIDI_eventlist,
     bpos: i32,
@@ -246,8 +569,12 @@ pub struct MidiOutput(pub(crate) NonNull<raw::midi_Output>);
 impl MidiOutput {
     /// Sends the given arbitrary MIDI message to this device at the given time.
     ///
+    /// Long messages such as SysEx dumps of arbitrary length can be sent by passing an
+    /// [`OwnedLongMidiEvent`].
+    ///
     /// This must only be called in the real-time audio thread! See [`get_midi_output()`].
     ///
+    /// [`OwnedLongMidiEvent`]: struct.OwnedLongMidiEvent.html
     /// [`get_midi_output()`]: struct.Reaper.html#method.get_midi_output
     pub fn send_msg(&self, msg: impl AsRef<raw::MIDI_event_t>, time: SendMidiTime) {
         unsafe {
@@ -271,3 +598,81 @@ impl MidiOutput {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    #[test]
+    fn owned_long_midi_event_layout() {
+        // Given
+        let msg = [0xf0, 0x01, 0x02, 0x03, 0x04, 0x05, 0xf7];
+        // When
+        let evt = OwnedLongMidiEvent::new(MidiFrameOffset::new(5), &msg);
+        // Then
+        let raw: &raw::MIDI_event_t = evt.as_ref();
+        assert_eq!(raw.frame_offset, 5);
+        assert_eq!(raw.size, 7);
+        assert_eq!(raw.midi_message, [0xf0, 0x01, 0x02, 0x03]);
+        assert_eq!(evt.bytes(), &msg);
+    }
+
+    #[test]
+    fn owned_long_midi_event_reserves_at_least_4_message_bytes() {
+        // When
+        let evt = OwnedLongMidiEvent::with_capacity(0);
+        // Then
+        assert_eq!(evt.words.len(), 3);
+        assert_eq!(evt.size(), 0);
+        assert_eq!(evt.bytes(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn owned_long_midi_event_round_trip() {
+        // Given
+        let mut evt = OwnedLongMidiEvent::with_capacity(2);
+        // When
+        evt.set_frame_offset(MidiFrameOffset::new(1000));
+        evt.set_bytes(&[0xf0, 0x7e, 0x7f, 0x09, 0x01, 0xf7]);
+        // Then
+        assert_eq!(evt.frame_offset(), MidiFrameOffset::new(1000));
+        assert_eq!(evt.bytes(), &[0xf0, 0x7e, 0x7f, 0x09, 0x01, 0xf7]);
+    }
+
+    #[test]
+    fn owned_long_midi_event_ignores_stale_bytes() {
+        // Given
+        let mut evt = OwnedLongMidiEvent::new(MidiFrameOffset::new(0), &[0xf0; 12]);
+        let fresh = OwnedLongMidiEvent::new(MidiFrameOffset::new(0), &[0x90, 0x40, 0x7f]);
+        // When
+        evt.set_bytes(&[0x90, 0x40, 0x7f]);
+        // Then
+        assert_eq!(evt.bytes(), &[0x90, 0x40, 0x7f]);
+        assert_eq!(evt, fresh);
+        assert_eq!(hash(&evt), hash(&fresh));
+    }
+
+    #[test]
+    fn borrowed_long_midi_event_compares_complete_message() {
+        // Given
+        let a = OwnedLongMidiEvent::new(MidiFrameOffset::new(0), &[0xf0, 1, 2, 3, 4, 0xf7]);
+        let b = OwnedLongMidiEvent::new(MidiFrameOffset::new(0), &[0xf0, 1, 2, 3, 5, 0xf7]);
+        let c = OwnedLongMidiEvent::new(MidiFrameOffset::new(0), &[0xf0, 1, 2, 3, 4, 0xf7]);
+        // When
+        let a = BorrowedLongMidiEvent::ref_cast(a.as_ref());
+        let b = BorrowedLongMidiEvent::ref_cast(b.as_ref());
+        let c = BorrowedLongMidiEvent::ref_cast(c.as_ref());
+        // Then
+        assert_ne!(a, b);
+        assert_ne!(hash(a), hash(b));
+        assert_eq!(a, c);
+        assert_eq!(hash(a), hash(c));
+    }
+
+    fn hash(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+}
@@ -6,6 +6,8 @@ use crate::{
 use crate::util::concat_reaper_strs;
 use helgoboss_midi::{U14, U7};
 use reaper_low::raw;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::num::NonZeroU32;
@@ -14,6 +16,7 @@ use std::ptr::{null_mut, NonNull};
 
 /// Determines the behavior when adding an FX.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AddFxBehavior {
     /// Adds the FX only if it hasn't been found in the FX chain.
     AddIfNotFound,
@@ -33,6 +36,7 @@ impl From<AddFxBehavior> for FxAddByNameBehavior {
 
 /// Represents the type of a track FX chain.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackFxChainType {
     /// The normal (or output) FX chain.
     NormalFxChain,
@@ -47,6 +51,7 @@ pub enum TrackFxChainType {
 /// They are linked by default in REAPER so users might not even be aware that there's a
 /// difference, but there is.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TimeRangeType {
     /// The loop points (displayed in the ruler).
     LoopPoints,
@@ -56,6 +61,7 @@ pub enum TimeRangeType {
 
 /// Describes whether to allow auto-seek or not.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AutoSeekBehavior {
     /// Prevents auto-seek from happening when setting loop points.
     DenyAutoSeek,
@@ -65,6 +71,7 @@ pub enum AutoSeekBehavior {
 
 /// Determines how to deal with the master track.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MasterTrackBehavior {
     /// Without master track.
     ExcludeMasterTrack,
@@ -74,6 +81,7 @@ pub enum MasterTrackBehavior {
 
 /// Something which refers to a certain marker or region.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BookmarkRef {
     /// Counts only regions or only markers depending on the usage context.
     Position(NonZeroU32),
@@ -99,6 +107,7 @@ impl BookmarkRef {
 ///
 /// Has implications on both performance and chunk content.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ChunkCacheHint {
     /// This takes longer but is the best choice for most situations.
     NormalMode,
@@ -145,6 +154,7 @@ impl<T: Copy + Into<f64>> ValueChange<T> {
 
 /// Determines whether to create an undo point.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UndoBehavior {
     /// Doesn't create an undo point.
     OmitUndoPoint,
@@ -154,6 +164,7 @@ pub enum UndoBehavior {
 
 /// Determines whether to import MIDI as in-project MIDI events or not.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MidiImportBehavior {
     /// Uses the relevant REAPER preference.
     UsePreference,
@@ -163,6 +174,7 @@ pub enum MidiImportBehavior {
 
 /// Determines whether to copy or move something (e.g. an FX).
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransferBehavior {
     /// Copies the thing.
     Copy,
@@ -172,6 +184,7 @@ pub enum TransferBehavior {
 
 /// Determines how track defaults should be used.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackDefaultsBehavior {
     /// Creates the track without default envelopes and FX.
     ///
@@ -183,6 +196,7 @@ pub enum TrackDefaultsBehavior {
 
 /// Determines the gang behavior.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GangBehavior {
     /// Change will affect the targeted track only.
     DenyGang,
@@ -192,6 +206,7 @@ pub enum GangBehavior {
 
 /// Defines whether a track is armed for recording.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RecordArmMode {
     /// Track is not armed for recording.
     Unarmed,
@@ -212,6 +227,7 @@ impl RecordArmMode {
 
 /// Defines whether to align with measure starts when playing previews.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MeasureAlignment {
     /// Plays immediately.
     PlayImmediately,
@@ -232,6 +248,7 @@ impl MeasureAlignment {
 
 /// Determines if and how to show/hide a FX user interface.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FxShowInstruction {
     /// Closes the complete FX chain.
     HideChain(TrackFxChainType),
@@ -274,8 +291,46 @@ impl FxShowInstruction {
     }
 }
 
+/// Determines if and how to show/hide a take FX user interface.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TakeFxShowInstruction {
+    /// Closes the complete FX chain.
+    HideChain,
+    /// Shows the complete FX chain and makes the FX at the given index visible.
+    ShowChain(u32),
+    /// Closes the floating FX window of the FX at the given index.
+    HideFloatingWindow(u32),
+    /// Shows the floating FX window of the FX at the given index.
+    ShowFloatingWindow(u32),
+}
+
+impl TakeFxShowInstruction {
+    /// Converts the instruction part of this value to a `showFlag` integer as expected by the
+    /// low-level API.
+    pub fn instruction_to_raw(&self) -> i32 {
+        use TakeFxShowInstruction::*;
+        match self {
+            HideChain => 0,
+            ShowChain(_) => 1,
+            HideFloatingWindow(_) => 2,
+            ShowFloatingWindow(_) => 3,
+        }
+    }
+
+    /// Converts the FX index part of this value to an integer as expected by the low-level API.
+    pub fn fx_index_to_raw(&self) -> i32 {
+        use TakeFxShowInstruction::*;
+        match self {
+            HideChain => 0,
+            ShowChain(i) | HideFloatingWindow(i) | ShowFloatingWindow(i) => *i as i32,
+        }
+    }
+}
+
 /// Defines whether you are referring to a send or a receive.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackSendDirection {
     /// You are referring to a receive (a send from the other track's perspective).
     Receive,
@@ -285,6 +340,7 @@ pub enum TrackSendDirection {
 
 /// Defines the kind of route.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackSendCategory {
     /// A receive from another track (a send from that other track's perspective).
     Receive = -1,
@@ -308,6 +364,7 @@ impl TrackSendCategory {
 
 /// Defines an edit mode for changing send volume or pan.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum EditMode {
     /// An instant edit such as reset via double-clicking a fader or typing a value in an edit
     /// field.
@@ -342,6 +399,7 @@ impl From<TrackSendDirection> for TrackSendCategory {
 
 /// Reference to a track send, hardware output send or track receive.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackSendRef {
     /// A receive from another track (a send from that other track's perspective).
     Receive(u32),
@@ -363,6 +421,7 @@ impl TrackSendRef {
 
 /// Determines where to route a MIDI message.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum StuffMidiMessageTarget {
     /// Routes the message to REAPER's virtual MIDI keyboard.
     VirtualMidiKeyboardQueue,
@@ -393,6 +452,7 @@ impl StuffMidiMessageTarget {
 ///
 /// This is not a stable identifier because track FX locations can change!
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackFxLocation {
     /// FX index in the normal FX chain.
     NormalFxChain(u32),
@@ -653,6 +713,14 @@ pub enum RegistrationObject<'a> {
     /// (IReaperControlSurface*)instance
     /// </pre>
     CsurfInst(NonNull<raw::IReaperControlSurface>),
+    /// A PCM source type (e.g. a custom media decoder).
+    ///
+    /// Extract from `reaper_plugin.h`:
+    ///
+    /// <pre>
+    /// register with Register("pcmsrc",&struct ... and unregister with "-pcmsrc"
+    /// </pre>
+    PcmSrc(NonNull<raw::pcmsrc_register_t>),
     /// If a variant is missing in this enum, you can use this custom one as a resort.
     ///
     /// Use [`custom()`] to create this variant.
@@ -750,6 +818,10 @@ impl<'a> RegistrationObject<'a> {
                 key: reaper_str!("csurf_inst").into(),
                 value: inst.as_ptr() as _,
             },
+            PcmSrc(reg) => PluginRegistration {
+                key: reaper_str!("pcmsrc").into(),
+                value: reg.as_ptr() as _,
+            },
             Custom(key, value) => PluginRegistration {
                 key: key.into_owned().into(),
                 value,
@@ -773,6 +845,7 @@ pub(crate) struct PluginRegistration {
 
 /// Type and location of a certain track.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrackLocation {
     /// The master track of a project.
     MasterTrack,
@@ -782,6 +855,7 @@ pub enum TrackLocation {
 
 /// Describes whether and how the recording input is monitored.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum InputMonitoringMode {
     /// No input monitoring.
     Off,
@@ -820,6 +894,7 @@ impl InputMonitoringMode {
 
 /// Track solo mode.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SoloMode {
     Off,
     SoloIgnoreRouting,
@@ -855,6 +930,7 @@ impl SoloMode {
 
 /// Information about visibility of an FX chain.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FxChainVisibility {
     /// FX chain is not visible.
     Hidden,
@@ -881,6 +957,7 @@ impl FxChainVisibility {
 
 /// Track pan mode.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PanMode {
     /// Classic v1 - v3.
     BalanceV1,
@@ -923,6 +1000,7 @@ impl PanMode {
 
 /// Track pan.
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pan {
     /// Classic v1 - v3.
     BalanceV1(ReaperPanValue),
@@ -945,6 +1023,7 @@ pub enum Pan {
 
 /// Something which refers to a certain project.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ProjectRef {
     /// Project in the currently open tab.
     Current,
@@ -968,6 +1047,7 @@ impl ProjectRef {
 
 /// Something which refers to a certain FX preset.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FxPresetRef {
     /// Factory preset for that FX.
     FactoryPreset,
@@ -1121,6 +1201,7 @@ impl WindowContext {
 
 /// Defines which action will be preselected when prompting for an action.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum InitialAction {
     /// No action will be preselected.
     NoneSelected,
@@ -1141,6 +1222,7 @@ impl InitialAction {
 
 /// Possible result when prompting for an action.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PromptForActionResult {
     /// Action window is no longer available.
     ActionWindowGone,
@@ -1168,6 +1250,7 @@ impl PromptForActionResult {
 
 /// Decides when a MIDI message will be sent.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SendMidiTime {
     /// MIDI message will be sent instantly.
     Instantly,
@@ -1185,3 +1268,46 @@ impl SendMidiTime {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::AutomationMode;
+
+    #[test]
+    fn serialized_forms() {
+        assert_eq!(
+            serde_json::to_string(&TransferBehavior::Copy).unwrap(),
+            r#""Copy""#
+        );
+        assert_eq!(
+            serde_json::to_string(&AutomationMode::TrimRead).unwrap(),
+            r#""TrimRead""#
+        );
+        assert_eq!(
+            serde_json::to_string(&TrackSendCategory::Receive).unwrap(),
+            r#""Receive""#
+        );
+        assert_eq!(
+            serde_json::to_string(&TrackFxLocation::InputFxChain(3)).unwrap(),
+            r#"{"InputFxChain":3}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&TrackLocation::MasterTrack).unwrap(),
+            r#""MasterTrack""#
+        );
+        assert_eq!(
+            serde_json::to_string(&TrackLocation::NormalTrack(1)).unwrap(),
+            r#"{"NormalTrack":1}"#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            serde_json::from_str::<TrackFxLocation>(r#"{"NormalFxChain":2}"#).unwrap(),
+            TrackFxLocation::NormalFxChain(2)
+        );
+        assert!(serde_json::from_str::<TransferBehavior>(r#""Clone""#).is_err());
+    }
+}
@@ -1197,6 +1197,21 @@ impl<'a> Display for ReaperVersion<'a> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'a> Serialize for ReaperVersion<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.to_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ReaperVersion<'static> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        Ok(ReaperVersion::new(expression))
+    }
+}
+
 /// A MIDI frame offset.
 ///
 /// This is a 1/1024000 of a second, *not* a sample frame!
@@ -1226,4 +1241,42 @@ impl MidiFrameOffset {
 /// It's mainly used inside `Unknown` variants in order to enable forward compatibility without
 /// information loss.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Hidden<T>(pub(crate) T);
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_forms() {
+        assert_eq!(serde_json::to_string(&CommandId::new(42)).unwrap(), "42");
+        assert_eq!(
+            serde_json::to_string(&NativeColor::new(16576)).unwrap(),
+            "16576"
+        );
+        assert_eq!(serde_json::to_string(&Bpm::new(120.5)).unwrap(), "120.5");
+        assert_eq!(serde_json::to_string(&Db::new(-6.0)).unwrap(), "-6.0");
+        assert_eq!(
+            serde_json::to_string(&PositionInSeconds::new(1.5)).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            serde_json::to_string(&ReaperVersion::new("6.12/x64")).unwrap(),
+            r#""6.12/x64""#
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        // When
+        let version: ReaperVersion = serde_json::from_str(r#""6.12/x64""#).unwrap();
+        let db: Db = serde_json::from_str("-6.0").unwrap();
+        // Then
+        assert_eq!(version, ReaperVersion::new("6.12/x64"));
+        assert_eq!(db, Db::new(-6.0));
+        assert!(serde_json::from_str::<CommandId>("0").is_err());
+        assert!(serde_json::from_str::<Bpm>("0.5").is_err());
+        assert!(serde_json::from_str::<Db>("-1001.0").is_err());
+    }
+}
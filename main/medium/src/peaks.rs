@@ -0,0 +1,323 @@
+use crate::{BorrowedPcmSourcePeakTransfer, Hidden};
+use reaper_low::raw;
+use std::ptr::NonNull;
+
+/// Owned peak getter.
+///
+/// This is REAPER's reader for `.reapeaks` files. It's usually used by PCM source implementations
+/// to answer peak requests. Automatically destroys the associated C++ `REAPER_PeakGet_Interface`
+/// when dropped.
+//
+// Case 3: Internals exposed: no | vtable: yes
+// ===========================================
+//
+// A borrowed version is not necessary for now because peak getters are never handed out by REAPER
+// except as return value of `PeakGet_Create()`.
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub struct OwnedPeakGetter(NonNull<raw::REAPER_PeakGet_Interface>);
+
+impl OwnedPeakGetter {
+    /// Takes ownership of the given peak getter.
+    ///
+    /// # Safety
+    ///
+    /// You must guarantee that the given peak getter is currently owner-less, otherwise double-free
+    /// or use-after-free can occur.
+    pub unsafe fn new_unchecked(inner: NonNull<raw::REAPER_PeakGet_Interface>) -> Self {
+        Self(inner)
+    }
+
+    /// Returns the pointer to this peak getter.
+    pub fn as_ptr(&self) -> NonNull<raw::REAPER_PeakGet_Interface> {
+        self.0
+    }
+
+    /// Returns the maximum resolution (in peaks per second) available in the peak file.
+    pub fn get_max_peak_res(&self) -> f64 {
+        unsafe { self.0.as_ref().GetMaxPeakRes() }
+    }
+
+    /// Fills the given peak transfer with peaks from the peak file.
+    pub fn get_peak_info(&self, block: &BorrowedPcmSourcePeakTransfer) {
+        unsafe {
+            self.0.as_ref().GetPeakInfo(block.as_ptr().as_ptr());
+        }
+    }
+}
+
+impl Drop for OwnedPeakGetter {
+    fn drop(&mut self) {
+        unsafe {
+            reaper_low::delete_cpp_peak_get_interface(self.0);
+        }
+    }
+}
+
+/// Owned peak builder.
+///
+/// This is REAPER's writer for `.reapeaks` files. If it has been created for a PCM source,
+/// calling [`run()`] repeatedly makes REAPER read the samples from that source by itself. If it has
+/// been created without source, the samples must be fed via [`process_samples_interleaved()`].
+/// Automatically destroys the associated C++ `REAPER_PeakBuild_Interface` (and thereby finishes
+/// writing the peak file) when dropped.
+///
+/// [`run()`]: #method.run
+/// [`process_samples_interleaved()`]: #method.process_samples_interleaved
+//
+// Case 3: Internals exposed: no | vtable: yes
+// ===========================================
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub struct OwnedPeakBuilder(NonNull<raw::REAPER_PeakBuild_Interface>);
+
+impl OwnedPeakBuilder {
+    /// Takes ownership of the given peak builder.
+    ///
+    /// # Safety
+    ///
+    /// You must guarantee that the given peak builder is currently owner-less, otherwise
+    /// double-free or use-after-free can occur.
+    pub unsafe fn new_unchecked(inner: NonNull<raw::REAPER_PeakBuild_Interface>) -> Self {
+        Self(inner)
+    }
+
+    /// Returns the pointer to this peak builder.
+    pub fn as_ptr(&self) -> NonNull<raw::REAPER_PeakBuild_Interface> {
+        self.0
+    }
+
+    /// Lets REAPER continue building peaks from the source passed at creation time.
+    ///
+    /// Returns `true` if building should continue.
+    pub fn run(&self) -> bool {
+        unsafe { self.0.as_ref().Run() != 0 }
+    }
+
+    /// Feeds the given interleaved samples into the peak builder.
+    ///
+    /// Only makes sense if this builder has been created without source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel count is zero or if the buffer length is not a multiple of it.
+    pub fn process_samples_interleaved(&self, samples: &[f64], channel_count: u32) {
+        assert!(channel_count > 0, "channel count must not be zero");
+        let channel_count = channel_count as usize;
+        assert_eq!(
+            samples.len() % channel_count,
+            0,
+            "buffer length is not a multiple of the channel count"
+        );
+        let frame_count = samples.len() / channel_count;
+        // REAPER expects one pointer per channel and reads sample i of channel ch at
+        // `samples[ch][i * spread]`. With interleaved samples, that's simply an offset pointer
+        // for each channel and a spread equal to the channel count.
+        let mut channel_ptrs: Vec<*mut raw::ReaSample> = (0..channel_count)
+            .map(|ch| unsafe { samples.as_ptr().add(ch) as *mut _ })
+            .collect();
+        unsafe {
+            self.0.as_ref().ProcessSamples(
+                channel_ptrs.as_mut_ptr(),
+                frame_count as _,
+                channel_count as _,
+                0,
+                channel_count as _,
+            );
+        }
+    }
+
+    /// Copies the peaks of the last second into the given buffer.
+    ///
+    /// Returns the number of peaks written.
+    pub fn get_last_second_peaks(&self, buffer: &mut [f64]) -> u32 {
+        let count = unsafe {
+            self.0
+                .as_ref()
+                .GetLastSecondPeaks(buffer.len() as _, buffer.as_mut_ptr())
+        };
+        if count < 0 { 0 } else { count as u32 }
+    }
+
+    /// Fills the given peak transfer with the peaks built so far.
+    ///
+    /// This won't hit the highest resolution.
+    pub fn get_peak_info(&self, block: &BorrowedPcmSourcePeakTransfer) {
+        unsafe {
+            self.0.as_ref().GetPeakInfo(block.as_ptr().as_ptr());
+        }
+    }
+}
+
+impl Drop for OwnedPeakBuilder {
+    fn drop(&mut self) {
+        unsafe {
+            reaper_low::delete_cpp_peak_build_interface(self.0);
+        }
+    }
+}
+
+/// Additional peak information that can be requested together with the normal peaks.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PeakExtraType {
+    /// Spectral information (frequency and tonality).
+    Spectral,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<i32>),
+}
+
+impl PeakExtraType {
+    /// Converts an integer as returned by the low-level API to a peak extra type.
+    pub fn from_raw(v: i32) -> PeakExtraType {
+        use PeakExtraType::*;
+        match v {
+            115 => Spectral,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        use PeakExtraType::*;
+        match self {
+            // 's'
+            Spectral => 115,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
+
+/// Defines the kind of data in a peak transfer.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PeakTransferMode {
+    /// Normal peaks (maximums and minimums).
+    Peaks,
+    /// Waveform.
+    Waveform,
+    /// MIDI notes.
+    MidiNote,
+    /// MIDI drum notes.
+    MidiDrum,
+    /// MIDI drum notes displayed as triangles.
+    MidiDrumTriangle,
+    /// Represents a variant unknown to *reaper-rs*. Please contribute if you encounter a variant
+    /// that is supported by REAPER but not yet by *reaper-rs*. Thanks!
+    Unknown(Hidden<u32>),
+}
+
+impl PeakTransferMode {
+    /// Converts an integer as returned by the low-level API to a peak transfer mode.
+    pub fn from_raw(v: u32) -> PeakTransferMode {
+        use PeakTransferMode::*;
+        match v {
+            raw::PCM_source_peaktransfer_t_PEAKTRANSFER_PEAKS_MODE => Peaks,
+            raw::PCM_source_peaktransfer_t_PEAKTRANSFER_WAVEFORM_MODE => Waveform,
+            raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_NOTE_MODE => MidiNote,
+            raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_MODE => MidiDrum,
+            raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_TRIANGLE_MODE => MidiDrumTriangle,
+            x => Unknown(Hidden(x)),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> u32 {
+        use PeakTransferMode::*;
+        match self {
+            Peaks => raw::PCM_source_peaktransfer_t_PEAKTRANSFER_PEAKS_MODE,
+            Waveform => raw::PCM_source_peaktransfer_t_PEAKTRANSFER_WAVEFORM_MODE,
+            MidiNote => raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_NOTE_MODE,
+            MidiDrum => raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_MODE,
+            MidiDrumTriangle => raw::PCM_source_peaktransfer_t_PEAKTRANSFER_MIDI_DRUM_TRIANGLE_MODE,
+            Unknown(Hidden(x)) => x,
+        }
+    }
+}
+
+/// The result of getting peaks.
+///
+/// The buffer passed to the function contains the peak samples interleaved by channel, but in two
+/// or three consecutive blocks: maximums, then minimums, then extra (if requested and available).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct GetPeaksResult {
+    /// Number of peak samples returned per channel.
+    pub sample_count: u32,
+    /// The kind of data returned.
+    pub output_mode: PeakTransferMode,
+    /// Whether the requested extra type was available.
+    pub extra_type_available: bool,
+}
+
+impl GetPeaksResult {
+    /// Decodes the return value of `PCM_Source_GetPeaks()` and `GetMediaItemTake_Peaks()`.
+    ///
+    /// The return value has 20 bits of returned sample count, then 4 bits of output mode, then one
+    /// bit which signifies whether the extra type was available.
+    pub fn from_raw(v: i32) -> GetPeaksResult {
+        let v = v as u32;
+        GetPeaksResult {
+            sample_count: v & 0x000f_ffff,
+            output_mode: PeakTransferMode::from_raw((v & 0x00f0_0000) >> 20),
+            extra_type_available: (v & 0x0100_0000) != 0,
+        }
+    }
+}
+
+/// Spectral information of one peak sample.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SpectralPeak {
+    /// Frequency in Hz (15 bits).
+    pub frequency: u16,
+    /// Tonality (14 bits).
+    pub tonality: u16,
+}
+
+impl SpectralPeak {
+    /// Decodes a value in the extra block of a peak buffer which has been requested with
+    /// [`PeakExtraType::Spectral`].
+    ///
+    /// [`PeakExtraType::Spectral`]: enum.PeakExtraType.html#variant.Spectral
+    pub fn from_raw(v: f64) -> SpectralPeak {
+        let v = v as u32;
+        SpectralPeak {
+            frequency: (v & 0x7fff) as u16,
+            tonality: ((v >> 15) & 0x3fff) as u16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_get_peaks_result() {
+        // Given
+        let raw = 0x0110_0200;
+        // When
+        let result = GetPeaksResult::from_raw(raw);
+        // Then
+        assert_eq!(
+            result,
+            GetPeaksResult {
+                sample_count: 0x200,
+                output_mode: PeakTransferMode::Waveform,
+                extra_type_available: true
+            }
+        );
+    }
+
+    #[test]
+    fn decode_spectral_peak() {
+        // Given
+        let raw = ((1234 << 15) | 440) as f64;
+        // When
+        let peak = SpectralPeak::from_raw(raw);
+        // Then
+        assert_eq!(
+            peak,
+            SpectralPeak {
+                frequency: 440,
+                tonality: 1234
+            }
+        );
+    }
+}
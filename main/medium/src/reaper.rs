@@ -12,21 +12,24 @@ use reaper_low::{raw, register_plugin_destroy_hook};
 use crate::ProjectContext::CurrentProject;
 use crate::{
     require_non_null_panic, ActionValueChange, AddFxBehavior, AutoSeekBehavior, AutomationMode,
-    BookmarkId, BookmarkRef, Bpm, ChunkCacheHint, CommandId, Db, DurationInSeconds, EditMode,
-    EnvChunkName, FxAddByNameBehavior, FxChainVisibility, FxPresetRef, FxShowInstruction,
-    GangBehavior, GlobalAutomationModeOverride, Hidden, Hwnd, InitialAction, InputMonitoringMode,
-    KbdSectionInfo, MasterTrackBehavior, MediaItem, MediaItemTake, MediaTrack, MessageBoxResult,
-    MessageBoxType, MidiImportBehavior, MidiInput, MidiInputDeviceId, MidiOutput,
+    BookmarkId, BookmarkRef, BorrowedPcmSourcePeakTransfer, BorrowedPcmSourceTransfer, Bpm,
+    ChunkCacheHint, CommandId, Db, DurationInSeconds, EditMode, EnvChunkName, FxAddByNameBehavior,
+    FxChainVisibility, FxPresetRef, FxShowInstruction, GangBehavior, GetPeaksResult,
+    GlobalAutomationModeOverride, Hidden, Hwnd, Hz, InitialAction, InputMonitoringMode,
+    ItemAttributeKey, KbdSectionInfo, MasterTrackBehavior, MediaItem, MediaItemTake, MediaTrack,
+    MessageBoxResult, MessageBoxType, MidiImportBehavior, MidiInput, MidiInputDeviceId, MidiOutput,
     MidiOutputDeviceId, NativeColor, NormalizedPlayRate, NotificationBehavior, OwnedPcmSource,
-    PanMode, PcmSource, PlaybackSpeedFactor, PluginContext, PositionInBeats, PositionInSeconds,
-    ProjectContext, ProjectRef, PromptForActionResult, ReaProject, ReaperFunctionError,
-    ReaperFunctionResult, ReaperNormalizedFxParamValue, ReaperPanLikeValue, ReaperPanValue,
-    ReaperPointer, ReaperStr, ReaperString, ReaperStringArg, ReaperVersion, ReaperVolumeValue,
-    ReaperWidthValue, RecordArmMode, RecordingInput, SectionContext, SectionId, SendTarget,
-    SoloMode, StuffMidiMessageTarget, TimeRangeType, TrackArea, TrackAttributeKey,
-    TrackDefaultsBehavior, TrackEnvelope, TrackFxChainType, TrackFxLocation, TrackLocation,
-    TrackSendAttributeKey, TrackSendCategory, TrackSendDirection, TrackSendRef, TransferBehavior,
-    UndoBehavior, UndoScope, ValueChange, VolumeSliderValue, WindowContext,
+    OwnedPeakBuilder, OwnedPeakGetter, PanMode, PcmSource, PeakExtraType, PitchShiftMode,
+    PitchShiftSubMode, PitchShifter, PlaybackSpeedFactor, PluginContext, PositionInBeats,
+    PositionInSeconds, ProjectContext, ProjectRef, PromptForActionResult, ReaProject,
+    ReaperFunctionError, ReaperFunctionResult, ReaperNormalizedFxParamValue, ReaperPanLikeValue,
+    ReaperPanValue, ReaperPointer, ReaperStr, ReaperString, ReaperStringArg, ReaperVersion,
+    ReaperVolumeValue, ReaperWidthValue, RecordArmMode, RecordingInput, Resampler, SectionContext,
+    SectionId, SendTarget, SoloMode, StuffMidiMessageTarget, TakeFxShowInstruction, TimeRangeType,
+    TrackArea, TrackAttributeKey, TrackDefaultsBehavior, TrackEnvelope, TrackFxChainType,
+    TrackFxLocation, TrackLocation, TrackSendAttributeKey, TrackSendCategory, TrackSendDirection,
+    TrackSendRef, TransferBehavior, UndoBehavior, UndoScope, ValueChange, VolumeSliderValue,
+    WindowContext,
 };
 
 use helgoboss_midi::ShortMessage;
@@ -1001,6 +1004,32 @@ impl<UsageScope> Reaper<UsageScope> {
         }
     }
 
+    /// Queries the combined play state of all open projects.
+    ///
+    /// A flag is set if it's set in at least one project. If a project is given, it's not taken
+    /// into account. This can be used to cheaply check if any background project is playing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_all_project_play_states(&self, ignore_project: Option<ReaProject>) -> PlayState
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        if let Some(p) = ignore_project {
+            self.require_valid_project(ProjectContext::Proj(p));
+        }
+        let ptr = ignore_project.map(|p| p.as_ptr()).unwrap_or(null_mut());
+        let result = unsafe { self.low.GetAllProjectPlayStates(ptr) } as u32;
+        PlayState {
+            is_playing: result & 1 > 0,
+            is_paused: result & 2 > 0,
+            is_recording: result & 4 > 0,
+        }
+    }
+
     /// Queries the current repeat state.
     ///
     /// # Panics
@@ -1347,6 +1376,118 @@ impl<UsageScope> Reaper<UsageScope> {
         Bpm(bpm)
     }
 
+    /// Returns the number of tempo/time signature markers in the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn count_tempo_time_sig_markers(&self, project: ProjectContext) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe { self.count_tempo_time_sig_markers_unchecked(project) }
+    }
+
+    /// Like [`count_tempo_time_sig_markers()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`count_tempo_time_sig_markers()`]: #method.count_tempo_time_sig_markers
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn count_tempo_time_sig_markers_unchecked(&self, project: ProjectContext) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.CountTempoTimeSigMarkers(project.to_raw()) as u32
+    }
+
+    /// Returns information about the tempo/time signature marker at the given index.
+    ///
+    /// Returns `None` if there's no marker at that index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_tempo_time_sig_marker(
+        &self,
+        project: ProjectContext,
+        marker_index: u32,
+    ) -> Option<GetTempoTimeSigMarkerResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe { self.get_tempo_time_sig_marker_unchecked(project, marker_index) }
+    }
+
+    /// Like [`get_tempo_time_sig_marker()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_tempo_time_sig_marker()`]: #method.get_tempo_time_sig_marker
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_tempo_time_sig_marker_unchecked(
+        &self,
+        project: ProjectContext,
+        marker_index: u32,
+    ) -> Option<GetTempoTimeSigMarkerResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut timepos = MaybeUninit::zeroed();
+        let mut measurepos = MaybeUninit::zeroed();
+        let mut beatpos = MaybeUninit::zeroed();
+        let mut bpm = MaybeUninit::zeroed();
+        let mut timesig_num = MaybeUninit::zeroed();
+        let mut timesig_denom = MaybeUninit::zeroed();
+        let mut lineartempo = MaybeUninit::zeroed();
+        let successful = self.low.GetTempoTimeSigMarker(
+            project.to_raw(),
+            marker_index as i32,
+            timepos.as_mut_ptr(),
+            measurepos.as_mut_ptr(),
+            beatpos.as_mut_ptr(),
+            bpm.as_mut_ptr(),
+            timesig_num.as_mut_ptr(),
+            timesig_denom.as_mut_ptr(),
+            lineartempo.as_mut_ptr(),
+        );
+        if !successful {
+            return None;
+        }
+        // A numerator of 0 means that the marker doesn't change the time signature
+        let time_signature = match (
+            NonZeroU32::new(timesig_num.assume_init().max(0) as u32),
+            NonZeroU32::new(timesig_denom.assume_init().max(0) as u32),
+        ) {
+            (Some(numerator), Some(denominator)) => Some(TimeSignature {
+                numerator,
+                denominator,
+            }),
+            _ => None,
+        };
+        let result = GetTempoTimeSigMarkerResult {
+            position: PositionInSeconds::new(timepos.assume_init()),
+            measure_index: measurepos.assume_init(),
+            beats_since_measure: PositionInBeats::new(beatpos.assume_init()),
+            tempo: Bpm(bpm.assume_init()),
+            time_signature,
+            linear_tempo: lineartempo.assume_init(),
+        };
+        Some(result)
+    }
+
     /// Returns the current position of the edit cursor.
     ///
     /// # Panics
@@ -3578,804 +3719,2024 @@ impl<UsageScope> Reaper<UsageScope> {
         ReaperNormalizedFxParamValue::new(raw_value)
     }
 
-    /// Returns the master track of the given project.
+    /// Gets the number of FX instances on the given take's FX chain.
     ///
-    /// # Panics
+    /// # Safety
     ///
-    /// Panics if the given project is not valid anymore.
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn get_master_track(&self, project: ProjectContext) -> MediaTrack
+    pub unsafe fn take_fx_get_count(&self, take: MediaItemTake) -> u32
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.require_valid_project(project);
-        unsafe { self.get_master_track_unchecked(project) }
+        self.low.TakeFX_GetCount(take.as_ptr()) as u32
     }
 
-    /// Like [`get_master_track()`] but doesn't check if project is valid.
+    /// Returns the GUID of the given take FX.
     ///
-    /// # Safety
+    /// # Errors
     ///
-    /// REAPER can crash if you pass an invalid project.
+    /// Returns an error if the FX doesn't exist.
     ///
-    /// [`get_master_track()`]: #method.get_master_track
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_master_track_unchecked(&self, project: ProjectContext) -> MediaTrack
+    pub unsafe fn take_fx_get_fx_guid(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+    ) -> ReaperFunctionResult<GUID>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.low.GetMasterTrack(project.to_raw());
-        require_non_null_panic(ptr)
-    }
-
-    /// Converts the given GUID to a string (including braces).
-    #[measure(ResponseTimeMultiThreaded)]
-    pub fn guid_to_string(&self, guid: &GUID) -> ReaperString
-    where
-        UsageScope: AnyThread,
-    {
-        let (guid_string, _) = with_string_buffer(64, |buffer, _| unsafe {
-            self.low.guidToString(guid as *const GUID, buffer)
-        });
-        guid_string
+        let ptr = self.low.TakeFX_GetFXGUID(take.as_ptr(), fx_index as i32);
+        deref(ptr).ok_or_else(|| {
+            ReaperFunctionError::new("couldn't get FX GUID (probably FX doesn't exist)")
+        })
     }
 
-    /// Returns the project recording path.
+    /// Returns the number of parameters of the given take FX.
     ///
-    /// With `buffer_size` you can tell REAPER how many bytes of the resulting path you want.
+    /// If the FX doesn't exist, returns 0.
     ///
-    /// # Panics
+    /// # Safety
     ///
-    /// Panics if the given buffer size is 0.
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn get_project_path_ex(&self, project: ProjectContext, buffer_size: u32) -> PathBuf
+    pub unsafe fn take_fx_get_num_params(&self, take: MediaItemTake, fx_index: u32) -> u32
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_valid_project(project);
-        unsafe { self.get_project_path_ex_unchecked(project, buffer_size) }
+        self.require_main_thread();
+        self.low.TakeFX_GetNumParams(take.as_ptr(), fx_index as i32) as u32
     }
 
-    /// Like [`get_project_path_ex()`] but doesn't check if project is valid.
+    /// Returns the current value of the given take FX parameter in REAPER-normalized form.
+    ///
+    /// See [`track_fx_get_param_normalized()`] for the meaning of values lower than zero.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid project.
+    /// REAPER can crash if you pass an invalid take.
     ///
-    /// [`get_project_path_ex()`]: #method.get_project_path_ex
+    /// [`track_fx_get_param_normalized()`]: #method.track_fx_get_param_normalized
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_project_path_ex_unchecked(
+    pub unsafe fn take_fx_get_param_normalized(
         &self,
-        project: ProjectContext,
-        buffer_size: u32,
-    ) -> PathBuf
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+    ) -> ReaperNormalizedFxParamValue
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let (reaper_string, _) = with_string_buffer(buffer_size, |buffer, max_size| {
+        let raw_value =
             self.low
-                .GetProjectPathEx(project.to_raw(), buffer, max_size)
-        });
-        let owned_string = reaper_string.into_string();
-        PathBuf::from(owned_string)
-    }
-
-    /// Returns the master tempo of the current project.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub fn master_get_tempo(&self) -> Bpm
-    where
-        UsageScope: MainThreadOnly,
-    {
-        self.require_main_thread();
-        Bpm(self.low.Master_GetTempo())
+                .TakeFX_GetParamNormalized(take.as_ptr(), fx_index as i32, param_index as i32);
+        ReaperNormalizedFxParamValue::new(raw_value)
     }
 
-    /// Sets the current tempo of the given project.
+    /// Returns the index of the first FX instance in the given take's FX chain.
     ///
-    /// # Panics
+    /// See [`track_fx_add_by_name_query()`] for possible FX name prefixes.
     ///
-    /// Panics if the given project is not valid anymore.
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_add_by_name_query()`]: #method.track_fx_add_by_name_query
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn set_current_bpm(&self, project: ProjectContext, tempo: Bpm, undo_behavior: UndoBehavior)
+    pub unsafe fn take_fx_add_by_name_query<'a>(
+        &self,
+        take: MediaItemTake,
+        fx_name: impl Into<ReaperStringArg<'a>>,
+    ) -> Option<u32>
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_valid_project(project);
-        unsafe {
-            self.set_current_bpm_unchecked(project, tempo, undo_behavior);
+        self.require_main_thread();
+        let index = self.low.TakeFX_AddByName(
+            take.as_ptr(),
+            fx_name.into().as_ptr(),
+            FxAddByNameBehavior::Query.to_raw(),
+        );
+        match index {
+            -1 => None,
+            idx if idx >= 0 => Some(idx as u32),
+            _ => unreachable!(),
         }
     }
 
-    /// Like [`set_current_bpm()`] but doesn't check if project is valid.
+    /// Adds an instance of an FX to the given take's FX chain.
+    ///
+    /// See [`track_fx_add_by_name_query()`] for possible FX name prefixes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX couldn't be added (e.g. if no such FX is installed).
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid project.
+    /// REAPER can crash if you pass an invalid take.
     ///
-    /// [`set_current_bpm()`]: #method.set_current_bpm
+    /// [`track_fx_add_by_name_query()`]: #method.track_fx_add_by_name_query
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn set_current_bpm_unchecked(
+    pub unsafe fn take_fx_add_by_name_add<'a>(
         &self,
-        project: ProjectContext,
-        tempo: Bpm,
-        undo_behavior: UndoBehavior,
-    ) where
+        take: MediaItemTake,
+        fx_name: impl Into<ReaperStringArg<'a>>,
+        behavior: AddFxBehavior,
+    ) -> ReaperFunctionResult<u32>
+    where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.low.SetCurrentBPM(
-            project.to_raw(),
-            tempo.get(),
-            undo_behavior == UndoBehavior::AddUndoPoint,
-        );
+        let behavior: FxAddByNameBehavior = behavior.into();
+        let index =
+            self.low
+                .TakeFX_AddByName(take.as_ptr(), fx_name.into().as_ptr(), behavior.to_raw());
+        match index {
+            -1 => Err(ReaperFunctionError::new("FX couldn't be added")),
+            idx if idx >= 0 => Ok(idx as u32),
+            _ => unreachable!(),
+        }
     }
 
-    /// Converts the given playback speed factor to a normalized play rate.
+    /// Returns whether the given take FX is enabled.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn master_normalize_play_rate_normalize(
-        &self,
-        value: PlaybackSpeedFactor,
-    ) -> NormalizedPlayRate
+    pub unsafe fn take_fx_get_enabled(&self, take: MediaItemTake, fx_index: u32) -> bool
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let result = self.low.Master_NormalizePlayRate(value.get(), false);
-        NormalizedPlayRate::new(result)
+        self.low.TakeFX_GetEnabled(take.as_ptr(), fx_index as i32)
     }
 
-    /// Converts the given normalized play rate to a playback speed factor.
+    /// Enables or disables a take FX.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn master_normalize_play_rate_denormalize(
-        &self,
-        value: NormalizedPlayRate,
-    ) -> PlaybackSpeedFactor
+    pub unsafe fn take_fx_set_enabled(&self, take: MediaItemTake, fx_index: u32, is_enabled: bool)
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let result = self.low.Master_NormalizePlayRate(value.get(), true);
-        PlaybackSpeedFactor::new(result)
+        self.low
+            .TakeFX_SetEnabled(take.as_ptr(), fx_index as i32, is_enabled);
     }
 
-    /// Returns the master play rate of the given project.
+    /// Returns the name of the given take FX.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the FX name you want.
     ///
     /// # Panics
     ///
-    /// Panics if the given project is not valid anymore.
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn master_get_play_rate(&self, project: ProjectContext) -> PlaybackSpeedFactor
+    pub unsafe fn take_fx_get_fx_name(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<ReaperString>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.require_valid_project(project);
-        unsafe { self.master_get_play_rate_unchecked(project) }
+        assert!(buffer_size > 0);
+        let (name, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low
+                .TakeFX_GetFXName(take.as_ptr(), fx_index as i32, buffer, max_size)
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't get FX name (probably FX doesn't exist)",
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Returns the name of the given take FX parameter.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the parameter name you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX or parameter doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_param_name(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (name, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.TakeFX_GetParamName(
+                take.as_ptr(),
+                fx_index as i32,
+                param_index as i32,
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't get FX parameter name (probably FX or parameter doesn't exist)",
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Returns the current value of the given take FX parameter formatted as string.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the parameter value string you
+    /// want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX or parameter doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_formatted_param_value(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (name, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.TakeFX_GetFormattedParamValue(
+                take.as_ptr(),
+                fx_index as i32,
+                param_index as i32,
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't format current FX parameter value (probably FX or parameter doesn't exist)",
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Returns the given value formatted as string according to the given take FX parameter.
+    ///
+    /// See [`track_fx_format_param_value_normalized()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX or parameter doesn't exist or if the FX doesn't support
+    /// formatting arbitrary parameter values.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_format_param_value_normalized()`]: #method.track_fx_format_param_value_normalized
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_format_param_value_normalized(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+        param_value: ReaperNormalizedFxParamValue,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (name, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.TakeFX_FormatParamValueNormalized(
+                take.as_ptr(),
+                fx_index as i32,
+                param_index as i32,
+                param_value.get(),
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't format FX parameter value (FX maybe doesn't support Cockos extensions or FX or parameter doesn't exist)",
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Sets the value of the given take FX parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX or parameter doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_set_param_normalized(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+        param_value: ReaperNormalizedFxParamValue,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.TakeFX_SetParamNormalized(
+            take.as_ptr(),
+            fx_index as i32,
+            param_index as i32,
+            param_value.get(),
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set FX parameter value (probably FX or parameter doesn't exist)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns information about the given take FX parameter's step sizes.
+    ///
+    /// See [`track_fx_get_parameter_step_sizes()`] for details.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_get_parameter_step_sizes()`]: #method.track_fx_get_parameter_step_sizes
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_parameter_step_sizes(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+    ) -> Option<GetParameterStepSizesResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        // Zeroed for the same reason as in track_fx_get_parameter_step_sizes()
+        let mut step = MaybeUninit::zeroed();
+        let mut small_step = MaybeUninit::zeroed();
+        let mut large_step = MaybeUninit::zeroed();
+        let mut is_toggle = MaybeUninit::zeroed();
+        let successful = self.low.TakeFX_GetParameterStepSizes(
+            take.as_ptr(),
+            fx_index as i32,
+            param_index as i32,
+            step.as_mut_ptr(),
+            small_step.as_mut_ptr(),
+            large_step.as_mut_ptr(),
+            is_toggle.as_mut_ptr(),
+        );
+        if !successful {
+            return None;
+        }
+        let is_toggle = is_toggle.assume_init();
+        if is_toggle {
+            Some(GetParameterStepSizesResult::Toggle)
+        } else {
+            Some(GetParameterStepSizesResult::Normal {
+                normal_step: step.assume_init(),
+                small_step: make_some_if_greater_than_zero(small_step.assume_init()),
+                large_step: make_some_if_greater_than_zero(large_step.assume_init()),
+            })
+        }
+    }
+
+    /// Returns the current value and min/mid/max values of the given take FX parameter.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_param_ex(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_index: u32,
+    ) -> GetParamExResult
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut min_val = MaybeUninit::uninit();
+        let mut max_val = MaybeUninit::uninit();
+        let mut mid_val = MaybeUninit::uninit();
+        let value = self.low.TakeFX_GetParamEx(
+            take.as_ptr(),
+            fx_index as i32,
+            param_index as i32,
+            min_val.as_mut_ptr(),
+            max_val.as_mut_ptr(),
+            mid_val.as_mut_ptr(),
+        );
+        GetParamExResult {
+            current_value: value,
+            min_value: min_val.assume_init(),
+            mid_value: mid_val.assume_init(),
+            max_value: max_val.assume_init(),
+        }
+    }
+
+    /// Gets a plug-in specific named configuration value of the given take FX.
+    ///
+    /// See [`track_fx_get_named_config_parm()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given FX doesn't have this named parameter or doesn't support named
+    /// parameters.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_get_named_config_parm()`]: #method.track_fx_get_named_config_parm
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_named_config_parm<'a>(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_name: impl Into<ReaperStringArg<'a>>,
+        buffer_size: u32,
+    ) -> ReaperFunctionResult<Vec<u8>>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let (buffer, successful) = with_buffer(buffer_size, |buffer, max_size| {
+            self.low.TakeFX_GetNamedConfigParm(
+                take.as_ptr(),
+                fx_index as i32,
+                param_name.into().as_ptr(),
+                buffer,
+                max_size,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't get named parameter value",
+            ));
+        }
+        Ok(buffer)
+    }
+
+    /// Sets a plug-in specific named configuration value of the given take FX.
+    ///
+    /// See [`track_fx_set_named_config_parm()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given FX doesn't have this named parameter or doesn't support named
+    /// parameters.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_set_named_config_parm()`]: #method.track_fx_set_named_config_parm
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_set_named_config_parm<'a>(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        param_name: impl Into<ReaperStringArg<'a>>,
+        buffer: &[u8],
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.TakeFX_SetNamedConfigParm(
+            take.as_ptr(),
+            fx_index as i32,
+            param_name.into().as_ptr(),
+            buffer.as_ptr() as _,
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set named parameter value",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Copies, moves or reorders take FX.
+    ///
+    /// Reorders if source and destination take are the same.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_copy_to_take(
+        &self,
+        source: (MediaItemTake, u32),
+        destination: (MediaItemTake, u32),
+        transfer_behavior: TransferBehavior,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.TakeFX_CopyToTake(
+            source.0.as_ptr(),
+            source.1 as i32,
+            destination.0.as_ptr(),
+            destination.1 as i32,
+            transfer_behavior == TransferBehavior::Move,
+        );
+    }
+
+    /// Removes the given FX from the take FX chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the FX doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_delete(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.TakeFX_Delete(take.as_ptr(), fx_index as i32);
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't delete FX (probably FX doesn't exist)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the master track of the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_master_track(&self, project: ProjectContext) -> MediaTrack
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe { self.get_master_track_unchecked(project) }
+    }
+
+    /// Like [`get_master_track()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_master_track()`]: #method.get_master_track
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_master_track_unchecked(&self, project: ProjectContext) -> MediaTrack
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetMasterTrack(project.to_raw());
+        require_non_null_panic(ptr)
+    }
+
+    /// Converts the given GUID to a string (including braces).
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn guid_to_string(&self, guid: &GUID) -> ReaperString
+    where
+        UsageScope: AnyThread,
+    {
+        let (guid_string, _) = with_string_buffer(64, |buffer, _| unsafe {
+            self.low.guidToString(guid as *const GUID, buffer)
+        });
+        guid_string
+    }
+
+    /// Returns the project recording path.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the resulting path you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_project_path_ex(&self, project: ProjectContext, buffer_size: u32) -> PathBuf
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_valid_project(project);
+        unsafe { self.get_project_path_ex_unchecked(project, buffer_size) }
+    }
+
+    /// Like [`get_project_path_ex()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_project_path_ex()`]: #method.get_project_path_ex
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_project_path_ex_unchecked(
+        &self,
+        project: ProjectContext,
+        buffer_size: u32,
+    ) -> PathBuf
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let (reaper_string, _) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low
+                .GetProjectPathEx(project.to_raw(), buffer, max_size)
+        });
+        let owned_string = reaper_string.into_string();
+        PathBuf::from(owned_string)
+    }
+
+    /// Returns the master tempo of the current project.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn master_get_tempo(&self) -> Bpm
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        Bpm(self.low.Master_GetTempo())
+    }
+
+    /// Sets the current tempo of the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn set_current_bpm(&self, project: ProjectContext, tempo: Bpm, undo_behavior: UndoBehavior)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_valid_project(project);
+        unsafe {
+            self.set_current_bpm_unchecked(project, tempo, undo_behavior);
+        }
+    }
+
+    /// Like [`set_current_bpm()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`set_current_bpm()`]: #method.set_current_bpm
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_current_bpm_unchecked(
+        &self,
+        project: ProjectContext,
+        tempo: Bpm,
+        undo_behavior: UndoBehavior,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.SetCurrentBPM(
+            project.to_raw(),
+            tempo.get(),
+            undo_behavior == UndoBehavior::AddUndoPoint,
+        );
+    }
+
+    /// Converts the given playback speed factor to a normalized play rate.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn master_normalize_play_rate_normalize(
+        &self,
+        value: PlaybackSpeedFactor,
+    ) -> NormalizedPlayRate
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let result = self.low.Master_NormalizePlayRate(value.get(), false);
+        NormalizedPlayRate::new(result)
+    }
+
+    /// Converts the given normalized play rate to a playback speed factor.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn master_normalize_play_rate_denormalize(
+        &self,
+        value: NormalizedPlayRate,
+    ) -> PlaybackSpeedFactor
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let result = self.low.Master_NormalizePlayRate(value.get(), true);
+        PlaybackSpeedFactor::new(result)
+    }
+
+    /// Returns the master play rate of the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn master_get_play_rate(&self, project: ProjectContext) -> PlaybackSpeedFactor
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe { self.master_get_play_rate_unchecked(project) }
+    }
+
+    /// Like [`master_get_play_rate()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`master_get_play_rate()`]: #method.master_get_play_rate
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn master_get_play_rate_unchecked(
+        &self,
+        project: ProjectContext,
+    ) -> PlaybackSpeedFactor
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.Master_GetPlayRate(project.to_raw());
+        PlaybackSpeedFactor(raw)
+    }
+
+    /// Sets the master play rate of the current project.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn csurf_on_play_rate_change(&self, play_rate: PlaybackSpeedFactor) {
+        self.low.CSurf_OnPlayRateChange(play_rate.get());
+    }
+
+    /// Shows a message box to the user.
+    ///
+    /// Blocks the main thread.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn show_message_box<'a>(
+        &self,
+        message: impl Into<ReaperStringArg<'a>>,
+        title: impl Into<ReaperStringArg<'a>>,
+        r#type: MessageBoxType,
+    ) -> MessageBoxResult
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let result = unsafe {
+            self.low.ShowMessageBox(
+                message.into().as_ptr(),
+                title.into().as_ptr(),
+                r#type.to_raw(),
+            )
+        };
+        MessageBoxResult::from_raw(result)
+    }
+
+    /// Parses the given string as GUID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given string is not a valid GUID string.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn string_to_guid<'a>(
+        &self,
+        guid_string: impl Into<ReaperStringArg<'a>>,
+    ) -> ReaperFunctionResult<GUID>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let mut guid = MaybeUninit::uninit();
+        unsafe {
+            self.low
+                .stringToGuid(guid_string.into().as_ptr(), guid.as_mut_ptr());
+        }
+        let guid = unsafe { guid.assume_init() };
+        if guid == ZERO_GUID {
+            return Err(ReaperFunctionError::new("GUID string is invalid"));
+        }
+        Ok(guid)
+    }
+
+    /// Sets the input monitoring mode of the given track.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_on_input_monitoring_change_ex(
+        &self,
+        track: MediaTrack,
+        mode: InputMonitoringMode,
+        gang_behavior: GangBehavior,
+    ) -> i32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.CSurf_OnInputMonitorChangeEx(
+            track.as_ptr(),
+            mode.to_raw(),
+            gang_behavior == GangBehavior::AllowGang,
+        )
+    }
+
+    /// Scrolls the mixer so that the given track is the leftmost visible track.
+    ///
+    /// Returns the leftmost visible track after scrolling, which may be different from the given
+    /// track if there are not enough tracks to its right. Not exactly sure what it's supposed to
+    /// mean if this returns `None`, but it happens at times.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_mixer_scroll(&self, track: MediaTrack) -> Option<MediaTrack>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.SetMixerScroll(track.as_ptr());
+        NonNull::new(ptr)
+    }
+
+    /// Sets a track attribute as numerical value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an invalid (e.g. non-numerical) track attribute key is passed.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_media_track_info_value(
+        &self,
+        track: MediaTrack,
+        attribute_key: TrackAttributeKey,
+        new_value: f64,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.SetMediaTrackInfo_Value(
+            track.as_ptr(),
+            attribute_key.into_raw().as_ptr(),
+            new_value,
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set track attribute (maybe attribute key is invalid)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stuffs a 3-byte MIDI message into a queue or send it to an external MIDI hardware.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let session = reaper_medium::ReaperSession::default();
+    /// use helgoboss_midi::test_util::note_on;
+    /// use reaper_medium::StuffMidiMessageTarget::VirtualMidiKeyboardQueue;
+    ///
+    /// session.reaper().stuff_midi_message(VirtualMidiKeyboardQueue, note_on(0, 64, 100));
+    /// ```
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn stuff_midi_message(&self, target: StuffMidiMessageTarget, message: impl ShortMessage)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let bytes = message.to_bytes();
+        self.low.StuffMIDIMessage(
+            target.to_raw(),
+            bytes.0.into(),
+            bytes.1.into(),
+            bytes.2.into(),
+        );
+    }
+
+    /// Converts a decibel value into a volume slider value.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn db2slider(&self, value: Db) -> VolumeSliderValue
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        VolumeSliderValue(self.low.DB2SLIDER(value.get()))
+    }
+
+    /// Converts a volume slider value into a decibel value.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn slider2db(&self, value: VolumeSliderValue) -> Db
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        Db(self.low.SLIDER2DB(value.get()))
+    }
+
+    /// Returns the given track's volume and incomplete pan. Also returns the correct value during
+    /// the process of writing an automation envelope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_track_ui_vol_pan(
+        &self,
+        track: MediaTrack,
+    ) -> ReaperFunctionResult<VolumeAndPan>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        // We zero them just for being safe.
+        let mut volume = MaybeUninit::zeroed();
+        let mut pan = MaybeUninit::zeroed();
+        let successful =
+            self.low
+                .GetTrackUIVolPan(track.as_ptr(), volume.as_mut_ptr(), pan.as_mut_ptr());
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't get track volume and pan",
+            ));
+        }
+        Ok(VolumeAndPan {
+            volume: ReaperVolumeValue::new(volume.assume_init()),
+            pan: ReaperPanValue::new(pan.assume_init()),
+        })
+    }
+
+    /// Returns the given track's mute state. Also returns the correct value during the process of
+    /// writing an automation envelope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_track_ui_mute(&self, track: MediaTrack) -> ReaperFunctionResult<bool>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        // We zero them just for being safe.
+        let mut mute = MaybeUninit::zeroed();
+        let successful = self.low.GetTrackUIMute(track.as_ptr(), mute.as_mut_ptr());
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get track mute"));
+        }
+        Ok(mute.assume_init())
+    }
+
+    /// Returns the given track's complete pan. Also returns the correct value during the process of
+    /// writing an automation envelope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_track_ui_pan(
+        &self,
+        track: MediaTrack,
+    ) -> ReaperFunctionResult<GetTrackUiPanResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        // We zero them just for being safe.
+        let mut pan_1 = MaybeUninit::zeroed();
+        let mut pan_2 = MaybeUninit::zeroed();
+        let mut pan_mode = MaybeUninit::zeroed();
+        let successful = self.low.GetTrackUIPan(
+            track.as_ptr(),
+            pan_1.as_mut_ptr(),
+            pan_2.as_mut_ptr(),
+            pan_mode.as_mut_ptr(),
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get track pan"));
+        }
+        let pan_mode = PanMode::from_raw(pan_mode.assume_init());
+        let res = GetTrackUiPanResult {
+            pan_mode,
+            pan_1: ReaperPanLikeValue(pan_1.assume_init()),
+            pan_2: ReaperPanLikeValue(pan_2.assume_init()),
+        };
+        Ok(res)
+    }
+
+    /// Informs control surfaces that the given track's volume has changed.
+    ///
+    /// Doesn't actually change the volume.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track or an invalid control surface.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_set_surface_volume(
+        &self,
+        track: MediaTrack,
+        volume: ReaperVolumeValue,
+        notification_behavior: NotificationBehavior,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.CSurf_SetSurfaceVolume(
+            track.as_ptr(),
+            volume.get(),
+            notification_behavior.to_raw(),
+        );
+    }
+
+    /// Sets the given track's volume, also supports relative changes and gang.
+    ///
+    /// Returns the value that has actually been set. I think this only deviates if 0.0 is sent.
+    /// Then it returns a slightly higher value - the one which actually corresponds to -150 dB.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_on_volume_change_ex(
+        &self,
+        track: MediaTrack,
+        value_change: ValueChange<ReaperVolumeValue>,
+        gang_behavior: GangBehavior,
+    ) -> ReaperVolumeValue
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.CSurf_OnVolumeChangeEx(
+            track.as_ptr(),
+            value_change.value(),
+            value_change.is_relative(),
+            gang_behavior == GangBehavior::AllowGang,
+        );
+        ReaperVolumeValue::new(raw)
+    }
+
+    /// Informs control surfaces that the given track's pan has been changed.
+    ///
+    /// Doesn't actually change the pan.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track or an invalid control surface.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_set_surface_pan(
+        &self,
+        track: MediaTrack,
+        pan: ReaperPanValue,
+        notification_behavior: NotificationBehavior,
+    ) where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low
+            .CSurf_SetSurfacePan(track.as_ptr(), pan.get(), notification_behavior.to_raw());
+    }
+
+    /// Sets the given track's pan. Also supports relative changes and gang.
+    ///
+    /// Returns the value that has actually been set.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_on_pan_change_ex(
+        &self,
+        track: MediaTrack,
+        value_change: ValueChange<ReaperPanValue>,
+        gang_behavior: GangBehavior,
+    ) -> ReaperPanValue
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.CSurf_OnPanChangeEx(
+            track.as_ptr(),
+            value_change.value(),
+            value_change.is_relative(),
+            gang_behavior == GangBehavior::AllowGang,
+        );
+        ReaperPanValue::new(raw)
+    }
+
+    /// Sets the given track's width. Also supports relative changes and gang.
+    ///
+    /// Returns the value that has actually been set.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn csurf_on_width_change_ex(
+        &self,
+        track: MediaTrack,
+        value_change: ValueChange<ReaperWidthValue>,
+        gang_behavior: GangBehavior,
+    ) -> ReaperWidthValue
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.CSurf_OnWidthChangeEx(
+            track.as_ptr(),
+            value_change.value(),
+            value_change.is_relative(),
+            gang_behavior == GangBehavior::AllowGang,
+        );
+        ReaperWidthValue::new(raw)
+    }
+
+    /// Counts the number of selected tracks in the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn count_selected_tracks_2(
+        &self,
+        project: ProjectContext,
+        master_track_behavior: MasterTrackBehavior,
+    ) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.require_valid_project(project);
+        unsafe { self.count_selected_tracks_2_unchecked(project, master_track_behavior) }
+    }
+
+    /// Like [`count_selected_tracks_2()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`count_selected_tracks_2()`]: #method.count_selected_tracks_2
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn count_selected_tracks_2_unchecked(
+        &self,
+        project: ProjectContext,
+        master_track_behavior: MasterTrackBehavior,
+    ) -> u32
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.CountSelectedTracks2(
+            project.to_raw(),
+            master_track_behavior == MasterTrackBehavior::IncludeMasterTrack,
+        ) as u32
+    }
+
+    /// Selects or deselects the given track.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_track_selected(&self, track: MediaTrack, is_selected: bool)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.SetTrackSelected(track.as_ptr(), is_selected);
+    }
+
+    /// Returns a selected track from the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_selected_track_2(
+        &self,
+        project: ProjectContext,
+        selected_track_index: u32,
+        master_track_behavior: MasterTrackBehavior,
+    ) -> Option<MediaTrack>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_valid_project(project);
+        unsafe {
+            self.get_selected_track_2_unchecked(
+                project,
+                selected_track_index,
+                master_track_behavior,
+            )
+        }
+    }
+
+    /// Like [`get_selected_track_2()`] but doesn't check if project is valid.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_selected_track_2()`]: #method.get_selected_track_2
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_selected_track_2_unchecked(
+        &self,
+        project: ProjectContext,
+        selected_track_index: u32,
+        master_track_behavior: MasterTrackBehavior,
+    ) -> Option<MediaTrack>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetSelectedTrack2(
+            project.to_raw(),
+            selected_track_index as i32,
+            master_track_behavior == MasterTrackBehavior::IncludeMasterTrack,
+        );
+        NonNull::new(ptr)
+    }
+
+    /// Returns a selected item from the given project.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given project is not valid anymore.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_selected_media_item(
+        &self,
+        project: ProjectContext,
+        selected_item_index: u32,
+    ) -> Option<MediaItem>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_valid_project(project);
+        unsafe { self.get_selected_media_item_unchecked(project, selected_item_index) }
     }
 
-    /// Like [`master_get_play_rate()`] but doesn't check if project is valid.
+    /// Like [`get_selected_media_item()`] but doesn't check if project is valid.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid project.
     ///
-    /// [`master_get_play_rate()`]: #method.master_get_play_rate
+    /// [`get_selected_media_item()`]: #method.get_selected_media_item
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn master_get_play_rate_unchecked(
+    pub unsafe fn get_selected_media_item_unchecked(
         &self,
         project: ProjectContext,
-    ) -> PlaybackSpeedFactor
+        selected_item_index: u32,
+    ) -> Option<MediaItem>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let raw = self.low.Master_GetPlayRate(project.to_raw());
-        PlaybackSpeedFactor(raw)
-    }
-
-    /// Sets the master play rate of the current project.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub fn csurf_on_play_rate_change(&self, play_rate: PlaybackSpeedFactor) {
-        self.low.CSurf_OnPlayRateChange(play_rate.get());
+        let ptr = self
+            .low
+            .GetSelectedMediaItem(project.to_raw(), selected_item_index as i32);
+        NonNull::new(ptr)
     }
 
-    /// Shows a message box to the user.
+    /// Returns the media source of the given media item take.
     ///
-    /// Blocks the main thread.
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn show_message_box<'a>(
-        &self,
-        message: impl Into<ReaperStringArg<'a>>,
-        title: impl Into<ReaperStringArg<'a>>,
-        r#type: MessageBoxType,
-    ) -> MessageBoxResult
+    pub unsafe fn get_media_item_take_source(&self, take: MediaItemTake) -> Option<PcmSource>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let result = unsafe {
-            self.low.ShowMessageBox(
-                message.into().as_ptr(),
-                title.into().as_ptr(),
-                r#type.to_raw(),
-            )
-        };
-        MessageBoxResult::from_raw(result)
+        let ptr = self.low.GetMediaItemTake_Source(take.as_ptr());
+        NonNull::new(ptr).map(PcmSource)
     }
 
-    /// Parses the given string as GUID.
+    /// Gets a block of peak samples from the given PCM source.
     ///
-    /// # Errors
+    /// The buffer must have room for `channel_count * sample_count_per_channel` samples times 2
+    /// (maximums and minimums) or times 3 if an extra type is requested. It will be filled with
+    /// peak samples interleaved by channel, but in consecutive blocks (maximums, then minimums,
+    /// then extra).
     ///
-    /// Returns an error if the given string is not a valid GUID string.
+    /// # Panics
+    ///
+    /// Panics if the buffer is too small.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid source.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn string_to_guid<'a>(
+    pub unsafe fn pcm_source_get_peaks(
         &self,
-        guid_string: impl Into<ReaperStringArg<'a>>,
-    ) -> ReaperFunctionResult<GUID>
+        source: PcmSource,
+        peak_rate: Hz,
+        start_time: PositionInSeconds,
+        channel_count: u32,
+        sample_count_per_channel: u32,
+        extra_type: Option<PeakExtraType>,
+        buffer: &mut [f64],
+    ) -> GetPeaksResult
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let mut guid = MaybeUninit::uninit();
-        unsafe {
-            self.low
-                .stringToGuid(guid_string.into().as_ptr(), guid.as_mut_ptr());
-        }
-        let guid = unsafe { guid.assume_init() };
-        if guid == ZERO_GUID {
-            return Err(ReaperFunctionError::new("GUID string is invalid"));
-        }
-        Ok(guid)
+        require_peak_buffer_size(buffer, channel_count, sample_count_per_channel, extra_type);
+        let result = self.low.PCM_Source_GetPeaks(
+            source.to_raw(),
+            peak_rate.get(),
+            start_time.get(),
+            channel_count as _,
+            sample_count_per_channel as _,
+            extra_type.map(|t| t.to_raw()).unwrap_or(0),
+            buffer.as_mut_ptr(),
+        );
+        GetPeaksResult::from_raw(result)
     }
 
-    /// Sets the input monitoring mode of the given track.
+    /// Gets a block of peak samples from the given take.
+    ///
+    /// Same as [`pcm_source_get_peaks()`] but takes the take properties (e.g. start offset and
+    /// play rate) into account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is too small.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`pcm_source_get_peaks()`]: #method.pcm_source_get_peaks
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_on_input_monitoring_change_ex(
+    pub unsafe fn get_media_item_take_peaks(
         &self,
-        track: MediaTrack,
-        mode: InputMonitoringMode,
-        gang_behavior: GangBehavior,
-    ) -> i32
-    where
-        UsageScope: MainThreadOnly,
+        take: MediaItemTake,
+        peak_rate: Hz,
+        start_time: PositionInSeconds,
+        channel_count: u32,
+        sample_count_per_channel: u32,
+        extra_type: Option<PeakExtraType>,
+        buffer: &mut [f64],
+    ) -> GetPeaksResult
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        require_peak_buffer_size(buffer, channel_count, sample_count_per_channel, extra_type);
+        let result = self.low.GetMediaItemTake_Peaks(
+            take.as_ptr(),
+            peak_rate.get(),
+            start_time.get(),
+            channel_count as _,
+            sample_count_per_channel as _,
+            extra_type.map(|t| t.to_raw()).unwrap_or(0),
+            buffer.as_mut_ptr(),
+        );
+        GetPeaksResult::from_raw(result)
+    }
+
+    /// Unstable!!!
+    ///
+    /// Fills the given peak transfer by calculating peaks directly from the samples of the given
+    /// source (without using a peak file).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid source or an incorrectly filled peak transfer.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub unsafe fn hires_peaks_from_source(
+        &self,
+        source: PcmSource,
+        block: &BorrowedPcmSourcePeakTransfer,
+    ) where
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        self.low.CSurf_OnInputMonitorChangeEx(
-            track.as_ptr(),
-            mode.to_raw(),
-            gang_behavior == GangBehavior::AllowGang,
-        )
+        self.low
+            .HiresPeaksFromSource(source.to_raw(), block.as_ptr().as_ptr());
     }
 
-    /// Scrolls the mixer so that the given track is the leftmost visible track.
+    /// Unstable!!!
     ///
-    /// Returns the leftmost visible track after scrolling, which may be different from the given
-    /// track if there are not enough tracks to its right. Not exactly sure what it's supposed to
-    /// mean if this returns `None`, but it happens at times.
+    /// Calculates peaks from the samples in the given source transfer and writes them into the
+    /// given peak transfer.
+    ///
+    /// Returns the number of peaks written.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn set_mixer_scroll(&self, track: MediaTrack) -> Option<MediaTrack>
+    /// REAPER can crash if you pass incorrectly filled transfers.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub unsafe fn calculate_peaks(
+        &self,
+        source_block: &BorrowedPcmSourceTransfer,
+        peaks_block: &BorrowedPcmSourcePeakTransfer,
+    ) -> u32
     where
-        UsageScope: MainThreadOnly,
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        let ptr = self.low.SetMixerScroll(track.as_ptr());
-        NonNull::new(ptr)
+        let count = self.low.CalculatePeaks(
+            source_block.as_ptr().as_ptr(),
+            peaks_block.as_ptr().as_ptr(),
+        );
+        if count < 0 { 0 } else { count as u32 }
     }
 
-    /// Sets a track attribute as numerical value.
+    /// Returns the peak file name for the given media file.
+    ///
+    /// This can be either `FILE.reapeaks` or a hashed file name in another directory, depending on
+    /// the user preferences.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the file name you want.
+    ///
+    /// Callable from any thread because it's typically needed within PCM source implementations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given file name is not valid UTF-8 or if the given buffer size is 0.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn get_peak_file_name(&self, file_name: &Path, buffer_size: u32) -> PathBuf
+    where
+        UsageScope: AnyThread,
+    {
+        assert!(buffer_size > 0);
+        let file_name_str = file_name.to_str().expect("file name is not valid UTF-8");
+        let file_name_reaper_string = ReaperString::from_str(file_name_str);
+        let (peak_file_name, _) = with_string_buffer(buffer_size, |buffer, max_size| unsafe {
+            self.low
+                .GetPeakFileName(file_name_reaper_string.as_ptr(), buffer, max_size)
+        });
+        PathBuf::from(peak_file_name.into_string())
+    }
+
+    /// Creates a peak builder for writing the peak file of the given media file.
+    ///
+    /// If a source is given, REAPER reads the samples from it when calling
+    /// [`OwnedPeakBuilder::run()`]. Otherwise you need to feed the samples yourself via
+    /// [`OwnedPeakBuilder::process_samples_interleaved()`].
+    ///
+    /// Callable from any thread because it's typically needed within PCM source implementations.
     ///
     /// # Errors
     ///
-    /// Returns an error if an invalid (e.g. non-numerical) track attribute key is passed.
+    /// Returns an error if the peak builder could not be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given file name is not valid UTF-8.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn set_media_track_info_value(
+    /// REAPER can crash if you pass an invalid source.
+    ///
+    /// [`OwnedPeakBuilder::run()`]: struct.OwnedPeakBuilder.html#method.run
+    /// [`OwnedPeakBuilder::process_samples_interleaved()`]:
+    /// struct.OwnedPeakBuilder.html#method.process_samples_interleaved
+    #[measure(ResponseTimeMultiThreaded)]
+    pub unsafe fn peak_build_create(
         &self,
-        track: MediaTrack,
-        attribute_key: TrackAttributeKey,
-        new_value: f64,
-    ) -> ReaperFunctionResult<()>
+        source: Option<PcmSource>,
+        file_name: &Path,
+        sample_rate: Hz,
+        channel_count: u32,
+    ) -> ReaperFunctionResult<OwnedPeakBuilder>
     where
-        UsageScope: MainThreadOnly,
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        let successful = self.low.SetMediaTrackInfo_Value(
-            track.as_ptr(),
-            attribute_key.into_raw().as_ptr(),
-            new_value,
+        let file_name_str = file_name.to_str().expect("file name is not valid UTF-8");
+        let file_name_reaper_string = ReaperString::from_str(file_name_str);
+        let ptr = self.low.PeakBuild_Create(
+            source.map(|s| s.to_raw()).unwrap_or(null_mut()),
+            file_name_reaper_string.as_ptr(),
+            sample_rate.get().round() as _,
+            channel_count as _,
         );
-        if !successful {
-            return Err(ReaperFunctionError::new(
-                "couldn't set track attribute (maybe attribute key is invalid)",
-            ));
-        }
-        Ok(())
+        NonNull::new(ptr)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't create peak builder"))
+            .map(|p| OwnedPeakBuilder::new_unchecked(p))
     }
 
-    /// Stuffs a 3-byte MIDI message into a queue or send it to an external MIDI hardware.
+    /// Creates a peak getter for reading the peak file of the given media file.
     ///
-    /// # Example
+    /// Callable from any thread because it's typically needed within PCM source implementations.
     ///
-    /// ```no_run
-    /// # let session = reaper_medium::ReaperSession::default();
-    /// use helgoboss_midi::test_util::note_on;
-    /// use reaper_medium::StuffMidiMessageTarget::VirtualMidiKeyboardQueue;
+    /// # Errors
     ///
-    /// session.reaper().stuff_midi_message(VirtualMidiKeyboardQueue, note_on(0, 64, 100));
-    /// ```
-    #[measure(ResponseTimeSingleThreaded)]
-    pub fn stuff_midi_message(&self, target: StuffMidiMessageTarget, message: impl ShortMessage)
+    /// Returns an error if the peak getter could not be created (e.g. if there's no peak file).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given file name is not valid UTF-8.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn peak_get_create(
+        &self,
+        file_name: &Path,
+        sample_rate: Hz,
+        channel_count: u32,
+    ) -> ReaperFunctionResult<OwnedPeakGetter>
     where
-        UsageScope: MainThreadOnly,
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        let bytes = message.to_bytes();
-        self.low.StuffMIDIMessage(
-            target.to_raw(),
-            bytes.0.into(),
-            bytes.1.into(),
-            bytes.2.into(),
-        );
+        let file_name_str = file_name.to_str().expect("file name is not valid UTF-8");
+        let file_name_reaper_string = ReaperString::from_str(file_name_str);
+        let ptr = unsafe {
+            self.low.PeakGet_Create(
+                file_name_reaper_string.as_ptr(),
+                sample_rate.get().round() as _,
+                channel_count as _,
+            )
+        };
+        NonNull::new(ptr)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't create peak getter"))
+            .map(|p| unsafe { OwnedPeakGetter::new_unchecked(p) })
     }
 
-    /// Converts a decibel value into a volume slider value.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub fn db2slider(&self, value: Db) -> VolumeSliderValue
+    /// Creates a resampler.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resampler couldn't be created.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn resampler_create(&self) -> ReaperFunctionResult<Resampler>
     where
-        UsageScope: MainThreadOnly,
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        VolumeSliderValue(self.low.DB2SLIDER(value.get()))
+        let ptr = self.low.Resampler_Create();
+        NonNull::new(ptr)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't create resampler"))
+            .map(|p| unsafe { Resampler::new_unchecked(p) })
     }
 
-    /// Converts a volume slider value into a decibel value.
-    #[measure(ResponseTimeSingleThreaded)]
-    pub fn slider2db(&self, value: VolumeSliderValue) -> Db
+    /// Creates a pitch shifter (using the pitch shift API version supported by this crate).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pitch shifter couldn't be created.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn reaper_get_pitch_shift_api(&self) -> ReaperFunctionResult<PitchShifter>
     where
-        UsageScope: MainThreadOnly,
+        UsageScope: AnyThread,
     {
-        self.require_main_thread();
-        Db(self.low.SLIDER2DB(value.get()))
+        let ptr = self
+            .low
+            .ReaperGetPitchShiftAPI(raw::REAPER_PITCHSHIFT_API_VER as _);
+        NonNull::new(ptr)
+            .ok_or_else(|| ReaperFunctionError::new("couldn't create pitch shifter"))
+            .map(|p| unsafe { PitchShifter::new_unchecked(p) })
     }
 
-    /// Returns the given track's volume and incomplete pan. Also returns the correct value during
-    /// the process of writing an automation envelope.
+    /// Grants temporary access to the name of the given pitch shift mode.
     ///
-    /// # Errors
+    /// Returns `None` if the mode index is out of range. Passes `None` to the given function if the
+    /// mode exists but is not supported on this system.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn enum_pitch_shift_modes<R>(
+        &self,
+        mode: PitchShiftMode,
+        use_name: impl FnOnce(Option<&ReaperStr>) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: AnyThread,
+    {
+        let mut ptr: *const c_char = std::ptr::null();
+        let exists = unsafe {
+            self.low
+                .EnumPitchShiftModes(mode.get() as _, &mut ptr as *mut _)
+        };
+        if !exists {
+            return None;
+        }
+        let name = unsafe { create_passing_c_str(ptr) };
+        Some(use_name(name))
+    }
+
+    /// Grants temporary access to the name of the given pitch shift sub mode.
+    ///
+    /// Returns `None` if the mode or sub mode index is out of range.
+    #[measure(ResponseTimeMultiThreaded)]
+    pub fn enum_pitch_shift_sub_modes<R>(
+        &self,
+        mode: PitchShiftMode,
+        sub_mode: PitchShiftSubMode,
+        use_name: impl FnOnce(&ReaperStr) -> R,
+    ) -> Option<R>
+    where
+        UsageScope: AnyThread,
+    {
+        let ptr = self
+            .low
+            .EnumPitchShiftSubModes(mode.get() as _, sub_mode.get() as _);
+        let name = unsafe { create_passing_c_str(ptr) }?;
+        Some(use_name(name))
+    }
+
+    /// Unstable!!!
     ///
-    /// Returns an error if not successful (unclear when this happens).
+    /// Returns the project which contains this item.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid item.
+    // TODO-high-unstable Can this EVER be None?
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_track_ui_vol_pan(
-        &self,
-        track: MediaTrack,
-    ) -> ReaperFunctionResult<VolumeAndPan>
+    pub unsafe fn get_item_project_context(&self, item: MediaItem) -> Option<ReaProject>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        // We zero them just for being safe.
-        let mut volume = MaybeUninit::zeroed();
-        let mut pan = MaybeUninit::zeroed();
-        let successful =
-            self.low
-                .GetTrackUIVolPan(track.as_ptr(), volume.as_mut_ptr(), pan.as_mut_ptr());
-        if !successful {
-            return Err(ReaperFunctionError::new(
-                "couldn't get track volume and pan",
-            ));
-        }
-        Ok(VolumeAndPan {
-            volume: ReaperVolumeValue::new(volume.assume_init()),
-            pan: ReaperPanValue::new(pan.assume_init()),
-        })
+        let ptr = self.low.GetItemProjectContext(item.as_ptr());
+        NonNull::new(ptr)
     }
 
-    /// Returns the given track's mute state. Also returns the correct value during the process of
-    /// writing an automation envelope.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if not successful (unclear when this happens).
+    /// Returns the active take in this item.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid item.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_track_ui_mute(&self, track: MediaTrack) -> ReaperFunctionResult<bool>
+    pub unsafe fn get_active_take(&self, item: MediaItem) -> Option<MediaItemTake>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        // We zero them just for being safe.
-        let mut mute = MaybeUninit::zeroed();
-        let successful = self.low.GetTrackUIMute(track.as_ptr(), mute.as_mut_ptr());
-        if !successful {
-            return Err(ReaperFunctionError::new("couldn't get track mute"));
-        }
-        Ok(mute.assume_init())
+        let ptr = self.low.GetActiveTake(item.as_ptr());
+        NonNull::new(ptr)
     }
 
-    /// Returns the given track's complete pan. Also returns the correct value during the process of
-    /// writing an automation envelope.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if not successful (unclear when this happens).
+    /// Returns the number of items in the given project.
     ///
-    /// # Safety
+    /// # Panics
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// Panics if the given project is not valid anymore.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_track_ui_pan(
-        &self,
-        track: MediaTrack,
-    ) -> ReaperFunctionResult<GetTrackUiPanResult>
+    pub fn count_media_items(&self, project: ProjectContext) -> u32
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_main_thread();
-        // We zero them just for being safe.
-        let mut pan_1 = MaybeUninit::zeroed();
-        let mut pan_2 = MaybeUninit::zeroed();
-        let mut pan_mode = MaybeUninit::zeroed();
-        let successful = self.low.GetTrackUIPan(
-            track.as_ptr(),
-            pan_1.as_mut_ptr(),
-            pan_2.as_mut_ptr(),
-            pan_mode.as_mut_ptr(),
-        );
-        if !successful {
-            return Err(ReaperFunctionError::new("couldn't get track pan"));
-        }
-        let pan_mode = PanMode::from_raw(pan_mode.assume_init());
-        let res = GetTrackUiPanResult {
-            pan_mode,
-            pan_1: ReaperPanLikeValue(pan_1.assume_init()),
-            pan_2: ReaperPanLikeValue(pan_2.assume_init()),
-        };
-        Ok(res)
+        self.require_valid_project(project);
+        unsafe { self.count_media_items_unchecked(project) }
     }
 
-    /// Informs control surfaces that the given track's volume has changed.
-    ///
-    /// Doesn't actually change the volume.
+    /// Like [`count_media_items()`] but doesn't check if project is valid.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track or an invalid control surface.
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`count_media_items()`]: #method.count_media_items
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_set_surface_volume(
-        &self,
-        track: MediaTrack,
-        volume: ReaperVolumeValue,
-        notification_behavior: NotificationBehavior,
-    ) where
+    pub unsafe fn count_media_items_unchecked(&self, project: ProjectContext) -> u32
+    where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.low.CSurf_SetSurfaceVolume(
-            track.as_ptr(),
-            volume.get(),
-            notification_behavior.to_raw(),
-        );
+        self.low.CountMediaItems(project.to_raw()) as u32
     }
 
-    /// Sets the given track's volume, also supports relative changes and gang.
+    /// Returns a number which is incremented whenever the given project changes in a way that
+    /// creates an undo point or marks it as dirty.
     ///
-    /// Returns the value that has actually been set. I think this only deviates if 0.0 is sent.
-    /// Then it returns a slightly higher value - the one which actually corresponds to -150 dB.
+    /// This is useful for cheaply detecting whether anything has changed at all.
     ///
-    /// # Safety
+    /// # Panics
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// Panics if the given project is not valid anymore.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_on_volume_change_ex(
-        &self,
-        track: MediaTrack,
-        value_change: ValueChange<ReaperVolumeValue>,
-        gang_behavior: GangBehavior,
-    ) -> ReaperVolumeValue
+    pub fn get_project_state_change_count(&self, project: ProjectContext) -> u32
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_main_thread();
-        let raw = self.low.CSurf_OnVolumeChangeEx(
-            track.as_ptr(),
-            value_change.value(),
-            value_change.is_relative(),
-            gang_behavior == GangBehavior::AllowGang,
-        );
-        ReaperVolumeValue::new(raw)
+        self.require_valid_project(project);
+        unsafe { self.get_project_state_change_count_unchecked(project) }
     }
 
-    /// Informs control surfaces that the given track's pan has been changed.
-    ///
-    /// Doesn't actually change the pan.
+    /// Like [`get_project_state_change_count()`] but doesn't check if project is valid.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track or an invalid control surface.
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_project_state_change_count()`]: #method.get_project_state_change_count
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_set_surface_pan(
-        &self,
-        track: MediaTrack,
-        pan: ReaperPanValue,
-        notification_behavior: NotificationBehavior,
-    ) where
+    pub unsafe fn get_project_state_change_count_unchecked(&self, project: ProjectContext) -> u32
+    where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.low
-            .CSurf_SetSurfacePan(track.as_ptr(), pan.get(), notification_behavior.to_raw());
+        self.low.GetProjectStateChangeCount(project.to_raw()) as u32
     }
 
-    /// Sets the given track's pan. Also supports relative changes and gang.
+    /// Returns the item at the given index.
     ///
-    /// Returns the value that has actually been set.
+    /// Items are numbered across all tracks of the project.
     ///
-    /// # Safety
+    /// # Panics
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// Panics if the given project is not valid anymore.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_on_pan_change_ex(
-        &self,
-        track: MediaTrack,
-        value_change: ValueChange<ReaperPanValue>,
-        gang_behavior: GangBehavior,
-    ) -> ReaperPanValue
+    pub fn get_media_item(&self, project: ProjectContext, item_index: u32) -> Option<MediaItem>
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_main_thread();
-        let raw = self.low.CSurf_OnPanChangeEx(
-            track.as_ptr(),
-            value_change.value(),
-            value_change.is_relative(),
-            gang_behavior == GangBehavior::AllowGang,
-        );
-        ReaperPanValue::new(raw)
+        self.require_valid_project(project);
+        unsafe { self.get_media_item_unchecked(project, item_index) }
     }
 
-    /// Sets the given track's width. Also supports relative changes and gang.
-    ///
-    /// Returns the value that has actually been set.
+    /// Like [`get_media_item()`] but doesn't check if project is valid.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid project.
+    ///
+    /// [`get_media_item()`]: #method.get_media_item
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn csurf_on_width_change_ex(
+    pub unsafe fn get_media_item_unchecked(
         &self,
-        track: MediaTrack,
-        value_change: ValueChange<ReaperWidthValue>,
-        gang_behavior: GangBehavior,
-    ) -> ReaperWidthValue
+        project: ProjectContext,
+        item_index: u32,
+    ) -> Option<MediaItem>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let raw = self.low.CSurf_OnWidthChangeEx(
-            track.as_ptr(),
-            value_change.value(),
-            value_change.is_relative(),
-            gang_behavior == GangBehavior::AllowGang,
-        );
-        ReaperWidthValue::new(raw)
+        let ptr = self.low.GetMediaItem(project.to_raw(), item_index as i32);
+        NonNull::new(ptr)
     }
 
-    /// Counts the number of selected tracks in the given project.
+    /// Returns the take with the given GUID.
     ///
     /// # Panics
     ///
     /// Panics if the given project is not valid anymore.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn count_selected_tracks_2(
+    pub fn get_media_item_take_by_guid(
         &self,
         project: ProjectContext,
-        master_track_behavior: MasterTrackBehavior,
-    ) -> u32
+        guid: &GUID,
+    ) -> Option<MediaItemTake>
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_main_thread();
         self.require_valid_project(project);
-        unsafe { self.count_selected_tracks_2_unchecked(project, master_track_behavior) }
+        unsafe { self.get_media_item_take_by_guid_unchecked(project, guid) }
     }
 
-    /// Like [`count_selected_tracks_2()`] but doesn't check if project is valid.
+    /// Like [`get_media_item_take_by_guid()`] but doesn't check if project is valid.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid project.
     ///
-    /// [`count_selected_tracks_2()`]: #method.count_selected_tracks_2
+    /// [`get_media_item_take_by_guid()`]: #method.get_media_item_take_by_guid
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn count_selected_tracks_2_unchecked(
+    pub unsafe fn get_media_item_take_by_guid_unchecked(
         &self,
         project: ProjectContext,
-        master_track_behavior: MasterTrackBehavior,
-    ) -> u32
+        guid: &GUID,
+    ) -> Option<MediaItemTake>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.low.CountSelectedTracks2(
-            project.to_raw(),
-            master_track_behavior == MasterTrackBehavior::IncludeMasterTrack,
-        ) as u32
+        let ptr = self
+            .low
+            .GetMediaItemTakeByGUID(project.to_raw(), guid as *const _);
+        NonNull::new(ptr)
     }
 
-    /// Selects or deselects the given track.
+    /// Returns the number of takes in the given item.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid item.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn set_track_selected(&self, track: MediaTrack, is_selected: bool)
+    pub unsafe fn count_takes(&self, item: MediaItem) -> u32
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        self.low.SetTrackSelected(track.as_ptr(), is_selected);
+        self.low.CountTakes(item.as_ptr()) as u32
     }
 
-    /// Returns a selected track from the given project.
+    /// Returns the take at the given index of the given item.
     ///
-    /// # Panics
+    /// # Safety
     ///
-    /// Panics if the given project is not valid anymore.
+    /// REAPER can crash if you pass an invalid item.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn get_selected_track_2(
-        &self,
-        project: ProjectContext,
-        selected_track_index: u32,
-        master_track_behavior: MasterTrackBehavior,
-    ) -> Option<MediaTrack>
+    pub unsafe fn get_take(&self, item: MediaItem, take_index: u32) -> Option<MediaItemTake>
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_valid_project(project);
-        unsafe {
-            self.get_selected_track_2_unchecked(
-                project,
-                selected_track_index,
-                master_track_behavior,
-            )
-        }
+        self.require_main_thread();
+        let ptr = self.low.GetTake(item.as_ptr(), take_index as i32);
+        NonNull::new(ptr)
     }
 
-    /// Like [`get_selected_track_2()`] but doesn't check if project is valid.
+    /// Returns the number of items on the given track.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid project.
-    ///
-    /// [`get_selected_track_2()`]: #method.get_selected_track_2
+    /// REAPER can crash if you pass an invalid track.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_selected_track_2_unchecked(
-        &self,
-        project: ProjectContext,
-        selected_track_index: u32,
-        master_track_behavior: MasterTrackBehavior,
-    ) -> Option<MediaTrack>
+    pub unsafe fn count_track_media_items(&self, track: MediaTrack) -> u32
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.low.GetSelectedTrack2(
-            project.to_raw(),
-            selected_track_index as i32,
-            master_track_behavior == MasterTrackBehavior::IncludeMasterTrack,
-        );
+        self.low.CountTrackMediaItems(track.as_ptr()) as u32
+    }
+
+    /// Returns the item at the given index of the given track.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_track_media_item(
+        &self,
+        track: MediaTrack,
+        item_index: u32,
+    ) -> Option<MediaItem>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self.low.GetTrackMediaItem(track.as_ptr(), item_index as i32);
         NonNull::new(ptr)
     }
 
-    /// Returns a selected item from the given project.
+    /// Gets an item attribute as numerical value.
     ///
-    /// # Panics
+    /// # Safety
     ///
-    /// Panics if the given project is not valid anymore.
+    /// REAPER can crash if you pass an invalid item.
     #[measure(ResponseTimeSingleThreaded)]
-    pub fn get_selected_media_item(
+    pub unsafe fn get_media_item_info_value(
         &self,
-        project: ProjectContext,
-        selected_item_index: u32,
-    ) -> Option<MediaItem>
+        item: MediaItem,
+        attribute_key: ItemAttributeKey,
+    ) -> f64
     where
         UsageScope: MainThreadOnly,
     {
-        self.require_valid_project(project);
-        unsafe { self.get_selected_media_item_unchecked(project, selected_item_index) }
+        self.require_main_thread();
+        self.low
+            .GetMediaItemInfo_Value(item.as_ptr(), attribute_key.into_raw().as_ptr())
     }
 
-    /// Like [`get_selected_media_item()`] but doesn't check if project is valid.
+    /// Returns the track which contains the given item.
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid project.
-    ///
-    /// [`get_selected_media_item()`]: #method.get_selected_media_item
+    /// REAPER can crash if you pass an invalid item.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_selected_media_item_unchecked(
-        &self,
-        project: ProjectContext,
-        selected_item_index: u32,
-    ) -> Option<MediaItem>
+    pub unsafe fn get_media_item_track(&self, item: MediaItem) -> Option<MediaTrack>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self
-            .low
-            .GetSelectedMediaItem(project.to_raw(), selected_item_index as i32);
+        let ptr = self.low.GetMediaItem_Track(item.as_ptr());
         NonNull::new(ptr)
     }
 
-    /// Returns the media source of the given media item take.
+    /// Returns the item which contains the given take.
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_media_item_take_source(&self, take: MediaItemTake) -> Option<PcmSource>
+    pub unsafe fn get_media_item_take_item(&self, take: MediaItemTake) -> Option<MediaItem>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.low.GetMediaItemTake_Source(take.as_ptr());
-        NonNull::new(ptr).map(PcmSource)
+        let ptr = self.low.GetMediaItemTake_Item(take.as_ptr());
+        NonNull::new(ptr)
     }
 
-    /// Unstable!!!
-    ///
-    /// Returns the project which contains this item.
+    /// Convenience function which returns the given item's GUID (`GUID`).
     ///
     /// # Safety
     ///
     /// REAPER can crash if you pass an invalid item.
-    // TODO-high-unstable Can this EVER be None?
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_item_project_context(&self, item: MediaItem) -> Option<ReaProject>
+    pub unsafe fn get_set_media_item_info_get_guid(&self, item: MediaItem) -> GUID
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.low.GetItemProjectContext(item.as_ptr());
-        NonNull::new(ptr)
+        let ptr = self.low.GetSetMediaItemInfo(
+            item.as_ptr(),
+            ItemAttributeKey::Guid.into_raw().as_ptr(),
+            null_mut(),
+        );
+        deref_as::<GUID>(ptr).expect("GUID pointer is null")
     }
 
-    /// Returns the active take in this item.
+    /// Convenience function which returns the given take's GUID (`GUID`).
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid item.
+    /// REAPER can crash if you pass an invalid take.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_active_take(&self, item: MediaItem) -> Option<MediaItemTake>
+    pub unsafe fn get_set_media_item_take_info_get_guid(&self, take: MediaItemTake) -> GUID
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        let ptr = self.low.GetActiveTake(item.as_ptr());
-        NonNull::new(ptr)
+        let ptr = self.low.GetSetMediaItemTakeInfo(
+            take.as_ptr(),
+            reaper_str!("GUID").as_ptr(),
+            null_mut(),
+        );
+        deref_as::<GUID>(ptr).expect("GUID pointer is null")
     }
 
     /// Selects exactly one track and deselects all others.
@@ -4536,35 +5897,295 @@ impl<UsageScope> Reaper<UsageScope> {
     ///
     /// # Errors
     ///
-    /// Returns an error if not successful (unclear when this happens).
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid track.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_track_state_chunk(
+        &self,
+        track: MediaTrack,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetTrackStateChunk(
+                track.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get track chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Returns the RPPXML state of the given item.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the chunk you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid item.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_item_state_chunk(
+        &self,
+        item: MediaItem,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetItemStateChunk(
+                item.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get item chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Returns the RPPXML state of the given envelope.
+    ///
+    /// With `buffer_size` you can tell REAPER how many bytes of the chunk you want.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_envelope_state_chunk(
+        &self,
+        envelope: TrackEnvelope,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetEnvelopeStateChunk(
+                envelope.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get envelope chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Returns the RPPXML state of the given item using `GetSetItemState2`.
+    ///
+    /// Works like [`get_item_state_chunk()`]. Exists because some older REAPER versions only provide this
+    /// variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid item.
+    ///
+    /// [`get_item_state_chunk()`]: #method.get_item_state_chunk
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_set_item_state_2_get(
+        &self,
+        item: MediaItem,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        // An empty buffer means "get".
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetSetItemState2(
+                item.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get item chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Sets the RPPXML state of the given item using `GetSetItemState2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (for example if the given chunk is not accepted).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid item.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_set_item_state_2_set<'a>(
+        &self,
+        item: MediaItem,
+        chunk: impl Into<ReaperStringArg<'a>>,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let chunk = chunk.into();
+        if chunk.as_reaper_str().as_c_str().to_bytes().is_empty() {
+            // An empty buffer would mean "get".
+            return Err(ReaperFunctionError::new("chunk must not be empty"));
+        }
+        let buffer_size = chunk.as_reaper_str().as_c_str().to_bytes_with_nul().len() as u32;
+        // The function takes a mutable buffer but doesn't write to it when setting.
+        let (_, successful) =
+            with_string_buffer_prefilled(chunk, buffer_size, |buffer, max_size| {
+                self.low.GetSetItemState2(
+                    item.as_ptr(),
+                    buffer,
+                    max_size,
+                    cache_hint == ChunkCacheHint::UndoMode,
+                )
+            });
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set item chunk (maybe chunk was invalid)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the RPPXML state of the given envelope using `GetSetEnvelopeState2`.
+    ///
+    /// Works like [`get_envelope_state_chunk()`]. Exists because some older REAPER versions only provide this
+    /// variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given buffer size is 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (unclear when this happens).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    ///
+    /// [`get_envelope_state_chunk()`]: #method.get_envelope_state_chunk
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn get_set_envelope_state_2_get(
+        &self,
+        envelope: TrackEnvelope,
+        buffer_size: u32,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<ReaperString>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        assert!(buffer_size > 0);
+        // An empty buffer means "get".
+        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
+            self.low.GetSetEnvelopeState2(
+                envelope.as_ptr(),
+                buffer,
+                max_size,
+                cache_hint == ChunkCacheHint::UndoMode,
+            )
+        });
+        if !successful {
+            return Err(ReaperFunctionError::new("couldn't get envelope chunk"));
+        }
+        Ok(chunk_content)
+    }
+
+    /// Sets the RPPXML state of the given envelope using `GetSetEnvelopeState2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (for example if the given chunk is not accepted).
     ///
     /// # Safety
     ///
-    /// REAPER can crash if you pass an invalid track.
+    /// REAPER can crash if you pass an invalid envelope.
     #[measure(ResponseTimeSingleThreaded)]
-    pub unsafe fn get_track_state_chunk(
+    pub unsafe fn get_set_envelope_state_2_set<'a>(
         &self,
-        track: MediaTrack,
-        buffer_size: u32,
+        envelope: TrackEnvelope,
+        chunk: impl Into<ReaperStringArg<'a>>,
         cache_hint: ChunkCacheHint,
-    ) -> ReaperFunctionResult<ReaperString>
+    ) -> ReaperFunctionResult<()>
     where
         UsageScope: MainThreadOnly,
     {
         self.require_main_thread();
-        assert!(buffer_size > 0);
-        let (chunk_content, successful) = with_string_buffer(buffer_size, |buffer, max_size| {
-            self.low.GetTrackStateChunk(
-                track.as_ptr(),
-                buffer,
-                max_size,
-                cache_hint == ChunkCacheHint::UndoMode,
-            )
-        });
+        let chunk = chunk.into();
+        if chunk.as_reaper_str().as_c_str().to_bytes().is_empty() {
+            // An empty buffer would mean "get".
+            return Err(ReaperFunctionError::new("chunk must not be empty"));
+        }
+        let buffer_size = chunk.as_reaper_str().as_c_str().to_bytes_with_nul().len() as u32;
+        // The function takes a mutable buffer but doesn't write to it when setting.
+        let (_, successful) =
+            with_string_buffer_prefilled(chunk, buffer_size, |buffer, max_size| {
+                self.low.GetSetEnvelopeState2(
+                    envelope.as_ptr(),
+                    buffer,
+                    max_size,
+                    cache_hint == ChunkCacheHint::UndoMode,
+                )
+            });
         if !successful {
-            return Err(ReaperFunctionError::new("couldn't get track chunk"));
+            return Err(ReaperFunctionError::new(
+                "couldn't set envelope chunk (maybe chunk was invalid)",
+            ));
         }
-        Ok(chunk_content)
+        Ok(())
     }
 
     /// Prompts the user for string values.
@@ -4762,6 +6383,72 @@ impl<UsageScope> Reaper<UsageScope> {
         Ok(())
     }
 
+    /// Sets the RPPXML state of the given item.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (for example if the given chunk is not accepted).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid item.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_item_state_chunk<'a>(
+        &self,
+        item: MediaItem,
+        chunk: impl Into<ReaperStringArg<'a>>,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.SetItemStateChunk(
+            item.as_ptr(),
+            chunk.into().as_ptr(),
+            cache_hint == ChunkCacheHint::UndoMode,
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set item chunk (maybe chunk was invalid)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets the RPPXML state of the given envelope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not successful (for example if the given chunk is not accepted).
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid envelope.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn set_envelope_state_chunk<'a>(
+        &self,
+        envelope: TrackEnvelope,
+        chunk: impl Into<ReaperStringArg<'a>>,
+        cache_hint: ChunkCacheHint,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful = self.low.SetEnvelopeStateChunk(
+            envelope.as_ptr(),
+            chunk.into().as_ptr(),
+            cache_hint == ChunkCacheHint::UndoMode,
+        );
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't set envelope chunk (maybe chunk was invalid)",
+            ));
+        }
+        Ok(())
+    }
+
     /// Shows or hides an FX user interface.
     ///
     /// # Safety
@@ -4878,6 +6565,76 @@ impl<UsageScope> Reaper<UsageScope> {
         FxChainVisibility::from_raw(raw)
     }
 
+    /// Shows or hides a take FX user interface.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_show(&self, take: MediaItemTake, instruction: TakeFxShowInstruction)
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.TakeFX_Show(
+            take.as_ptr(),
+            instruction.fx_index_to_raw(),
+            instruction.instruction_to_raw(),
+        );
+    }
+
+    /// Returns the floating window handle of the given take FX, if there is any.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_floating_window(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+    ) -> Option<Hwnd>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let ptr = self
+            .low
+            .TakeFX_GetFloatingWindow(take.as_ptr(), fx_index as i32);
+        NonNull::new(ptr)
+    }
+
+    /// Returns whether the user interface of the given take FX is open.
+    ///
+    /// *Open* means either visible in the FX chain window or visible in a floating window.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_open(&self, take: MediaItemTake, fx_index: u32) -> bool
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        self.low.TakeFX_GetOpen(take.as_ptr(), fx_index as i32)
+    }
+
+    /// Returns the visibility state of the given take's FX chain.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_chain_visible(&self, take: MediaItemTake) -> FxChainVisibility
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let raw = self.low.TakeFX_GetChainVisible(take.as_ptr());
+        FxChainVisibility::from_raw(raw)
+    }
+
     /// Sets the volume of the given track send or hardware output send.
     ///
     /// When choosing the send index, keep in mind that the hardware output sends (if any) come
@@ -5482,6 +7239,121 @@ impl<UsageScope> Reaper<UsageScope> {
         }
     }
 
+    /// Returns the index of the currently selected preset of the given take FX as well as the
+    /// total preset count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error e.g. if the FX doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_preset_index(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+    ) -> ReaperFunctionResult<TrackFxGetPresetIndexResult>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        // We zero this just for being safe
+        let mut num_presets = MaybeUninit::zeroed();
+        let index = self.low.TakeFX_GetPresetIndex(
+            take.as_ptr(),
+            fx_index as i32,
+            num_presets.as_mut_ptr(),
+        );
+        if index == -1 {
+            return Err(ReaperFunctionError::new(
+                "couldn't get FX preset index (maybe FX doesn't exist)",
+            ));
+        }
+        let num_presets = num_presets.assume_init();
+        Ok(TrackFxGetPresetIndexResult {
+            index: if index == num_presets {
+                None
+            } else {
+                Some(index as u32)
+            },
+            count: num_presets as u32,
+        })
+    }
+
+    /// Selects a preset of the given take FX.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error e.g. if the FX doesn't exist.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_set_preset_by_index(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        preset: FxPresetRef,
+    ) -> ReaperFunctionResult<()>
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        let successful =
+            self.low
+                .TakeFX_SetPresetByIndex(take.as_ptr(), fx_index as i32, preset.to_raw());
+        if !successful {
+            return Err(ReaperFunctionError::new(
+                "couldn't select FX preset (maybe FX doesn't exist)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns information about the currently selected preset of the given take FX.
+    ///
+    /// See [`track_fx_get_preset()`] for details.
+    ///
+    /// # Safety
+    ///
+    /// REAPER can crash if you pass an invalid take.
+    ///
+    /// [`track_fx_get_preset()`]: #method.track_fx_get_preset
+    #[measure(ResponseTimeSingleThreaded)]
+    pub unsafe fn take_fx_get_preset(
+        &self,
+        take: MediaItemTake,
+        fx_index: u32,
+        buffer_size: u32,
+    ) -> TrackFxGetPresetResult
+    where
+        UsageScope: MainThreadOnly,
+    {
+        self.require_main_thread();
+        if buffer_size == 0 {
+            let state_matches_preset =
+                self.low
+                    .TakeFX_GetPreset(take.as_ptr(), fx_index as i32, null_mut(), 0);
+            TrackFxGetPresetResult {
+                state_matches_preset,
+                name: None,
+            }
+        } else {
+            let (name, state_matches_preset) =
+                with_string_buffer(buffer_size, |buffer, max_size| {
+                    self.low
+                        .TakeFX_GetPreset(take.as_ptr(), fx_index as i32, buffer, max_size)
+                });
+            TrackFxGetPresetResult {
+                state_matches_preset,
+                name: if name.is_empty() { None } else { Some(name) },
+            }
+        }
+    }
+
     /// Grants temporary access to an already open MIDI input device.
     ///
     /// Passes `None` to the given function if the device doesn't exist, is not connected or is not
@@ -5523,6 +7395,30 @@ impl<UsageScope> Reaper<UsageScope> {
         use_device(arg.as_ref())
     }
 
+    /// Grants temporary mutable access to an already open MIDI input device.
+    ///
+    /// Works like [`get_midi_input()`] but makes it possible to modify the input buffer (see
+    /// [`get_read_buf_mut()`]), e.g. in order to filter or transform incoming MIDI events before
+    /// REAPER routes them to the tracks. For this to work, it needs to be called in the pre-phase
+    /// of the [audio hook].
+    ///
+    /// [`get_midi_input()`]: #method.get_midi_input
+    /// [`get_read_buf_mut()`]: struct.MidiInput.html#method.get_read_buf_mut
+    /// [audio hook]: struct.ReaperSession.html#method.audio_reg_hardware_hook_add
+    #[measure(ResponseTimeSingleThreaded)]
+    pub fn get_midi_input_mut<R>(
+        &self,
+        device_id: MidiInputDeviceId,
+        use_device: impl FnOnce(Option<&mut MidiInput>) -> R,
+    ) -> R
+    where
+        UsageScope: AudioThreadOnly,
+    {
+        let ptr = self.low.GetMidiInput(device_id.to_raw());
+        let mut arg = NonNull::new(ptr).map(MidiInput);
+        use_device(arg.as_mut())
+    }
+
     /// Returns if the given device is open (enabled in REAPER's MIDI preferences).
     #[measure(ResponseTimeMultiThreaded)]
     pub fn get_midi_input_is_open(&self, device_id: MidiInputDeviceId) -> bool
@@ -5761,6 +7657,21 @@ pub struct TimeMap2TimeToBeatsResult {
     pub time_signature: TimeSignature,
 }
 
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GetTempoTimeSigMarkerResult {
+    /// Position of the marker.
+    pub position: PositionInSeconds,
+    /// Index of the measure in which the marker is located.
+    pub measure_index: i32,
+    /// Position in beats within that measure.
+    pub beats_since_measure: PositionInBeats,
+    pub tempo: Bpm,
+    /// `None` if the marker doesn't change the time signature.
+    pub time_signature: Option<TimeSignature>,
+    /// Whether the tempo changes gradually towards the next marker.
+    pub linear_tempo: bool,
+}
+
 /// Time signature.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct TimeSignature {
@@ -5859,6 +7770,22 @@ pub enum GetFocusedFxResult {
     Unknown(Hidden<i32>),
 }
 
+fn require_peak_buffer_size(
+    buffer: &[f64],
+    channel_count: u32,
+    sample_count_per_channel: u32,
+    extra_type: Option<PeakExtraType>,
+) {
+    let block_count = if extra_type.is_some() { 3 } else { 2 };
+    let required_size = channel_count as usize * sample_count_per_channel as usize * block_count;
+    assert!(
+        buffer.len() >= required_size,
+        "peak buffer too small (required: {}, given: {})",
+        required_size,
+        buffer.len()
+    );
+}
+
 fn make_some_if_greater_than_zero(value: f64) -> Option<f64> {
     if value <= 0.0 || value.is_nan() {
         return None;
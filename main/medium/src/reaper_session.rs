@@ -11,12 +11,13 @@ use crate::{
     concat_reaper_strs, delegating_hook_command, delegating_hook_command_2,
     delegating_hook_post_command, delegating_hook_post_command_2, delegating_toggle_action,
     BufferingBehavior, CommandId, ControlSurface, ControlSurfaceAdapter, HookCommand, HookCommand2,
-    HookPostCommand, HookPostCommand2, MainThreadScope, MeasureAlignment, OnAudioBuffer,
-    OwnedAudioHookRegister, OwnedGaccelRegister, OwnedPreviewRegister, PluginRegistration,
-    ProjectContext, RealTimeAudioThreadScope, Reaper, ReaperFunctionError, ReaperFunctionResult,
-    ReaperMutex, ReaperString, ReaperStringArg, RegistrationHandle, RegistrationObject,
-    ToggleAction,
+    HookPostCommand, HookPostCommand2, MainThreadScope, MeasureAlignment, MediaDecoderFactory,
+    OnAudioBuffer, OwnedAudioHookRegister, OwnedGaccelRegister, OwnedPreviewRegister,
+    PluginRegistration, ProjectContext, RealTimeAudioThreadScope, Reaper, ReaperFunctionError,
+    ReaperFunctionResult, ReaperMutex, ReaperString, ReaperStringArg, RegistrationHandle,
+    RegistrationObject, ToggleAction,
 };
+use crate::media_decoder::{make_low_reaper_available_to_media_decoders, OwnedPcmSourceRegister};
 use reaper_low::raw::audio_hook_register_t;
 
 use enumflags2::BitFlags;
@@ -83,6 +84,8 @@ pub struct ReaperSession {
     /// While in here, the control surface is considered to be owned by REAPER, meaning that REAPER
     /// is supposed to have exclusive access to it.
     csurf_insts: HashMap<NonNull<c_void>, Box<Box<dyn IReaperControlSurface>>>,
+    /// Provides a safe place in memory for registered PCM source types (e.g. media decoders).
+    pcmsrc_registers: Keeper<OwnedPcmSourceRegister, raw::pcmsrc_register_t>,
     /// Provides a safe place in memory for plug-in registration keys (e.g. "API_myfunction").
     ///
     /// Also used for keeping track of registrations so they can be unregistered automatically on
@@ -107,6 +110,7 @@ impl ReaperSession {
             api_defs: Default::default(),
             audio_hook_registers: Default::default(),
             csurf_insts: Default::default(),
+            pcmsrc_registers: Default::default(),
             plugin_registrations: Default::default(),
             audio_hook_registrations: Default::default(),
             playing_preview_registers: Default::default(),
@@ -725,6 +729,141 @@ impl ReaperSession {
         unsafe { self.plugin_register_remove(RegistrationObject::Gaccel(handle)) };
     }
 
+    /// Registers a custom media decoder.
+    ///
+    /// This teaches REAPER to import a media format that it doesn't support natively. Whenever
+    /// REAPER encounters a file or source type, it asks the given factory for a
+    /// [`CustomMediaDecoder`] and wraps it in a PCM source.
+    ///
+    /// This function returns a handle which you can use to unregister the media decoder at any
+    /// time via [`plugin_register_remove_media_decoder()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registration failed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut session = reaper_medium::ReaperSession::default();
+    /// use reaper_medium::{
+    ///     reaper_str, CloseMediaArgs, CustomMediaDecoder, Hz, MediaDecoderFactory, OpenMediaArgs,
+    ///     ReadSamplesArgs, ReaperStr,
+    /// };
+    /// use std::path::Path;
+    ///
+    /// const SAMPLE_RATE: f64 = 44100.0;
+    ///
+    /// /// Decodes each "silence" file as one second of mono silence.
+    /// #[derive(Default)]
+    /// struct SilenceDecoder {
+    ///     is_open: bool,
+    ///     position: u64,
+    /// }
+    ///
+    /// impl CustomMediaDecoder for SilenceDecoder {
+    ///     fn duplicate(&mut self) -> Option<Box<dyn CustomMediaDecoder>> {
+    ///         Some(Box::new(SilenceDecoder::default()))
+    ///     }
+    ///
+    ///     fn open(&mut self, _: OpenMediaArgs) {
+    ///         self.is_open = true;
+    ///     }
+    ///
+    ///     fn close(&mut self, _: CloseMediaArgs) {
+    ///         self.is_open = false;
+    ///     }
+    ///
+    ///     fn get_file_name(&mut self) -> Option<&ReaperStr> {
+    ///         None
+    ///     }
+    ///
+    ///     fn get_type(&mut self) -> &ReaperStr {
+    ///         reaper_str!("SILENCE")
+    ///     }
+    ///
+    ///     fn is_open(&mut self) -> bool {
+    ///         self.is_open
+    ///     }
+    ///
+    ///     fn get_num_channels(&mut self) -> u32 {
+    ///         1
+    ///     }
+    ///
+    ///     fn get_sample_rate(&mut self) -> Hz {
+    ///         Hz::new(SAMPLE_RATE)
+    ///     }
+    ///
+    ///     fn get_length(&mut self) -> u64 {
+    ///         SAMPLE_RATE as u64
+    ///     }
+    ///
+    ///     fn get_position(&mut self) -> u64 {
+    ///         self.position
+    ///     }
+    ///
+    ///     fn set_position(&mut self, position: u64) {
+    ///         self.position = position;
+    ///     }
+    ///
+    ///     fn read_samples(&mut self, args: ReadSamplesArgs) -> u32 {
+    ///         let frames_left = self.get_length().saturating_sub(self.position);
+    ///         let frame_count = (args.frame_count as u64).min(frames_left) as usize;
+    ///         for sample in &mut args.buffer[..frame_count] {
+    ///             *sample = 0.0;
+    ///         }
+    ///         self.position += frame_count as u64;
+    ///         frame_count as u32
+    ///     }
+    /// }
+    ///
+    /// struct SilenceFormat;
+    ///
+    /// impl MediaDecoderFactory for SilenceFormat {
+    ///     fn create_from_file(
+    ///         file_name: &Path,
+    ///         _priority: u32,
+    ///     ) -> Option<Box<dyn CustomMediaDecoder>> {
+    ///         if file_name.extension()? != "silence" {
+    ///             return None;
+    ///         }
+    ///         Some(Box::new(SilenceDecoder::default()))
+    ///     }
+    /// }
+    /// session.plugin_register_add_media_decoder::<SilenceFormat>()?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Design
+    ///
+    /// Like [`plugin_register_add_hook_command()`], this takes a type parameter instead of an
+    /// instance because REAPER doesn't pass any context to the registered callbacks.
+    ///
+    /// [`CustomMediaDecoder`]: trait.CustomMediaDecoder.html
+    /// [`plugin_register_remove_media_decoder()`]: #method.plugin_register_remove_media_decoder
+    /// [`plugin_register_add_hook_command()`]: #method.plugin_register_add_hook_command
+    pub fn plugin_register_add_media_decoder<T: MediaDecoderFactory>(
+        &mut self,
+    ) -> ReaperFunctionResult<NonNull<raw::pcmsrc_register_t>> {
+        make_low_reaper_available_to_media_decoders(*self.reaper.low());
+        let handle = self
+            .pcmsrc_registers
+            .keep(OwnedPcmSourceRegister::new::<T>());
+        unsafe { self.plugin_register_add(RegistrationObject::PcmSrc(handle))? };
+        Ok(handle)
+    }
+
+    /// Unregisters a custom media decoder.
+    ///
+    /// PCM sources which have been created with this decoder continue to work.
+    pub fn plugin_register_remove_media_decoder(
+        &mut self,
+        handle: NonNull<raw::pcmsrc_register_t>,
+    ) {
+        unsafe { self.plugin_register_remove(RegistrationObject::PcmSrc(handle)) };
+        self.pcmsrc_registers.release(handle);
+    }
+
     /// Registers a hidden control surface.
     ///
     /// This is very useful for being notified by REAPER about all kinds of events in the main
@@ -1,6 +1,8 @@
 use crate::{Hidden, MidiInputDeviceId};
 
 use helgoboss_midi::Channel;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 
 /// Recording input of a track.
@@ -92,4 +94,48 @@ impl RecordingInput {
     }
 }
 
+/// Serializes as the integer used by REAPER itself (e.g. in the `I_RECINPUT` track attribute).
+#[cfg(feature = "serde")]
+impl Serialize for RecordingInput {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.to_raw())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RecordingInput {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = i32::deserialize(deserializer)?;
+        Ok(RecordingInput::from_raw(raw))
+    }
+}
+
 const ALL_MIDI_DEVICES_FACTOR: u32 = 63;
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialized_forms() {
+        // Given
+        let midi = RecordingInput::Midi {
+            device_id: Some(MidiInputDeviceId::new(2)),
+            channel: Some(Channel::new(0)),
+        };
+        // Then
+        assert_eq!(
+            serde_json::to_string(&RecordingInput::Stereo(2)).unwrap(),
+            "1026"
+        );
+        assert_eq!(serde_json::to_string(&midi).unwrap(), "4161");
+        assert_eq!(
+            serde_json::from_str::<RecordingInput>("4161").unwrap(),
+            midi
+        );
+        assert_eq!(
+            serde_json::from_str::<RecordingInput>("3000").unwrap(),
+            RecordingInput::Unknown(Hidden(3000))
+        );
+    }
+}
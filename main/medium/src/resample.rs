@@ -0,0 +1,414 @@
+use crate::{DurationInSeconds, Hz};
+use reaper_low::raw;
+use std::os::raw::c_void;
+use std::ptr::{null_mut, NonNull};
+
+/// Owned resampler.
+///
+/// This is REAPER's high-quality resampling engine as returned by [`resampler_create()`].
+/// Automatically destroys the associated C++ `REAPER_Resample_Interface` when dropped.
+///
+/// All methods are allowed to be called from any thread (including the real-time audio thread)
+/// because they don't allocate and don't touch any global REAPER state.
+///
+/// # Example
+///
+/// ```no_run
+/// # let reaper = reaper_medium::ReaperSession::default().reaper().clone();
+/// use reaper_medium::Hz;
+///
+/// let mut resampler = reaper.resampler_create()?;
+/// resampler.set_rates(Hz::new(44100.0), Hz::new(48000.0));
+/// let mut output = vec![0.0; 2 * 512];
+/// let input = resampler.resample_prepare(512, 2);
+/// let input_frame_count = (input.len() / 2) as u32;
+/// // Fill the input buffer ...
+/// resampler.resample_out(&mut output, input_frame_count, 512, 2);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`resampler_create()`]: struct.Reaper.html#method.resampler_create
+//
+// Case 3: Internals exposed: no | vtable: yes
+// ===========================================
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub struct Resampler {
+    inner: NonNull<raw::REAPER_Resample_Interface>,
+    // Input frame count and channel count of the buffer handed out by the last
+    // `resample_prepare()`. Needed to prevent REAPER from reading beyond that buffer.
+    prepared: Option<(u32, u32)>,
+}
+
+// The resampler doesn't have any thread affinity, it's just not safe to use it from multiple
+// threads at the same time (which is prevented by not implementing `Sync` and requiring
+// `&mut self`).
+unsafe impl Send for Resampler {}
+
+impl Resampler {
+    /// Takes ownership of the given resampler.
+    ///
+    /// # Safety
+    ///
+    /// You must guarantee that the given resampler is currently owner-less, otherwise double-free
+    /// or use-after-free can occur.
+    pub unsafe fn new_unchecked(inner: NonNull<raw::REAPER_Resample_Interface>) -> Self {
+        Self {
+            inner,
+            prepared: None,
+        }
+    }
+
+    /// Returns the pointer to this resampler.
+    pub fn as_ptr(&self) -> NonNull<raw::REAPER_Resample_Interface> {
+        self.inner
+    }
+
+    /// Sets the input and output sample rates.
+    pub fn set_rates(&mut self, rate_in: Hz, rate_out: Hz) {
+        unsafe { self.inner.as_ref().SetRates(rate_in.get(), rate_out.get()) };
+    }
+
+    /// Resets all buffers.
+    pub fn reset(&mut self) {
+        self.prepared = None;
+        unsafe { self.inner.as_ref().Reset() };
+    }
+
+    /// Returns the latency which is currently buffered.
+    pub fn get_current_latency(&self) -> DurationInSeconds {
+        let latency = unsafe { self.inner.as_ref().GetCurrentLatency() };
+        DurationInSeconds::new(latency.max(0.0))
+    }
+
+    /// Sets the resample mode.
+    ///
+    /// `None` means project default.
+    pub fn set_resample_mode(&mut self, mode: Option<u32>) {
+        let mode = mode.map(|m| m as isize).unwrap_or(-1);
+        self.extended(raw::RESAMPLE_EXT_SETRSMODE, mode as *mut c_void);
+    }
+
+    /// If enabled, the frame count passed to [`resample_prepare()`] is interpreted as number of
+    /// requested input frames (instead of output frames).
+    ///
+    /// [`resample_prepare()`]: #method.resample_prepare
+    pub fn set_feed_mode(&mut self, enabled: bool) {
+        self.extended(
+            raw::RESAMPLE_EXT_SETFEEDMODE,
+            enabled as isize as *mut c_void,
+        );
+    }
+
+    /// Prepares resampling the given number of output frames.
+    ///
+    /// Returns the (interleaved) input buffer which you need to fill before calling
+    /// [`resample_out()`]. Its length is the number of required input frames multiplied with the
+    /// given channel count.
+    ///
+    /// [`resample_out()`]: #method.resample_out
+    pub fn resample_prepare(&mut self, out_frame_count: u32, channel_count: u32) -> &mut [f64] {
+        let mut in_buffer: *mut raw::ReaSample = null_mut();
+        let in_frame_count = unsafe {
+            self.inner.as_ref().ResamplePrepare(
+                out_frame_count as _,
+                channel_count as _,
+                &mut in_buffer as *mut _,
+            )
+        };
+        if in_buffer.is_null() || in_frame_count <= 0 {
+            self.prepared = None;
+            return &mut [];
+        }
+        self.prepared = Some((in_frame_count as u32, channel_count));
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                in_buffer,
+                in_frame_count as usize * channel_count as usize,
+            )
+        }
+    }
+
+    /// Writes the resampled (interleaved) frames into the given output buffer.
+    ///
+    /// Must be called after filling the buffer returned by [`resample_prepare()`]. Returns the
+    /// number of output frames written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output buffer is too small for the given output frame count, if
+    /// [`resample_prepare()`] has not been called before, if the given input frame count exceeds
+    /// the one of the prepared input buffer or if the given channel count differs from the one
+    /// passed to [`resample_prepare()`].
+    ///
+    /// [`resample_prepare()`]: #method.resample_prepare
+    pub fn resample_out(
+        &mut self,
+        out: &mut [f64],
+        in_frame_count: u32,
+        out_frame_count: u32,
+        channel_count: u32,
+    ) -> u32 {
+        assert!(
+            out.len() >= out_frame_count as usize * channel_count as usize,
+            "output buffer too small"
+        );
+        let (prepared_in_frame_count, prepared_channel_count) =
+            self.prepared.take().expect("resampling not prepared");
+        assert!(
+            in_frame_count <= prepared_in_frame_count,
+            "input frame count exceeds prepared input buffer"
+        );
+        assert_eq!(
+            channel_count, prepared_channel_count,
+            "channel count differs from prepared one"
+        );
+        let count = unsafe {
+            self.inner.as_ref().ResampleOut(
+                out.as_mut_ptr(),
+                in_frame_count as _,
+                out_frame_count as _,
+                channel_count as _,
+            )
+        };
+        count.max(0) as u32
+    }
+
+    fn extended(&mut self, call: u32, parm_1: *mut c_void) -> i32 {
+        unsafe {
+            self.inner
+                .as_ref()
+                .Extended(call as _, parm_1, null_mut(), null_mut())
+        }
+    }
+}
+
+impl Drop for Resampler {
+    fn drop(&mut self) {
+        unsafe {
+            reaper_low::delete_cpp_resample_interface(self.inner);
+        }
+    }
+}
+
+/// Owned pitch shifter.
+///
+/// This is REAPER's pitch shift and time stretch engine as returned by
+/// [`reaper_get_pitch_shift_api()`]. Automatically destroys the associated C++
+/// `IReaperPitchShift` when dropped.
+///
+/// All methods are allowed to be called from any thread (including the real-time audio thread).
+///
+/// [`reaper_get_pitch_shift_api()`]: struct.Reaper.html#method.reaper_get_pitch_shift_api
+//
+// Case 3: Internals exposed: no | vtable: yes
+// ===========================================
+#[derive(Eq, PartialEq, Hash, Debug)]
+pub struct PitchShifter {
+    inner: NonNull<raw::IReaperPitchShift>,
+    channel_count: u32,
+}
+
+// Same reasoning as for `Resampler`.
+unsafe impl Send for PitchShifter {}
+
+impl PitchShifter {
+    /// Takes ownership of the given pitch shifter.
+    ///
+    /// # Safety
+    ///
+    /// You must guarantee that the given pitch shifter is currently owner-less, otherwise
+    /// double-free or use-after-free can occur.
+    pub unsafe fn new_unchecked(inner: NonNull<raw::IReaperPitchShift>) -> Self {
+        Self {
+            inner,
+            channel_count: 0,
+        }
+    }
+
+    /// Returns the pointer to this pitch shifter.
+    pub fn as_ptr(&self) -> NonNull<raw::IReaperPitchShift> {
+        self.inner
+    }
+
+    /// Sets the sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: Hz) {
+        unsafe { self.inner.as_ref().set_srate(sample_rate.get()) };
+    }
+
+    /// Sets the number of channels.
+    ///
+    /// Must be called before exchanging samples.
+    pub fn set_channel_count(&mut self, channel_count: u32) {
+        self.channel_count = channel_count;
+        unsafe { self.inner.as_ref().set_nch(channel_count as _) };
+    }
+
+    /// Returns the number of channels set via [`set_channel_count()`].
+    ///
+    /// [`set_channel_count()`]: #method.set_channel_count
+    pub fn channel_count(&self) -> u32 {
+        self.channel_count
+    }
+
+    /// Sets the pitch shift as factor (1.0 means no shift).
+    pub fn set_shift(&mut self, shift: f64) {
+        unsafe { self.inner.as_ref().set_shift(shift) };
+    }
+
+    /// Sets the formant shift as factor.
+    ///
+    /// Can be < 0 for "only shift when in formant preserve mode", so that you can use it for
+    /// effective rate changes etc. in that mode.
+    pub fn set_formant_shift(&mut self, shift: f64) {
+        unsafe { self.inner.as_ref().set_formant_shift(shift) };
+    }
+
+    /// Sets the tempo as factor (1.0 means no time stretch).
+    pub fn set_tempo(&mut self, tempo: f64) {
+        unsafe { self.inner.as_ref().set_tempo(tempo) };
+    }
+
+    /// Sets the algorithm to be used.
+    pub fn set_quality(&mut self, quality: PitchShiftQuality) {
+        unsafe { self.inner.as_ref().SetQualityParameter(quality.to_raw()) };
+    }
+
+    /// Resets all buffers and latency.
+    pub fn reset(&mut self) {
+        unsafe { self.inner.as_ref().Reset() };
+    }
+
+    /// Returns whether the pitch shifter is in reset state.
+    pub fn is_reset(&self) -> bool {
+        unsafe { self.inner.as_ref().IsReset() }
+    }
+
+    /// Returns an (interleaved) input buffer for the given number of frames.
+    ///
+    /// After filling it, call [`buffer_done()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel count has not been set.
+    ///
+    /// [`buffer_done()`]: #method.buffer_done
+    pub fn get_buffer(&mut self, frame_count: u32) -> &mut [f64] {
+        assert!(self.channel_count > 0, "channel count not set");
+        let ptr = unsafe { self.inner.as_ref().GetBuffer(frame_count as _) };
+        if ptr.is_null() {
+            return &mut [];
+        }
+        unsafe {
+            std::slice::from_raw_parts_mut(ptr, frame_count as usize * self.channel_count as usize)
+        }
+    }
+
+    /// Tells the pitch shifter how many frames of the input buffer have been filled.
+    pub fn buffer_done(&mut self, filled_frame_count: u32) {
+        unsafe { self.inner.as_ref().BufferDone(filled_frame_count as _) };
+    }
+
+    /// Makes sure all output is available.
+    pub fn flush_samples(&mut self) {
+        unsafe { self.inner.as_ref().FlushSamples() };
+    }
+
+    /// Writes output frames into the given (interleaved) buffer.
+    ///
+    /// Requests as many frames as fit into the buffer and returns the number of frames actually
+    /// written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel count has not been set.
+    pub fn get_samples(&mut self, buffer: &mut [f64]) -> u32 {
+        assert!(self.channel_count > 0, "channel count not set");
+        let requested_frame_count = buffer.len() / self.channel_count as usize;
+        let count = unsafe {
+            self.inner
+                .as_ref()
+                .GetSamples(requested_frame_count as _, buffer.as_mut_ptr())
+        };
+        count.max(0) as u32
+    }
+}
+
+impl Drop for PitchShifter {
+    fn drop(&mut self) {
+        unsafe {
+            reaper_low::delete_cpp_pitch_shift(self.inner);
+        }
+    }
+}
+
+/// Defines which pitch shift algorithm to use.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PitchShiftQuality {
+    /// Uses the project default.
+    ProjectDefault,
+    /// Uses the given mode and sub mode.
+    ///
+    /// See [`enum_pitch_shift_modes()`] and [`enum_pitch_shift_sub_modes()`].
+    ///
+    /// [`enum_pitch_shift_modes()`]: struct.Reaper.html#method.enum_pitch_shift_modes
+    /// [`enum_pitch_shift_sub_modes()`]: struct.Reaper.html#method.enum_pitch_shift_sub_modes
+    Specific {
+        mode: PitchShiftMode,
+        sub_mode: PitchShiftSubMode,
+    },
+}
+
+impl PitchShiftQuality {
+    /// Converts an integer as returned by the low-level API to a pitch shift quality.
+    pub fn from_raw(v: i32) -> PitchShiftQuality {
+        if v < 0 {
+            return PitchShiftQuality::ProjectDefault;
+        }
+        PitchShiftQuality::Specific {
+            mode: PitchShiftMode::new((v >> 16) as u32),
+            sub_mode: PitchShiftSubMode::new((v & 0xffff) as u32),
+        }
+    }
+
+    /// Converts this value to an integer as expected by the low-level API.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            PitchShiftQuality::ProjectDefault => -1,
+            PitchShiftQuality::Specific { mode, sub_mode } => {
+                ((mode.get() << 16) | (sub_mode.get() & 0xffff)) as i32
+            }
+        }
+    }
+}
+
+/// A pitch shift mode index.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct PitchShiftMode(pub(crate) u32);
+
+impl PitchShiftMode {
+    /// Creates the pitch shift mode.
+    pub fn new(value: u32) -> PitchShiftMode {
+        PitchShiftMode(value)
+    }
+
+    /// Returns the wrapped value.
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// A pitch shift sub mode index.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct PitchShiftSubMode(pub(crate) u32);
+
+impl PitchShiftSubMode {
+    /// Creates the pitch shift sub mode.
+    pub fn new(value: u32) -> PitchShiftSubMode {
+        PitchShiftSubMode(value)
+    }
+
+    /// Returns the wrapped value.
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
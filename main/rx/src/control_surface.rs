@@ -1,6 +1,9 @@
 use crate::{EventStreamSubject, ReactiveEvent};
-use reaper_high::{AvailablePanValue, ChangeEvent, Fx, FxParameter, Project, Track, TrackRoute};
-use reaper_medium::Pan;
+use reaper_high::{
+    AvailablePanValue, BookmarkType, ChangeEvent, Fx, FxParameter, Guid, Item, Project, Reaper,
+    Take, Track, TrackRoute,
+};
+use reaper_medium::{BookmarkId, Pan, PositionInSeconds, TrackArea};
 use rxrust::prelude::*;
 use std::cell::RefCell;
 use std::fmt;
@@ -126,9 +129,20 @@ impl ControlSurfaceRxMiddleware {
                     self.rx.master_playrate_touched.borrow_mut().next(());
                 }
             }
-            PlayStateChanged(_) => self.rx.play_state_changed.borrow_mut().next(()),
+            PlayStateChanged(e) => {
+                if e.project == Reaper::get().current_project() {
+                    self.rx.play_state_changed.borrow_mut().next(());
+                } else {
+                    self.rx
+                        .background_play_state_changed
+                        .borrow_mut()
+                        .next(e.project);
+                }
+            }
             RepeatStateChanged(_) => self.rx.repeat_state_changed.borrow_mut().next(()),
             ProjectClosed(e) => self.rx.project_closed.borrow_mut().next(e.project),
+            ProjectOpened(e) => self.rx.project_opened.borrow_mut().next(e.project),
+            ProjectTabReordered(e) => self.rx.project_tab_reordered.borrow_mut().next(e.project),
             GlobalAutomationOverrideChanged(_) => self
                 .rx
                 .global_automation_override_changed
@@ -142,6 +156,62 @@ impl ControlSurfaceRxMiddleware {
                 .borrow_mut()
                 .next(e.track),
             TrackSendCountChanged(e) => self.rx.track_send_count_changed.borrow_mut().next(e.track),
+            ItemAdded(e) => self.rx.item_added.borrow_mut().next(e.item),
+            ItemRemoved(e) => self.rx.item_removed.borrow_mut().next(e.guid),
+            ItemMoved(e) => self.rx.item_moved.borrow_mut().next(e.item),
+            ItemLengthChanged(e) => self.rx.item_length_changed.borrow_mut().next(e.item),
+            ItemSelectedChanged(e) => self
+                .rx
+                .item_selected_changed
+                .borrow_mut()
+                .next((e.item, e.new_value)),
+            ActiveTakeChanged(e) => self.rx.active_take_changed.borrow_mut().next(e.item),
+            TakeNameChanged(e) => self.rx.take_name_changed.borrow_mut().next(e.take),
+            MarkerAdded(e) => self
+                .rx
+                .marker_added
+                .borrow_mut()
+                .next((e.bookmark_type, e.id)),
+            MarkerRemoved(e) => self
+                .rx
+                .marker_removed
+                .borrow_mut()
+                .next((e.bookmark_type, e.id)),
+            MarkerRenamed(e) => self
+                .rx
+                .marker_renamed
+                .borrow_mut()
+                .next((e.bookmark_type, e.id)),
+            MarkerMoved(e) => self.rx.marker_moved.borrow_mut().next(e.id),
+            RegionBoundsChanged(e) => self.rx.region_bounds_changed.borrow_mut().next(e.id),
+            BookmarkColorChanged(e) => self
+                .rx
+                .bookmark_color_changed
+                .borrow_mut()
+                .next((e.bookmark_type, e.id)),
+            EditCursorMoved(e) => self.rx.edit_cursor_moved.borrow_mut().next(e.new_position),
+            TimeSelectionChanged(_) => self.rx.time_selection_changed.borrow_mut().next(()),
+            LoopPointsChanged(_) => self.rx.loop_points_changed.borrow_mut().next(()),
+            CurrentRegionChanged(_) => self.rx.current_region_changed.borrow_mut().next(()),
+            TrackColorChanged(e) => self.rx.track_color_changed.borrow_mut().next(e.track),
+            TrackVisibilityChanged(e) => self
+                .rx
+                .track_visibility_changed
+                .borrow_mut()
+                .next((e.track, e.area)),
+            TrackFolderStateChanged(e) => self
+                .rx
+                .track_folder_state_changed
+                .borrow_mut()
+                .next(e.track),
+            TrackPhaseChanged(e) => self
+                .rx
+                .track_phase_changed
+                .borrow_mut()
+                .next((e.track, e.new_value)),
+            TrackWidthChanged(e) => self.rx.track_width_changed.borrow_mut().next(e.track),
+            TrackAttributeChanged(e) => self.rx.track_attribute_changed.borrow_mut().next(e.track),
+            TempoMapChanged(e) => self.rx.tempo_map_changed.borrow_mut().next(e.start),
         };
     }
 }
@@ -190,9 +260,36 @@ pub struct ControlSurfaceRx {
     pub master_playrate_changed: EventStreamSubject<()>,
     pub master_playrate_touched: EventStreamSubject<()>,
     pub play_state_changed: EventStreamSubject<()>,
+    pub background_play_state_changed: EventStreamSubject<Project>,
     pub repeat_state_changed: EventStreamSubject<()>,
     pub project_closed: EventStreamSubject<Project>,
+    pub project_opened: EventStreamSubject<Project>,
+    pub project_tab_reordered: EventStreamSubject<Project>,
     pub bookmarks_changed: EventStreamSubject<()>,
+    pub item_added: EventStreamSubject<Item>,
+    pub item_removed: EventStreamSubject<Guid>,
+    pub item_moved: EventStreamSubject<Item>,
+    pub item_length_changed: EventStreamSubject<Item>,
+    pub item_selected_changed: EventStreamSubject<(Item, bool)>,
+    pub active_take_changed: EventStreamSubject<Item>,
+    pub take_name_changed: EventStreamSubject<Take>,
+    pub marker_added: EventStreamSubject<(BookmarkType, BookmarkId)>,
+    pub marker_removed: EventStreamSubject<(BookmarkType, BookmarkId)>,
+    pub marker_renamed: EventStreamSubject<(BookmarkType, BookmarkId)>,
+    pub marker_moved: EventStreamSubject<BookmarkId>,
+    pub region_bounds_changed: EventStreamSubject<BookmarkId>,
+    pub bookmark_color_changed: EventStreamSubject<(BookmarkType, BookmarkId)>,
+    pub edit_cursor_moved: EventStreamSubject<PositionInSeconds>,
+    pub time_selection_changed: EventStreamSubject<()>,
+    pub loop_points_changed: EventStreamSubject<()>,
+    pub current_region_changed: EventStreamSubject<()>,
+    pub track_color_changed: EventStreamSubject<Track>,
+    pub track_visibility_changed: EventStreamSubject<(Track, TrackArea)>,
+    pub track_folder_state_changed: EventStreamSubject<Track>,
+    pub track_phase_changed: EventStreamSubject<(Track, bool)>,
+    pub track_width_changed: EventStreamSubject<Track>,
+    pub track_attribute_changed: EventStreamSubject<Track>,
+    pub tempo_map_changed: EventStreamSubject<PositionInSeconds>,
 }
 
 impl fmt::Debug for ControlSurfaceRx {
@@ -248,9 +345,36 @@ impl ControlSurfaceRx {
             master_playrate_changed: default(),
             master_playrate_touched: default(),
             play_state_changed: default(),
+            background_play_state_changed: default(),
             repeat_state_changed: default(),
             project_closed: default(),
+            project_opened: default(),
+            project_tab_reordered: default(),
             bookmarks_changed: default(),
+            item_added: default(),
+            item_removed: default(),
+            item_moved: default(),
+            item_length_changed: default(),
+            item_selected_changed: default(),
+            active_take_changed: default(),
+            take_name_changed: default(),
+            marker_added: default(),
+            marker_removed: default(),
+            marker_renamed: default(),
+            marker_moved: default(),
+            region_bounds_changed: default(),
+            bookmark_color_changed: default(),
+            edit_cursor_moved: default(),
+            time_selection_changed: default(),
+            loop_points_changed: default(),
+            current_region_changed: default(),
+            track_color_changed: default(),
+            track_visibility_changed: default(),
+            track_folder_state_changed: default(),
+            track_phase_changed: default(),
+            track_width_changed: default(),
+            track_attribute_changed: default(),
+            tempo_map_changed: default(),
         }
     }
 
@@ -323,10 +447,17 @@ impl ControlSurfaceRx {
         self.master_playrate_touched.borrow().clone()
     }
 
+    /// Only fires for the current project.
     pub fn play_state_changed(&self) -> impl ReactiveEvent<()> {
         self.play_state_changed.borrow().clone()
     }
 
+    /// Fires for projects in background tabs. Only fires if `run()` is called on the change
+    /// detection middleware.
+    pub fn background_play_state_changed(&self) -> impl ReactiveEvent<Project> {
+        self.background_play_state_changed.borrow().clone()
+    }
+
     pub fn repeat_state_changed(&self) -> impl ReactiveEvent<()> {
         self.repeat_state_changed.borrow().clone()
     }
@@ -427,6 +558,131 @@ impl ControlSurfaceRx {
         self.track_route_pan_touched.borrow().clone()
     }
 
+    pub fn item_added(&self) -> impl ReactiveEvent<Item> {
+        self.item_added.borrow().clone()
+    }
+
+    /// Delivers the GUID of the removed item.
+    pub fn item_removed(&self) -> impl ReactiveEvent<Guid> {
+        self.item_removed.borrow().clone()
+    }
+
+    pub fn item_moved(&self) -> impl ReactiveEvent<Item> {
+        self.item_moved.borrow().clone()
+    }
+
+    pub fn item_length_changed(&self) -> impl ReactiveEvent<Item> {
+        self.item_length_changed.borrow().clone()
+    }
+
+    /// New
+    pub fn item_selected_changed(&self) -> impl ReactiveEvent<(Item, bool)> {
+        self.item_selected_changed.borrow().clone()
+    }
+
+    pub fn active_take_changed(&self) -> impl ReactiveEvent<Item> {
+        self.active_take_changed.borrow().clone()
+    }
+
+    pub fn take_name_changed(&self) -> impl ReactiveEvent<Take> {
+        self.take_name_changed.borrow().clone()
+    }
+
+    /// Fires for markers and regions.
+    pub fn marker_added(&self) -> impl ReactiveEvent<(BookmarkType, BookmarkId)> {
+        self.marker_added.borrow().clone()
+    }
+
+    /// Fires for markers and regions.
+    pub fn marker_removed(&self) -> impl ReactiveEvent<(BookmarkType, BookmarkId)> {
+        self.marker_removed.borrow().clone()
+    }
+
+    /// Fires for markers and regions.
+    pub fn marker_renamed(&self) -> impl ReactiveEvent<(BookmarkType, BookmarkId)> {
+        self.marker_renamed.borrow().clone()
+    }
+
+    pub fn marker_moved(&self) -> impl ReactiveEvent<BookmarkId> {
+        self.marker_moved.borrow().clone()
+    }
+
+    pub fn region_bounds_changed(&self) -> impl ReactiveEvent<BookmarkId> {
+        self.region_bounds_changed.borrow().clone()
+    }
+
+    pub fn bookmark_color_changed(&self) -> impl ReactiveEvent<(BookmarkType, BookmarkId)> {
+        self.bookmark_color_changed.borrow().clone()
+    }
+
+    /// Only fires if `run()` is called on the change detection middleware.
+    pub fn edit_cursor_moved(&self) -> impl ReactiveEvent<PositionInSeconds> {
+        self.edit_cursor_moved.borrow().clone()
+    }
+
+    /// Only fires if `run()` is called on the change detection middleware.
+    pub fn time_selection_changed(&self) -> impl ReactiveEvent<()> {
+        self.time_selection_changed.borrow().clone()
+    }
+
+    /// Only fires if `run()` is called on the change detection middleware.
+    pub fn loop_points_changed(&self) -> impl ReactiveEvent<()> {
+        self.loop_points_changed.borrow().clone()
+    }
+
+    /// Fires if the play cursor (or edit cursor if not playing) enters another region or passes a
+    /// marker. Only fires if `run()` is called on the change detection middleware.
+    pub fn current_region_changed(&self) -> impl ReactiveEvent<()> {
+        self.current_region_changed.borrow().clone()
+    }
+
+    /// Only fires if `CustomColor` is watched, see
+    /// `ChangeDetectionMiddleware::watch_track_attributes()`.
+    pub fn track_color_changed(&self) -> impl ReactiveEvent<Track> {
+        self.track_color_changed.borrow().clone()
+    }
+
+    /// Only fires if `ShowInTcp` or `ShowInMixer` is watched.
+    pub fn track_visibility_changed(&self) -> impl ReactiveEvent<(Track, TrackArea)> {
+        self.track_visibility_changed.borrow().clone()
+    }
+
+    /// Only fires if `FolderDepth` or `FolderCompact` is watched.
+    pub fn track_folder_state_changed(&self) -> impl ReactiveEvent<Track> {
+        self.track_folder_state_changed.borrow().clone()
+    }
+
+    /// Only fires if `Phase` is watched.
+    pub fn track_phase_changed(&self) -> impl ReactiveEvent<(Track, bool)> {
+        self.track_phase_changed.borrow().clone()
+    }
+
+    /// Only fires if `Width` is watched.
+    pub fn track_width_changed(&self) -> impl ReactiveEvent<Track> {
+        self.track_width_changed.borrow().clone()
+    }
+
+    /// Fires for watched attributes which don't have a more specific event.
+    pub fn track_attribute_changed(&self) -> impl ReactiveEvent<Track> {
+        self.track_attribute_changed.borrow().clone()
+    }
+
+    /// Sends the start of the affected range. Only fires if `run()` is called on the change
+    /// detection middleware.
+    pub fn tempo_map_changed(&self) -> impl ReactiveEvent<PositionInSeconds> {
+        self.tempo_map_changed.borrow().clone()
+    }
+
+    /// Only fires if `run()` is called on the change detection middleware.
+    pub fn project_opened(&self) -> impl ReactiveEvent<Project> {
+        self.project_opened.borrow().clone()
+    }
+
+    /// Only fires if `run()` is called on the change detection middleware.
+    pub fn project_tab_reordered(&self) -> impl ReactiveEvent<Project> {
+        self.project_tab_reordered.borrow().clone()
+    }
+
     /// Only fires if `run()` is called on the driver.
     pub fn main_thread_idle(&self) -> impl ReactiveEvent<()> {
         self.main_thread_idle.borrow().clone()
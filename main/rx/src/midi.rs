@@ -4,47 +4,88 @@ use reaper_medium::{
     MidiFrameOffset, MidiInputDeviceId, OnAudioBufferArgs, RealTimeAudioThreadScope,
 };
 use rxrust::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
 
 pub struct MidiRxMiddleware {
     medium_reaper: reaper_medium::Reaper<RealTimeAudioThreadScope>,
     rx: MidiRx,
+    // Shared with the currently emitted intercepted event. Reused in order to not allocate in the
+    // real-time audio thread.
+    decision: Rc<Cell<MidiInterceptDecision>>,
 }
 
 #[derive(Clone, Default)]
 pub struct MidiRx {
     midi_message_received: LocalSubject<'static, MidiEvent<RawShortMessage>, ()>,
+    midi_message_intercepted: LocalSubject<'static, InterceptedMidiEvent, ()>,
 }
 
 impl MidiRxMiddleware {
+    pub fn new(
+        medium_reaper: reaper_medium::Reaper<RealTimeAudioThreadScope>,
+        rx: MidiRx,
+    ) -> MidiRxMiddleware {
+        MidiRxMiddleware {
+            medium_reaper,
+            rx,
+            decision: Default::default(),
+        }
+    }
+
     pub fn on_audio_buffer(&mut self, args: OnAudioBufferArgs) {
+        // Only in the pre-phase modifications of the input buffer have an effect on what's routed
+        // to the tracks.
         if args.is_post {
             return;
         }
-        let subject = &mut self.rx.midi_message_received;
-        if subject.subscribed_size() == 0 {
+        let rx = &mut self.rx;
+        let notify_received = rx.midi_message_received.subscribed_size() > 0;
+        let intercept = rx.midi_message_intercepted.subscribed_size() > 0;
+        if !notify_received && !intercept {
             return;
         }
+        let decision = &self.decision;
         for i in 0..self.medium_reaper.get_max_midi_inputs() {
-            self.medium_reaper
-                .get_midi_input(MidiInputDeviceId::new(i as u8), |input| {
-                    let input = if let Some(i) = input {
-                        i
-                    } else {
-                        return;
-                    };
-                    let evt_list = input.get_read_buf();
-                    for evt in evt_list.enum_items(0) {
-                        let msg = evt.message();
-                        if msg.r#type() == ShortMessageType::ActiveSensing {
-                            // TODO-low We should forward active sensing. Can be filtered out
-                            // later.
-                            continue;
+            let device_id = MidiInputDeviceId::new(i as u8);
+            self.medium_reaper.get_midi_input_mut(device_id, |input| {
+                let input = if let Some(i) = input {
+                    i
+                } else {
+                    return;
+                };
+                let evt_list = input.get_read_buf_mut();
+                evt_list.retain_mut(|evt| {
+                    let msg = evt.message();
+                    if msg.r#type() == ShortMessageType::ActiveSensing {
+                        // TODO-low We should forward active sensing. Can be filtered out
+                        // later.
+                        return true;
+                    }
+                    let owned_msg: RawShortMessage = msg.to_other();
+                    let owned_evt = MidiEvent::new(evt.frame_offset(), owned_msg);
+                    if notify_received {
+                        rx.midi_message_received.next(owned_evt);
+                    }
+                    if !intercept {
+                        return true;
+                    }
+                    decision.set(MidiInterceptDecision::Keep);
+                    rx.midi_message_intercepted.next(InterceptedMidiEvent {
+                        device_id,
+                        event: owned_evt,
+                        decision: decision.clone(),
+                    });
+                    match decision.get() {
+                        MidiInterceptDecision::Keep => true,
+                        MidiInterceptDecision::Swallow => false,
+                        MidiInterceptDecision::Replace(new_msg) => {
+                            evt.set_message(new_msg);
+                            true
                         }
-                        let owned_msg: RawShortMessage = msg.to_other();
-                        let owned_evt = MidiEvent::new(evt.frame_offset(), owned_msg);
-                        subject.next(owned_evt);
                     }
                 });
+            });
         }
     }
 }
@@ -53,6 +94,14 @@ impl MidiRx {
     pub fn midi_message_received(&self) -> impl ReactiveEvent<MidiEvent<RawShortMessage>> {
         self.midi_message_received.clone()
     }
+
+    /// Fires for each incoming short MIDI message before REAPER routes it to the tracks.
+    ///
+    /// Subscribers can swallow or replace the message by calling the corresponding methods of
+    /// the emitted event. If multiple subscribers decide, the last decision wins.
+    pub fn midi_message_intercepted(&self) -> impl ReactiveEvent<InterceptedMidiEvent> {
+        self.midi_message_intercepted.clone()
+    }
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
@@ -65,4 +114,53 @@ impl<M> MidiEvent<M> {
     pub fn new(frame_offset: MidiFrameOffset, msg: M) -> MidiEvent<M> {
         MidiEvent { frame_offset, msg }
     }
+
+    pub fn frame_offset(&self) -> MidiFrameOffset {
+        self.frame_offset
+    }
+
+    pub fn msg(&self) -> &M {
+        &self.msg
+    }
+}
+
+/// An incoming MIDI event which can be swallowed or replaced.
+#[derive(Clone, Debug)]
+pub struct InterceptedMidiEvent {
+    device_id: MidiInputDeviceId,
+    event: MidiEvent<RawShortMessage>,
+    decision: Rc<Cell<MidiInterceptDecision>>,
+}
+
+impl InterceptedMidiEvent {
+    pub fn device_id(&self) -> MidiInputDeviceId {
+        self.device_id
+    }
+
+    pub fn event(&self) -> MidiEvent<RawShortMessage> {
+        self.event
+    }
+
+    /// Prevents the message from reaching the tracks.
+    pub fn swallow(&self) {
+        self.decision.set(MidiInterceptDecision::Swallow);
+    }
+
+    /// Replaces the message with the given one.
+    pub fn replace(&self, msg: RawShortMessage) {
+        self.decision.set(MidiInterceptDecision::Replace(msg));
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum MidiInterceptDecision {
+    Keep,
+    Swallow,
+    Replace(RawShortMessage),
+}
+
+impl Default for MidiInterceptDecision {
+    fn default() -> Self {
+        MidiInterceptDecision::Keep
+    }
 }
@@ -14,7 +14,7 @@ use rxrust::prelude::*;
 
 use std::collections::VecDeque;
 
-use reaper_medium::RegistrationHandle;
+use reaper_medium::{RegistrationHandle, TrackAttributeKey};
 use reaper_rx::{ActionRxHookPostCommand, ActionRxHookPostCommand2, ControlSurfaceRxMiddleware};
 use slog::info;
 use std::ops::Deref;
@@ -45,8 +45,11 @@ struct TestControlSurfaceMiddleware {
 
 impl TestControlSurfaceMiddleware {
     fn new() -> Self {
+        let change_detection_middleware = ChangeDetectionMiddleware::new();
+        // Necessary for testing the detection of track attribute changes
+        change_detection_middleware.watch_track_attributes(vec![TrackAttributeKey::CustomColor], 1);
         Self {
-            change_detection_middleware: ChangeDetectionMiddleware::new(),
+            change_detection_middleware,
             rx_middleware: ControlSurfaceRxMiddleware::new(Test::control_surface_rx().clone()),
             main_task_middleware: MainTaskMiddleware::new(
                 Reaper::get().logger().clone(),
@@ -60,6 +63,9 @@ impl TestControlSurfaceMiddleware {
 impl ControlSurfaceMiddleware for TestControlSurfaceMiddleware {
     fn run(&mut self) {
         self.main_task_middleware.run();
+        let rx_middleware = &self.rx_middleware;
+        self.change_detection_middleware
+            .run(|e| rx_middleware.handle_change(e));
     }
 
     fn handle_event(&self, event: ControlSurfaceEvent) -> bool {
@@ -7,33 +7,37 @@ use std::ops::Deref;
 use c_str_macro::c_str;
 
 use reaper_high::{
-    get_media_track_guid, toggleable, ActionCharacter, ActionKind, FxChain, FxParameterCharacter,
-    FxParameterValueRange, Guid, Pan, PlayRate, Reaper, SendPartnerType, Tempo, Track,
+    get_media_track_guid, toggleable, ActionCharacter, ActionKind, BookmarkType,
+    ChangeDetectionMiddleware, ChangeEvent, ControlSurfaceEvent, FxChain, FxParameterCharacter,
+    FxParameterValueRange, Guid, Item, Pan, PlayRate, Reaper, SendPartnerType, Tempo, Track,
     TrackRoutePartner, Volume, Width,
 };
 use rxrust::prelude::*;
 
 use crate::api::{step, Test, TestStep};
 
-use super::invocation_mock::observe_invocations;
+use super::invocation_mock::{create_invocation_mock, observe_invocations, InvocationMock};
 use crate::api::VersionRestriction::AllVersions;
 use helgoboss_midi::test_util::{channel, key_number, u7};
 use helgoboss_midi::{RawShortMessage, ShortMessageFactory};
 
 use reaper_medium::ProjectContext::CurrentProject;
 use reaper_medium::{
-    reaper_str, AutoSeekBehavior, AutomationMode, Bpm, CommandId, Db, DurationInSeconds,
-    FxPresetRef, GangBehavior, InputMonitoringMode, MasterTrackBehavior, MidiInputDeviceId,
-    MidiOutputDeviceId, NormalizedPlayRate, PlaybackSpeedFactor, PositionInSeconds,
-    ReaperNormalizedFxParamValue, ReaperPanValue, ReaperVersion, ReaperVolumeValue,
-    ReaperWidthValue, RecordingInput, SoloMode, StuffMidiMessageTarget, TrackLocation,
-    UndoBehavior, ValueChange,
+    reaper_str, AutoSeekBehavior, AutomationMode, BookmarkId, Bpm, CommandId, Db,
+    DurationInSeconds, ExtSetProjectMarkerChangeArgs, FxPresetRef, GangBehavior,
+    InputMonitoringMode, MasterTrackBehavior, MidiInputDeviceId, MidiOutputDeviceId,
+    NormalizedPlayRate, PlaybackSpeedFactor, PositionInSeconds, ReaperNormalizedFxParamValue,
+    ReaperPanValue, ReaperVersion, ReaperVolumeValue, ReaperWidthValue, RecordingInput, SoloMode,
+    StuffMidiMessageTarget, TrackAttributeKey, TrackLocation, UndoBehavior, ValueChange,
 };
 
 use reaper_low::{raw, Swell};
 use reaper_rx::ActionRxProvider;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::ffi::CStr;
 use std::os::raw::{c_int, c_void};
-use std::ptr::null_mut;
+use std::ptr::{null_mut, NonNull};
 use std::rc::Rc;
 
 /// Creates all integration test steps to be executed. The order matters!
@@ -136,6 +140,7 @@ pub fn create_test_steps() -> impl Iterator<Item = TestStep> {
         set_project_tempo(),
         swell(),
         metrics(),
+        rename_marker_within_marker_listener(),
     ]
     .into_iter();
     let output_fx_steps = create_fx_steps("Output FX chain", || {
@@ -144,11 +149,284 @@ pub fn create_test_steps() -> impl Iterator<Item = TestStep> {
     let input_fx_steps = create_fx_steps("Input FX chain", || {
         get_track(1).map(|t| t.input_fx_chain())
     });
+    let polled_change_steps = vec![
+        add_item(),
+        move_item(),
+        remove_item(),
+        add_markers(),
+        rename_marker(),
+        change_track_color(),
+    ]
+    .into_iter()
+    .flatten();
     iter::empty()
         .chain(steps_a)
         .chain(output_fx_steps)
         .chain(input_fx_steps)
         .chain(steps_b)
+        .chain(polled_change_steps)
+}
+
+fn change_track_color() -> Vec<TestStep> {
+    polled_change_steps(
+        "Change track color",
+        |mock, checked| {
+            // Given
+            let track = get_track(0)?;
+            // When
+            Test::control_surface_rx()
+                .track_color_changed()
+                .take_until(checked)
+                .subscribe(move |t| {
+                    mock.invoke(t);
+                });
+            unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .set_media_track_info_value(
+                        track.raw(),
+                        TrackAttributeKey::CustomColor,
+                        (0x0100_0000 | 0x00ff_0000) as f64,
+                    )
+                    .map_err(|_| "couldn't set track color")?;
+            }
+            Ok(track)
+        },
+        |mock, track| {
+            // Then
+            assert_eq!(mock.invocation_count(), 1);
+            assert_eq!(mock.last_arg(), track);
+            Ok(())
+        },
+    )
+}
+
+fn rename_marker_within_marker_listener() -> TestStep {
+    step(
+        AllVersions,
+        "Rename marker within marker listener",
+        |_, _| {
+            // Given
+            let low = Reaper::get().medium_reaper().low();
+            let marker_id = unsafe {
+                low.AddProjectMarker2(null_mut(), false, 3.0, 0.0, c_str!("A").as_ptr(), -1, 0)
+            };
+            assert!(marker_id >= 0);
+            let rename = |name: &CStr| unsafe {
+                low.SetProjectMarker(marker_id, false, 3.0, 0.0, name.as_ptr());
+            };
+            let event =
+                ControlSurfaceEvent::ExtSetProjectMarkerChange(ExtSetProjectMarkerChangeArgs);
+            let middleware = ChangeDetectionMiddleware::new();
+            // The first event just captures the initial state
+            middleware.process(event, |_| {});
+            let rename_count = Cell::new(0);
+            // When
+            rename(c_str!("B"));
+            middleware.process(event, |e| {
+                if let ChangeEvent::MarkerRenamed(_) = e {
+                    rename_count.set(rename_count.get() + 1);
+                    // REAPER notifies control surfaces synchronously, so the middleware is
+                    // re-entered
+                    rename(c_str!("C"));
+                    middleware.process(event, |_| {});
+                }
+            });
+            // Then
+            assert_eq!(rename_count.get(), 1);
+            unsafe {
+                low.DeleteProjectMarker(null_mut(), marker_id, false);
+            }
+            Ok(())
+        },
+    )
+}
+
+fn rename_marker() -> Vec<TestStep> {
+    polled_change_steps(
+        "Rename marker",
+        |mock, checked| {
+            // Given
+            let project = Reaper::get().current_project();
+            let marker = project
+                .bookmarks()
+                .find(|b| b.basic_info().bookmark_type() == BookmarkType::Marker)
+                .ok_or("marker not found")?;
+            let info = marker.basic_info();
+            // When
+            Test::control_surface_rx()
+                .marker_renamed()
+                .take_until(checked)
+                .subscribe(move |m| {
+                    mock.invoke(m);
+                });
+            let successful = unsafe {
+                Reaper::get().medium_reaper().low().SetProjectMarker(
+                    info.id.to_raw(),
+                    false,
+                    info.position.get(),
+                    0.0,
+                    c_str!("Chorus").as_ptr(),
+                )
+            };
+            assert!(successful);
+            Ok(info.id)
+        },
+        |mock, id| {
+            // Then
+            assert_eq!(mock.invocation_count(), 1);
+            assert_eq!(mock.last_arg(), (BookmarkType::Marker, id));
+            Ok(())
+        },
+    )
+}
+
+fn add_markers() -> Vec<TestStep> {
+    polled_change_steps(
+        "Add marker and region",
+        |mock, checked| {
+            // Given
+            let low = Reaper::get().medium_reaper().low();
+            // When
+            Test::control_surface_rx()
+                .marker_added()
+                .take_until(checked)
+                .subscribe(move |m| {
+                    mock.invoke(m);
+                });
+            let (marker_id, region_id) = unsafe {
+                let marker_id = low.AddProjectMarker2(
+                    null_mut(),
+                    false,
+                    1.0,
+                    0.0,
+                    c_str!("Verse").as_ptr(),
+                    -1,
+                    0,
+                );
+                let region_id = low.AddProjectMarker2(
+                    null_mut(),
+                    true,
+                    2.0,
+                    4.0,
+                    c_str!("Intro").as_ptr(),
+                    -1,
+                    0,
+                );
+                (marker_id, region_id)
+            };
+            assert!(marker_id >= 0 && region_id >= 0);
+            Ok(BookmarkId::new(region_id as u32))
+        },
+        |mock, region_id| {
+            // Then
+            assert_eq!(mock.invocation_count(), 2);
+            assert_eq!(mock.last_arg(), (BookmarkType::Region, region_id));
+            Ok(())
+        },
+    )
+}
+
+fn remove_item() -> Vec<TestStep> {
+    polled_change_steps(
+        "Remove item",
+        |mock, checked| {
+            // Given
+            let project = Reaper::get().current_project();
+            let item = project.item_by_index(0).ok_or("item not found")?;
+            let track = item.track().ok_or("item has no track")?;
+            let guid = item.guid();
+            // When
+            Test::control_surface_rx()
+                .item_removed()
+                .take_until(checked)
+                .subscribe(move |g| {
+                    mock.invoke(g);
+                });
+            let successful = project.undoable("Remove item", || unsafe {
+                Reaper::get()
+                    .medium_reaper()
+                    .low()
+                    .DeleteTrackMediaItem(track.raw().as_ptr(), item.raw().as_ptr())
+            });
+            assert!(successful);
+            Ok(guid)
+        },
+        |mock, guid| {
+            // Then
+            assert_eq!(Reaper::get().current_project().item_count(), 0);
+            assert_eq!(mock.invocation_count(), 1);
+            assert_eq!(mock.last_arg(), guid);
+            Ok(())
+        },
+    )
+}
+
+fn move_item() -> Vec<TestStep> {
+    polled_change_steps(
+        "Move item",
+        |mock, checked| {
+            // Given
+            let project = Reaper::get().current_project();
+            let item = project.item_by_index(0).ok_or("item not found")?;
+            // When
+            Test::control_surface_rx()
+                .item_moved()
+                .take_until(checked)
+                .subscribe(move |i| {
+                    mock.invoke(i);
+                });
+            project.undoable("Move item", || unsafe {
+                Reaper::get().medium_reaper().low().SetMediaItemPosition(
+                    item.raw().as_ptr(),
+                    3.0,
+                    false,
+                );
+            });
+            Ok(item)
+        },
+        |mock, item| {
+            // Then
+            assert_eq!(item.position(), PositionInSeconds::new(3.0));
+            assert_eq!(mock.invocation_count(), 1);
+            assert_eq!(mock.last_arg(), item);
+            Ok(())
+        },
+    )
+}
+
+fn add_item() -> Vec<TestStep> {
+    polled_change_steps(
+        "Add item",
+        |mock, checked| {
+            // Given
+            let project = Reaper::get().current_project();
+            let track = get_track(0)?;
+            // When
+            Test::control_surface_rx()
+                .item_added()
+                .take_until(checked)
+                .subscribe(move |i| {
+                    mock.invoke(i);
+                });
+            let raw_item = project.undoable("Add item", || unsafe {
+                let low = Reaper::get().medium_reaper().low();
+                let raw_item = low.AddMediaItemToTrack(track.raw().as_ptr());
+                low.SetMediaItemLength(raw_item, 2.0, false);
+                raw_item
+            });
+            let item = Item::new(NonNull::new(raw_item).ok_or("couldn't add item")?);
+            Ok(item)
+        },
+        |mock, item| {
+            // Then
+            assert_eq!(Reaper::get().current_project().item_count(), 1);
+            assert_eq!(item.length(), DurationInSeconds::new(2.0));
+            assert_eq!(mock.invocation_count(), 1);
+            assert_eq!(mock.last_arg(), item);
+            Ok(())
+        },
+    )
 }
 
 fn swell() -> TestStep {
@@ -3463,6 +3741,43 @@ fn add_track_fx_by_original_name(get_fx_chain: GetFxChain) -> TestStep {
     )
 }
 
+/// Creates two steps for checking changes which are detected by polling in
+/// `ChangeDetectionMiddleware::run()`.
+///
+/// Polling happens only after a step has been executed. Therefore the first step subscribes and
+/// makes the change (`when`) and the second one checks the events which have been emitted in the
+/// meantime (`then`). The subscription ends as soon as `checked` emits.
+fn polled_change_steps<T, R, W, C>(name: &'static str, when: W, then: C) -> Vec<TestStep>
+where
+    T: Clone + 'static,
+    R: 'static,
+    W: FnOnce(Rc<InvocationMock<T>>, LocalSubject<'static, (), ()>) -> StepResult<R> + 'static,
+    C: FnOnce(&InvocationMock<T>, R) -> StepResult<()> + 'static,
+{
+    let (mock, shared_mock) = create_invocation_mock();
+    let mut checked = LocalSubject::new();
+    let shared_checked = checked.clone();
+    let state = Rc::new(RefCell::new(None));
+    let shared_state = state.clone();
+    vec![
+        step(AllVersions, name, move |_, _| {
+            let result = when(shared_mock, shared_checked)?;
+            shared_state.replace(Some(result));
+            Ok(())
+        }),
+        step(AllVersions, format!("{} (check)", name), move |_, _| {
+            checked.complete();
+            let result = state
+                .borrow_mut()
+                .take()
+                .ok_or("change has not been made")?;
+            then(&mock, result)
+        }),
+    ]
+}
+
+type StepResult<T> = Result<T, Cow<'static, str>>;
+
 fn get_track(index: u32) -> Result<Track, &'static str> {
     Reaper::get()
         .current_project()